@@ -0,0 +1,213 @@
+// python module `rust_nfp`, coordinates are passed as (n, 2) float arrays or anything numpy converts to one
+
+use numpy::{AllowTypeChange, PyArray1, PyArray2, PyArrayLike2, PyArrayMethods};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rust_nfp::geometry::{BooleanOperation, Point, Polygon, PolygonWithHoles, Path, Transform};
+use rust_nfp::lib::{calculate_nfp, calculate_rectangular_ifp, is_inside_nfp};
+use rust_nfp::nesting::{NestingJob, Nester, Part, Sheet};
+
+const DEFAULT_TOL: f64 = 0.0001;
+
+fn ring_from_array(coords: PyArrayLike2<'_, f64, AllowTypeChange>) -> PyResult<Polygon> {
+    let array = coords.as_array();
+    if array.ncols() != 2 {
+        return Err(PyValueError::new_err("coordinates have to be an array of shape (n, 2)"));
+    }
+    if array.nrows() < 3 {
+        return Err(PyValueError::new_err("a ring needs at least 3 points"));
+    }
+    if array.iter().any(|v| !v.is_finite()) {
+        return Err(PyValueError::new_err("coordinates have to be finite"));
+    }
+
+    Ok(Polygon::from_points(&array.rows().into_iter().map(|r| Point::new().set_values(r[0], r[1])).collect()))
+}
+
+fn ring_to_array<'py>(py: Python<'py>, ring: &Polygon) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let flat = ring.points.iter().flat_map(|p| [p.x, p.y]).collect::<Vec<_>>();
+    PyArray1::from_vec(py, flat).reshape([ring.points.len(), 2])
+}
+
+fn wrap(polys: Vec<PolygonWithHoles>) -> Vec<PyPolygon> {
+    polys.into_iter().map(|shape| PyPolygon{shape}).collect()
+}
+
+/// Polygon(outer, holes=[])
+///
+/// Polygon with an outer boundary and any number of holes, each given as an (n, 2) array of corners
+#[pyclass(name = "Polygon", module = "rust_nfp")]
+#[derive(Clone)]
+struct PyPolygon {
+    shape: PolygonWithHoles
+}
+
+#[pymethods]
+impl PyPolygon {
+    #[new]
+    #[pyo3(signature = (outer, holes = Vec::new()))]
+    fn new(outer: PyArrayLike2<'_, f64, AllowTypeChange>, holes: Vec<PyArrayLike2<'_, f64, AllowTypeChange>>) -> PyResult<PyPolygon> {
+        let outer = ring_from_array(outer)?;
+        let holes = holes.into_iter().map(ring_from_array).collect::<PyResult<Vec<_>>>()?;
+        Ok(PyPolygon{shape: PolygonWithHoles::new(outer, holes)})
+    }
+
+    /// Corners of the outer boundary as an (n, 2) array
+    #[getter]
+    fn outer<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyArray2<f64>>> {
+        ring_to_array(py, &self.shape.outer)
+    }
+
+    /// Corners of all holes, one (n, 2) array per hole
+    #[getter]
+    fn holes<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyArray2<f64>>>> {
+        self.shape.holes.iter().map(|h| ring_to_array(py, h)).collect()
+    }
+
+    /// Area of the outer boundary minus the area of all holes
+    #[getter]
+    fn area(&self) -> f64 {
+        self.shape.calculate_area()
+    }
+
+    /// Bounding box of the outer boundary as (min_x, min_y, max_x, max_y)
+    #[getter]
+    fn bounds(&self) -> (f64, f64, f64, f64) {
+        let bbox = self.shape.bounding_box();
+        (bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y)
+    }
+
+    /// Copy rotated counter-clockwise around the origin by the angle in radians, then moved by (dx, dy)
+    #[pyo3(signature = (dx = 0.0, dy = 0.0, angle = 0.0))]
+    fn transformed(&self, dx: f64, dy: f64, angle: f64) -> PyPolygon {
+        PyPolygon{shape: self.shape.transform(&Transform::rotation(angle).then(&Transform::translation(dx, dy)))}
+    }
+
+    /// Problems of the polygon as readable strings, empty for valid polygons
+    #[pyo3(signature = (tol = DEFAULT_TOL))]
+    fn validate(&self, tol: f64) -> Vec<String> {
+        self.shape.validate(tol).iter().map(|issue| format!("{:?}", issue)).collect()
+    }
+
+    /// Offset polygon, positive distances grow the material, rounded corners are tessellated with chord_error
+    #[pyo3(signature = (distance, chord_error = 0.01, tol = DEFAULT_TOL))]
+    fn offset(&self, distance: f64, chord_error: f64, tol: f64) -> PyResult<PyPolygon> {
+        if chord_error <= 0.0 {
+            return Err(PyValueError::new_err("chord_error has to be positive"));
+        }
+
+        let offset = |ring: &Polygon, d: f64| Path::from_polygon(ring).offset(d, tol).tessellate(chord_error);
+        let holes = self.shape.holes.iter()
+            .map(|h| offset(h, -distance))
+            .filter(|h| h.points.len() >= 3 && h.calculate_area() > tol)
+            .collect();
+        Ok(PyPolygon{shape: PolygonWithHoles::new(offset(&self.shape.outer, distance), holes)})
+    }
+
+    /// Union with other, a list because disjoint polygons stay separate
+    #[pyo3(signature = (other, tol = DEFAULT_TOL))]
+    fn union(&self, other: &PyPolygon, tol: f64) -> Vec<PyPolygon> {
+        wrap(self.shape.boolean(&other.shape, BooleanOperation::Union, tol))
+    }
+
+    /// Intersection with other
+    #[pyo3(signature = (other, tol = DEFAULT_TOL))]
+    fn intersection(&self, other: &PyPolygon, tol: f64) -> Vec<PyPolygon> {
+        wrap(self.shape.boolean(&other.shape, BooleanOperation::Intersection, tol))
+    }
+
+    /// This polygon minus other
+    #[pyo3(signature = (other, tol = DEFAULT_TOL))]
+    fn difference(&self, other: &PyPolygon, tol: f64) -> Vec<PyPolygon> {
+        wrap(self.shape.boolean(&other.shape, BooleanOperation::Difference, tol))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Polygon(<{} points, {} holes>, area={})", self.shape.outer.points.len(), self.shape.holes.len(), self.shape.calculate_area())
+    }
+}
+
+/// nfp(first, other, tol=0.0001)
+///
+/// Nfp of the outer boundaries as a list of convex (n, 2) arrays whose union is the nfp.
+/// The reference point of other is its coordinate origin
+#[pyfunction]
+#[pyo3(signature = (first, other, tol = DEFAULT_TOL))]
+fn nfp<'py>(py: Python<'py>, first: &PyPolygon, other: &PyPolygon, tol: f64) -> PyResult<Vec<Bound<'py, PyArray2<f64>>>> {
+    calculate_nfp(&first.shape.outer, &other.shape.outer, tol).iter()
+        .map(|piece| ring_to_array(py, piece))
+        .collect()
+}
+
+/// is_inside_nfp(nfp, x, y, tol=0.0001)
+///
+/// True if the reference point (x, y) lies strictly inside the nfp pieces, i.e. the polygons overlap
+#[pyfunction]
+#[pyo3(name = "is_inside_nfp", signature = (nfp, x, y, tol = DEFAULT_TOL))]
+fn is_inside(nfp: Vec<PyArrayLike2<'_, f64, AllowTypeChange>>, x: f64, y: f64, tol: f64) -> PyResult<bool> {
+    let pieces = nfp.into_iter().map(ring_from_array).collect::<PyResult<Vec<_>>>()?;
+    Ok(is_inside_nfp(&Point::new().set_values(x, y), &pieces, tol))
+}
+
+/// ifp(container, other, tol=0.0001)
+///
+/// Inner fit rectangle of other in the bounding box of container as (min_x, min_y, max_x, max_y), None if it does not fit
+#[pyfunction]
+#[pyo3(signature = (container, other, tol = DEFAULT_TOL))]
+fn ifp(container: &PyPolygon, other: &PyPolygon, tol: f64) -> Option<(f64, f64, f64, f64)> {
+    calculate_rectangular_ifp(&container.shape.outer, &other.shape.outer, tol)
+        .map(|bbox| (bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y))
+}
+
+/// nest(sheets, parts, spacing=0.0, tol=0.0001)
+///
+/// Bottom-left nesting. sheets is a list of (name, Polygon), parts a list of (name, Polygon, quantity, rotations)
+/// with rotations in radians. Returns a dict with the `placements`, each a dict of part, sheet index, rotation,
+/// translation and the placed shape, the `unplaced` part names and the `utilization` per sheet
+#[pyfunction]
+#[pyo3(signature = (sheets, parts, spacing = 0.0, tol = DEFAULT_TOL))]
+fn nest<'py>(py: Python<'py>, sheets: Vec<(String, PyPolygon)>, parts: Vec<(String, PyPolygon, usize, Vec<f64>)>, spacing: f64, tol: f64) -> PyResult<Bound<'py, PyDict>> {
+    let mut job = NestingJob::new(
+        sheets.into_iter().map(|(name, poly)| Sheet::new(&name, poly.shape)).collect(),
+        parts.into_iter().map(|(name, poly, quantity, rotations)| {
+            let mut part = Part::new(&name, poly.shape, quantity);
+            if !rotations.is_empty() {
+                part.rotations = rotations;
+            }
+            part
+        }).collect());
+    job.spacing = spacing;
+
+    // the nesting itself does not touch python objects
+    let layout = py.detach(|| Nester::nest(&job, tol));
+
+    let placements = layout.placements.iter()
+        .map(|placement| {
+            let entry = PyDict::new(py);
+            entry.set_item("part", &placement.part)?;
+            entry.set_item("sheet", placement.sheet)?;
+            entry.set_item("rotation", placement.rotation)?;
+            entry.set_item("translation", (placement.translation.x, placement.translation.y))?;
+            entry.set_item("shape", PyPolygon{shape: placement.shape.clone()})?;
+            Ok(entry)
+        })
+        .collect::<PyResult<Vec<_>>>()?;
+
+    let result = PyDict::new(py);
+    result.set_item("placements", placements)?;
+    result.set_item("unplaced", &layout.unplaced)?;
+    result.set_item("utilization", (0..layout.sheets.len()).map(|i| layout.calculate_utilization(i)).collect::<Vec<_>>())?;
+    Ok(result)
+}
+
+#[pymodule]
+#[pyo3(name = "rust_nfp")]
+fn rust_nfp_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPolygon>()?;
+    m.add_function(wrap_pyfunction!(nfp, m)?)?;
+    m.add_function(wrap_pyfunction!(is_inside, m)?)?;
+    m.add_function(wrap_pyfunction!(ifp, m)?)?;
+    m.add_function(wrap_pyfunction!(nest, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,154 @@
+# run with `maturin develop` followed by `python -m unittest discover tests`
+
+import math
+import unittest
+
+import numpy as np
+
+import rust_nfp
+
+TOL = 0.0001
+
+
+def square(size, x=0.0, y=0.0):
+    return rust_nfp.Polygon(np.array([[x, y], [x + size, y], [x + size, y + size], [x, y + size]]))
+
+
+class PolygonTests(unittest.TestCase):
+    def test_coordinates_round_trip(self):
+        # Arrange
+        outer = np.array([[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]])
+        hole = [[1, 1], [1, 2], [2, 2], [2, 1]]
+
+        # Act
+        poly = rust_nfp.Polygon(outer, [hole])
+
+        # Assert
+        self.assertEqual(poly.outer.shape, (4, 2))
+        self.assertEqual(poly.outer.dtype, np.float64)
+        np.testing.assert_allclose(poly.outer, outer)
+        self.assertEqual(len(poly.holes), 1)
+        self.assertAlmostEqual(poly.area, 15.0, delta=TOL)
+        self.assertEqual(poly.bounds, (0.0, 0.0, 4.0, 4.0))
+
+    def test_invalid_coordinates_raise(self):
+        with self.assertRaises(ValueError):
+            rust_nfp.Polygon(np.zeros((4, 3)))
+        with self.assertRaises(ValueError):
+            rust_nfp.Polygon([[0, 0], [1, 0]])
+        with self.assertRaises(ValueError):
+            rust_nfp.Polygon([[0, 0], [1, 0], [math.nan, 1]])
+
+    def test_transformed(self):
+        # Arrange
+        poly = square(2.0)
+
+        # Act
+        moved = poly.transformed(dx=1.0, dy=2.0, angle=math.pi / 2.0)
+
+        # Assert
+        min_x, min_y, max_x, max_y = moved.bounds
+        self.assertAlmostEqual(min_x, -1.0, delta=TOL)
+        self.assertAlmostEqual(min_y, 2.0, delta=TOL)
+        self.assertAlmostEqual(max_x, 1.0, delta=TOL)
+        self.assertAlmostEqual(max_y, 4.0, delta=TOL)
+
+    def test_validate(self):
+        bow_tie = rust_nfp.Polygon([[0, 0], [2, 2], [3, 0], [0, 1]])
+
+        self.assertEqual(square(1.0).validate(), [])
+        self.assertTrue(any(issue.startswith("SelfIntersection") for issue in bow_tie.validate()))
+
+    def test_offset(self):
+        # Act
+        grown = square(2.0).offset(1.0, chord_error=0.001)
+
+        # Assert
+        self.assertAlmostEqual(grown.area, 12.0 + math.pi, delta=0.01)
+
+
+class BooleanTests(unittest.TestCase):
+    def test_overlapping_squares(self):
+        # Arrange
+        first = square(2.0)
+        other = square(2.0, 1.0, 1.0)
+
+        # Act
+        union = first.union(other)
+        intersection = first.intersection(other)
+        difference = first.difference(other)
+
+        # Assert
+        self.assertEqual(len(union), 1)
+        self.assertAlmostEqual(union[0].area, 7.0, delta=TOL)
+        self.assertAlmostEqual(intersection[0].area, 1.0, delta=TOL)
+        self.assertAlmostEqual(difference[0].area, 3.0, delta=TOL)
+
+    def test_difference_creates_hole(self):
+        # Act
+        result = square(4.0).difference(square(2.0, 1.0, 1.0))
+
+        # Assert
+        self.assertEqual(len(result), 1)
+        self.assertEqual(len(result[0].holes), 1)
+        self.assertAlmostEqual(result[0].area, 12.0, delta=TOL)
+
+    def test_disjoint_union(self):
+        self.assertEqual(len(square(1.0).union(square(1.0, 5.0, 0.0))), 2)
+        self.assertEqual(square(1.0).intersection(square(1.0, 5.0, 0.0)), [])
+
+
+class NfpTests(unittest.TestCase):
+    def test_nfp_of_squares(self):
+        # Arrange
+        first = square(2.0)
+        other = square(1.0)
+
+        # Act
+        pieces = rust_nfp.nfp(first, other)
+
+        # Assert
+        self.assertEqual(len(pieces), 1)
+        self.assertEqual(pieces[0].shape[1], 2)
+        np.testing.assert_allclose(pieces[0].min(axis=0), [-1.0, -1.0], atol=TOL)
+        np.testing.assert_allclose(pieces[0].max(axis=0), [2.0, 2.0], atol=TOL)
+
+        self.assertTrue(rust_nfp.is_inside_nfp(pieces, 0.5, 0.5))
+        self.assertFalse(rust_nfp.is_inside_nfp(pieces, 2.0, 0.5))
+        self.assertFalse(rust_nfp.is_inside_nfp(pieces, 3.0, 3.0))
+
+    def test_ifp(self):
+        self.assertEqual(rust_nfp.ifp(square(5.0), square(2.0)), (0.0, 0.0, 3.0, 3.0))
+        self.assertIsNone(rust_nfp.ifp(square(1.0), square(2.0)))
+
+
+class NestTests(unittest.TestCase):
+    def test_nest_squares(self):
+        # Arrange
+        sheet = rust_nfp.Polygon([[0, 0], [5, 0], [5, 2], [0, 2]])
+
+        # Act
+        result = rust_nfp.nest([("sheet", sheet)], [("square", square(2.0), 3, [0.0])])
+
+        # Assert
+        self.assertEqual(len(result["placements"]), 2)
+        self.assertEqual(result["unplaced"], ["square"])
+        self.assertAlmostEqual(result["placements"][1]["translation"][0], 3.0, delta=TOL)
+        self.assertAlmostEqual(result["utilization"][0], 0.8, delta=TOL)
+        self.assertIsInstance(result["placements"][0]["shape"], rust_nfp.Polygon)
+
+    def test_nest_with_rotation(self):
+        # Arrange
+        sheet = rust_nfp.Polygon([[0, 0], [1, 0], [1, 3], [0, 3]])
+        bar = rust_nfp.Polygon([[0, 0], [3, 0], [3, 1], [0, 1]])
+
+        # Act
+        result = rust_nfp.nest([("sheet", sheet)], [("bar", bar, 1, [0.0, math.pi / 2.0])])
+
+        # Assert
+        self.assertEqual(result["unplaced"], [])
+        self.assertAlmostEqual(result["placements"][0]["rotation"], math.pi / 2.0, delta=TOL)
+
+
+if __name__ == "__main__":
+    unittest.main()
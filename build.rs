@@ -0,0 +1,23 @@
+// generates the C header of the `capi` feature from the exported functions into OUT_DIR,
+// the committed include/rust_nfp.h is only overwritten when RUST_NFP_UPDATE_HEADER is set
+
+fn main() {
+    #[cfg(feature = "capi")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let out_dir = std::env::var("OUT_DIR").unwrap();
+        println!("cargo:rerun-if-changed=src/capi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        println!("cargo:rerun-if-env-changed=RUST_NFP_UPDATE_HEADER");
+
+        let header = cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_config(cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap())
+            .generate()
+            .expect("unable to generate the C header");
+        header.write_to_file(format!("{}/rust_nfp.h", out_dir));
+        if std::env::var_os("RUST_NFP_UPDATE_HEADER").is_some() {
+            header.write_to_file(format!("{}/include/rust_nfp.h", crate_dir));
+        }
+    }
+}
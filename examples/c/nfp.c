@@ -0,0 +1,55 @@
+// nfp of a square and a triangle through the C interface
+//
+// cargo rustc --lib --release --features capi --crate-type staticlib
+// cc examples/c/nfp.c -Iinclude target/release/librust_nfp.a -lpthread -ldl -lm -o nfp
+
+#include <stdio.h>
+#include <stdlib.h>
+#include "rust_nfp.h"
+
+int main(void) {
+    const double square[] = {-1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0};
+    const double triangle[] = {0.0, 0.0, 2.0, 2.0, -2.0, 2.0};
+    const double tol = 0.0001;
+
+    NfpPolygon *first = NULL;
+    NfpPolygon *other = NULL;
+    NfpPolygonList *nfp = NULL;
+    if (nfp_polygon_create(square, 4, &first) != NFP_ERROR_OK ||
+        nfp_polygon_create(triangle, 3, &other) != NFP_ERROR_OK ||
+        nfp_calculate_nfp(first, other, tol, &nfp) != NFP_ERROR_OK) {
+        fprintf(stderr, "nfp calculation failed\n");
+        return 1;
+    }
+
+    size_t count = 0;
+    nfp_polygon_list_count(nfp, &count);
+    for (size_t i = 0; i < count; i++) {
+        NfpPolygon *piece = NULL;
+        nfp_polygon_list_get(nfp, i, &piece);
+
+        // query the size first, then fill a buffer of our own
+        size_t point_count = 0;
+        nfp_polygon_ring_points(piece, 0, NULL, 0, &point_count);
+        double *coords = malloc(2 * point_count * sizeof(double));
+        nfp_polygon_ring_points(piece, 0, coords, point_count, &point_count);
+
+        printf("piece %zu:", i);
+        for (size_t n = 0; n < point_count; n++) {
+            printf(" (%g, %g)", coords[2 * n], coords[2 * n + 1]);
+        }
+        printf("\n");
+
+        free(coords);
+        nfp_polygon_free(piece);
+    }
+
+    bool inside = false;
+    nfp_is_inside_nfp(nfp, 0.0, 0.0, tol, &inside);
+    printf("overlapping at the origin: %s\n", inside ? "yes" : "no");
+
+    nfp_polygon_list_free(nfp);
+    nfp_polygon_free(other);
+    nfp_polygon_free(first);
+    return 0;
+}
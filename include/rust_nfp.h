@@ -0,0 +1,152 @@
+#ifndef RUST_NFP_H
+#define RUST_NFP_H
+
+/* Generated by cbindgen from src/capi.rs with the `capi` feature, do not edit by hand */
+
+#include <stdbool.h>
+#include <stddef.h>
+
+// Result codes of all C functions
+typedef enum NfpError {
+  NFP_ERROR_OK = 0,
+  NFP_ERROR_NULL_POINTER = 1,
+  NFP_ERROR_INVALID_ARGUMENT = 2,
+  NFP_ERROR_BUFFER_TOO_SMALL = 3,
+  NFP_ERROR_NO_RESULT = 4,
+  NFP_ERROR_PANIC = 5,
+} NfpError;
+
+// Opaque polygon with holes
+typedef struct NfpPolygon NfpPolygon;
+
+// Opaque list of polygons, e.g. the convex pieces of an nfp
+typedef struct NfpPolygonList NfpPolygonList;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a polygon without holes from `point_count` interleaved coordinates
+//
+// # Safety
+// `coords` has to point to `2 * point_count` doubles and `out` to writable memory
+enum NfpError nfp_polygon_create(const double *coords, size_t point_count, struct NfpPolygon **out);
+
+// Adds a hole from `point_count` interleaved coordinates
+//
+// # Safety
+// `poly` has to be a live handle and `coords` has to point to `2 * point_count` doubles
+enum NfpError nfp_polygon_add_hole(struct NfpPolygon *poly,
+                                   const double *coords,
+                                   size_t point_count);
+
+// Creates an independent copy of a polygon
+//
+// # Safety
+// `poly` has to be a live handle and `out` has to point to writable memory
+enum NfpError nfp_polygon_clone(const struct NfpPolygon *poly, struct NfpPolygon **out);
+
+// Releases a polygon, null is ignored
+//
+// # Safety
+// `poly` has to be null or a live handle, which is invalid afterwards
+void nfp_polygon_free(struct NfpPolygon *poly);
+
+// Number of rings, the outer boundary followed by all holes
+//
+// # Safety
+// `poly` has to be a live handle and `count` has to point to writable memory
+enum NfpError nfp_polygon_ring_count(const struct NfpPolygon *poly, size_t *count);
+
+// Copies the corners of a ring into a caller-owned buffer of `capacity` points, ring 0 is the outer boundary
+//
+// # Safety
+// `poly` has to be a live handle, `buffer` null or pointing to `2 * capacity` doubles and `written` to writable memory
+enum NfpError nfp_polygon_ring_points(const struct NfpPolygon *poly,
+                                      size_t ring,
+                                      double *buffer,
+                                      size_t capacity,
+                                      size_t *written);
+
+// Area of the outer boundary minus the area of all holes
+//
+// # Safety
+// `poly` has to be a live handle and `area` has to point to writable memory
+enum NfpError nfp_polygon_area(const struct NfpPolygon *poly, double *area);
+
+// Offsets a polygon, positive distances grow the material and shrink the holes
+// Rounded corners are tessellated with the given chord error, holes that vanish are dropped
+//
+// # Safety
+// `poly` has to be a live handle and `out` has to point to writable memory
+enum NfpError nfp_polygon_offset(const struct NfpPolygon *poly,
+                                 double distance,
+                                 double chord_error,
+                                 double tol,
+                                 struct NfpPolygon **out);
+
+// Intersection points of the boundaries of two polygons, copied into a caller-owned buffer of `capacity` points
+//
+// # Safety
+// `first` and `other` have to be live handles, `buffer` null or pointing to `2 * capacity` doubles and `written` to writable memory
+enum NfpError nfp_polygon_intersections(const struct NfpPolygon *first,
+                                        const struct NfpPolygon *other,
+                                        double tol,
+                                        double *buffer,
+                                        size_t capacity,
+                                        size_t *written);
+
+// Nfp of the outer boundaries of two polygons as a list of convex pieces, whose union is the nfp
+// The reference point of other is its coordinate origin
+//
+// # Safety
+// `first` and `other` have to be live handles and `out` has to point to writable memory
+enum NfpError nfp_calculate_nfp(const struct NfpPolygon *first,
+                                const struct NfpPolygon *other,
+                                double tol,
+                                struct NfpPolygonList **out);
+
+// Inner fit rectangle of other inside the bounding rectangle of container, `NFP_ERROR_NO_RESULT` if it does not fit
+//
+// # Safety
+// `container` and `other` have to be live handles and `out` has to point to writable memory
+enum NfpError nfp_calculate_ifp(const struct NfpPolygon *container,
+                                const struct NfpPolygon *other,
+                                double tol,
+                                struct NfpPolygon **out);
+
+// Tests if a reference point lies strictly inside an nfp, i.e. if the placed polygons overlap
+//
+// # Safety
+// `nfp` has to be a live list handle and `inside` has to point to writable memory
+enum NfpError nfp_is_inside_nfp(const struct NfpPolygonList *nfp,
+                                double x,
+                                double y,
+                                double tol,
+                                bool *inside);
+
+// Number of polygons in a list
+//
+// # Safety
+// `list` has to be a live list handle and `count` has to point to writable memory
+enum NfpError nfp_polygon_list_count(const struct NfpPolygonList *list, size_t *count);
+
+// Copies a polygon out of a list, the new handle is owned by the caller
+//
+// # Safety
+// `list` has to be a live list handle and `out` has to point to writable memory
+enum NfpError nfp_polygon_list_get(const struct NfpPolygonList *list,
+                                   size_t index,
+                                   struct NfpPolygon **out);
+
+// Releases a list and all polygons in it, null is ignored
+//
+// # Safety
+// `list` has to be null or a live list handle, which is invalid afterwards
+void nfp_polygon_list_free(struct NfpPolygonList *list);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUST_NFP_H */
@@ -0,0 +1,319 @@
+// C interface of the library, built with the `capi` feature
+//
+// The package only builds an rlib, the C library is built on request with
+// `cargo rustc --lib --release --features capi --crate-type staticlib` or `--crate-type cdylib`.
+//
+// Polygons are passed around as opaque handles that the caller releases with the matching `_free` function.
+// Coordinates are interleaved `x, y` pairs of doubles, buffer capacities and counts are given in points.
+// Functions filling caller-owned buffers always report the required size, so a first call with a null
+// buffer queries the size and a second call fills it. Every function returns an `NfpError`,
+// panics are caught at the boundary and reported as `NFP_ERROR_PANIC`
+
+use super::geometry::{Point, Polygon, PolygonWithHoles, Path, Intersection, PolygonPolygonIntersectionResult};
+use super::lib::{calculate_nfp, calculate_rectangular_ifp, is_inside_nfp};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Result codes of all C functions
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NfpError {
+    Ok = 0,
+    NullPointer = 1, // a required pointer argument was null
+    InvalidArgument = 2, // index out of range, too few points or a non-finite number
+    BufferTooSmall = 3, // the output buffer cannot hold the result, the required size was written
+    NoResult = 4, // the query has no result, e.g. a part that does not fit its container
+    Panic = 5 // internal error, the library state is unchanged
+}
+
+/// Opaque polygon with holes
+pub struct NfpPolygon {
+    shape: PolygonWithHoles
+}
+
+/// Opaque list of polygons, e.g. the convex pieces of an nfp
+pub struct NfpPolygonList {
+    polygons: Vec<PolygonWithHoles>
+}
+
+// private panic barrier, no unwinding may cross the C boundary
+fn guard<F: FnOnce() -> Result<(), NfpError>>(body: F) -> NfpError {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => NfpError::Ok,
+        Ok(Err(error)) => error,
+        Err(_) => NfpError::Panic
+    }
+}
+
+unsafe fn borrow<'a, T>(ptr: *const T) -> Result<&'a T, NfpError> {
+    ptr.as_ref().ok_or(NfpError::NullPointer)
+}
+
+unsafe fn borrow_mut<'a, T>(ptr: *mut T) -> Result<&'a mut T, NfpError> {
+    ptr.as_mut().ok_or(NfpError::NullPointer)
+}
+
+unsafe fn write_out<T>(out: *mut T, value: T) -> Result<(), NfpError> {
+    if out.is_null() {
+        return Err(NfpError::NullPointer);
+    }
+    *out = value;
+    Ok(())
+}
+
+unsafe fn read_ring(coords: *const f64, point_count: usize) -> Result<Polygon, NfpError> {
+    if coords.is_null() {
+        return Err(NfpError::NullPointer);
+    }
+    if point_count < 3 {
+        return Err(NfpError::InvalidArgument);
+    }
+
+    let values = std::slice::from_raw_parts(coords, 2 * point_count);
+    if values.iter().any(|v| !v.is_finite()) {
+        return Err(NfpError::InvalidArgument);
+    }
+    Ok(Polygon::from_points(&values.chunks(2).map(|c| Point::new().set_values(c[0], c[1])).collect()))
+}
+
+// private copy into a caller-owned buffer, `written` always receives the required number of points
+unsafe fn write_points(pts: &[Point], buffer: *mut f64, capacity: usize, written: *mut usize) -> Result<(), NfpError> {
+    write_out(written, pts.len())?;
+    if buffer.is_null() {
+        return Ok(());
+    }
+    if capacity < pts.len() {
+        return Err(NfpError::BufferTooSmall);
+    }
+
+    let values = std::slice::from_raw_parts_mut(buffer, 2 * pts.len());
+    for (chunk, pt) in values.chunks_mut(2).zip(pts.iter()) {
+        chunk[0] = pt.x;
+        chunk[1] = pt.y;
+    }
+    Ok(())
+}
+
+fn into_handle(shape: PolygonWithHoles) -> *mut NfpPolygon {
+    Box::into_raw(Box::new(NfpPolygon{shape}))
+}
+
+/// Creates a polygon without holes from `point_count` interleaved coordinates
+///
+/// # Safety
+/// `coords` has to point to `2 * point_count` doubles and `out` to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_create(coords: *const f64, point_count: usize, out: *mut *mut NfpPolygon) -> NfpError {
+    guard(|| {
+        let out = borrow_mut(out)?;
+        let outer = read_ring(coords, point_count)?;
+        *out = into_handle(PolygonWithHoles::from_outer(outer));
+        Ok(())
+    })
+}
+
+/// Adds a hole from `point_count` interleaved coordinates
+///
+/// # Safety
+/// `poly` has to be a live handle and `coords` has to point to `2 * point_count` doubles
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_add_hole(poly: *mut NfpPolygon, coords: *const f64, point_count: usize) -> NfpError {
+    guard(|| {
+        let poly = borrow_mut(poly)?;
+        poly.shape.holes.push(read_ring(coords, point_count)?);
+        Ok(())
+    })
+}
+
+/// Creates an independent copy of a polygon
+///
+/// # Safety
+/// `poly` has to be a live handle and `out` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_clone(poly: *const NfpPolygon, out: *mut *mut NfpPolygon) -> NfpError {
+    guard(|| {
+        let (poly, out) = (borrow(poly)?, borrow_mut(out)?);
+        *out = into_handle(poly.shape.clone());
+        Ok(())
+    })
+}
+
+/// Releases a polygon, null is ignored
+///
+/// # Safety
+/// `poly` has to be null or a live handle, which is invalid afterwards
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_free(poly: *mut NfpPolygon) {
+    if !poly.is_null() {
+        drop(Box::from_raw(poly));
+    }
+}
+
+/// Number of rings, the outer boundary followed by all holes
+///
+/// # Safety
+/// `poly` has to be a live handle and `count` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_ring_count(poly: *const NfpPolygon, count: *mut usize) -> NfpError {
+    guard(|| {
+        let poly = borrow(poly)?;
+        write_out(count, 1 + poly.shape.holes.len())
+    })
+}
+
+/// Copies the corners of a ring into a caller-owned buffer of `capacity` points, ring 0 is the outer boundary
+///
+/// # Safety
+/// `poly` has to be a live handle, `buffer` null or pointing to `2 * capacity` doubles and `written` to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_ring_points(poly: *const NfpPolygon, ring: usize, buffer: *mut f64, capacity: usize, written: *mut usize) -> NfpError {
+    guard(|| {
+        let poly = borrow(poly)?;
+        let ring = poly.shape.rings().nth(ring).ok_or(NfpError::InvalidArgument)?;
+        write_points(&ring.points, buffer, capacity, written)
+    })
+}
+
+/// Area of the outer boundary minus the area of all holes
+///
+/// # Safety
+/// `poly` has to be a live handle and `area` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_area(poly: *const NfpPolygon, area: *mut f64) -> NfpError {
+    guard(|| {
+        let poly = borrow(poly)?;
+        write_out(area, poly.shape.calculate_area())
+    })
+}
+
+/// Offsets a polygon, positive distances grow the material and shrink the holes
+/// Rounded corners are tessellated with the given chord error, holes that vanish are dropped
+///
+/// # Safety
+/// `poly` has to be a live handle and `out` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_offset(poly: *const NfpPolygon, distance: f64, chord_error: f64, tol: f64, out: *mut *mut NfpPolygon) -> NfpError {
+    guard(|| {
+        let (poly, out) = (borrow(poly)?, borrow_mut(out)?);
+        if !distance.is_finite() || chord_error <= 0.0 || tol <= 0.0 {
+            return Err(NfpError::InvalidArgument);
+        }
+
+        let offset = |ring: &Polygon, d: f64| Path::from_polygon(ring).offset(d, tol).tessellate(chord_error);
+        let outer = offset(&poly.shape.outer, distance);
+        if outer.points.len() < 3 || outer.calculate_area() <= tol {
+            return Err(NfpError::NoResult);
+        }
+
+        let holes = poly.shape.holes.iter()
+            .map(|h| offset(h, -distance))
+            .filter(|h| h.points.len() >= 3 && h.calculate_area() > tol)
+            .collect();
+        *out = into_handle(PolygonWithHoles::new(outer, holes));
+        Ok(())
+    })
+}
+
+/// Intersection points of the boundaries of two polygons, copied into a caller-owned buffer of `capacity` points
+///
+/// # Safety
+/// `first` and `other` have to be live handles, `buffer` null or pointing to `2 * capacity` doubles and `written` to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_intersections(first: *const NfpPolygon, other: *const NfpPolygon, tol: f64, buffer: *mut f64, capacity: usize, written: *mut usize) -> NfpError {
+    guard(|| {
+        let (first, other) = (borrow(first)?, borrow(other)?);
+
+        let mut pts: Vec<Point> = Vec::new();
+        for first_ring in first.shape.rings() {
+            for other_ring in other.shape.rings() {
+                match Intersection::polygon_polygon(first_ring, other_ring, tol) {
+                    PolygonPolygonIntersectionResult::None => (),
+                    PolygonPolygonIntersectionResult::Point(pt) => pts.push(pt),
+                    PolygonPolygonIntersectionResult::Multiple(found) => pts.extend(found)
+                }
+            }
+        }
+
+        write_points(&pts, buffer, capacity, written)
+    })
+}
+
+/// Nfp of the outer boundaries of two polygons as a list of convex pieces, whose union is the nfp
+/// The reference point of other is its coordinate origin
+///
+/// # Safety
+/// `first` and `other` have to be live handles and `out` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_calculate_nfp(first: *const NfpPolygon, other: *const NfpPolygon, tol: f64, out: *mut *mut NfpPolygonList) -> NfpError {
+    guard(|| {
+        let (first, other, out) = (borrow(first)?, borrow(other)?, borrow_mut(out)?);
+        let polygons = calculate_nfp(&first.shape.outer, &other.shape.outer, tol).into_iter()
+            .map(PolygonWithHoles::from_outer)
+            .collect();
+        *out = Box::into_raw(Box::new(NfpPolygonList{polygons}));
+        Ok(())
+    })
+}
+
+/// Inner fit rectangle of other inside the bounding rectangle of container, `NFP_ERROR_NO_RESULT` if it does not fit
+///
+/// # Safety
+/// `container` and `other` have to be live handles and `out` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_calculate_ifp(container: *const NfpPolygon, other: *const NfpPolygon, tol: f64, out: *mut *mut NfpPolygon) -> NfpError {
+    guard(|| {
+        let (container, other, out) = (borrow(container)?, borrow(other)?, borrow_mut(out)?);
+        let ifp = calculate_rectangular_ifp(&container.shape.outer, &other.shape.outer, tol).ok_or(NfpError::NoResult)?;
+        *out = into_handle(PolygonWithHoles::from_outer(ifp.as_polygon()));
+        Ok(())
+    })
+}
+
+/// Tests if a reference point lies strictly inside an nfp, i.e. if the placed polygons overlap
+///
+/// # Safety
+/// `nfp` has to be a live list handle and `inside` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_is_inside_nfp(nfp: *const NfpPolygonList, x: f64, y: f64, tol: f64, inside: *mut bool) -> NfpError {
+    guard(|| {
+        let nfp = borrow(nfp)?;
+        let pieces = nfp.polygons.iter().map(|p| p.outer.clone()).collect::<Vec<_>>();
+        write_out(inside, is_inside_nfp(&Point::new().set_values(x, y), &pieces, tol))
+    })
+}
+
+/// Number of polygons in a list
+///
+/// # Safety
+/// `list` has to be a live list handle and `count` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_list_count(list: *const NfpPolygonList, count: *mut usize) -> NfpError {
+    guard(|| {
+        let list = borrow(list)?;
+        write_out(count, list.polygons.len())
+    })
+}
+
+/// Copies a polygon out of a list, the new handle is owned by the caller
+///
+/// # Safety
+/// `list` has to be a live list handle and `out` has to point to writable memory
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_list_get(list: *const NfpPolygonList, index: usize, out: *mut *mut NfpPolygon) -> NfpError {
+    guard(|| {
+        let (list, out) = (borrow(list)?, borrow_mut(out)?);
+        let poly = list.polygons.get(index).ok_or(NfpError::InvalidArgument)?;
+        *out = into_handle(poly.clone());
+        Ok(())
+    })
+}
+
+/// Releases a list and all polygons in it, null is ignored
+///
+/// # Safety
+/// `list` has to be null or a live list handle, which is invalid afterwards
+#[no_mangle]
+pub unsafe extern "C" fn nfp_polygon_list_free(list: *mut NfpPolygonList) {
+    if !list.is_null() {
+        drop(Box::from_raw(list));
+    }
+}
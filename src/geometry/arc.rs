@@ -0,0 +1,262 @@
+use super::{Point, Vector, BoundingBox};
+use std::f64::consts::PI;
+
+// finest tessellation, used when the chord error gives no positive step
+const MAX_SEGMENTS_PER_TURN: usize = 4096;
+
+/// Circular arc around `center`, starting at `start_angle` and sweeping by `sweep`
+/// A positive sweep runs counter-clockwise, a sweep of 2PI is a full circle
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Arc {
+    pub center: Point,
+    pub radius: f64,
+    pub start_angle: f64, // angle of the start point to the x-axis, in radians
+    pub sweep: f64 // signed opening angle, between -2PI and 2PI
+}
+
+impl Arc {
+    // public constructor from center, radius and angles
+    pub fn new(center: &Point, radius: f64, start_angle: f64, sweep: f64) -> Arc {
+        Arc{center: *center, radius, start_angle, sweep}
+    }
+
+    // public constructor for a full counter-clockwise circle, starting on the positive x-axis
+    pub fn circle(center: &Point, radius: f64) -> Arc {
+        Arc::new(center, radius, 0.0, 2.0 * PI)
+    }
+
+    /// Arc running from `from` over `through` to `to`
+    /// Returns `None` if the three points are colinear under tol
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Arc, Point};
+    /// use std::f64::consts::PI;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let arc = Arc::new_from_points(
+    ///     &Point::new().set_values(1.0, 0.0),
+    ///     &Point::new().set_values(0.0, -1.0),
+    ///     &Point::new().set_values(-1.0, 0.0),
+    ///     tol).unwrap();
+    ///
+    /// assert!(arc.center.epsilon_equals(&Point::new(), tol));
+    /// assert!((arc.sweep + PI).abs() < tol); // clockwise half circle
+    /// ```
+    pub fn new_from_points(from: &Point, through: &Point, to: &Point, tol: f64) -> Option<Arc> {
+        let ab = Vector::new_from_points(from, through);
+        let ac = Vector::new_from_points(from, to);
+        let cross = Vector::cross_product(&ab, &ac);
+        if cross.abs() < tol {
+            return None;
+        }
+
+        // circumcenter relative to from
+        let ab_sq = ab.dot_product(&ab);
+        let ac_sq = ac.dot_product(&ac);
+        let center = Point::new().set_values(
+            from.x + (ac.y * ab_sq - ab.y * ac_sq) / (2.0 * cross),
+            from.y + (ab.x * ac_sq - ac.x * ab_sq) / (2.0 * cross));
+
+        let start_angle = angle_of(&center, from);
+        let end_angle = angle_of(&center, to);
+        let sweep = if cross > 0.0 {
+            normalize_angle(end_angle - start_angle)
+        } else {
+            -normalize_angle(start_angle - end_angle)
+        };
+
+        Some(Arc::new(&center, center.distance_to(from), start_angle, sweep))
+    }
+
+    /// Arc from `from` to `to` with a bulge factor, as used by DXF polylines
+    /// The bulge is the tangent of a quarter of the sweep, positive bulges run counter-clockwise
+    /// Returns `None` for a zero bulge or coincident end points, which describe a straight line
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Arc, Point};
+    /// use std::f64::consts::PI;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let arc = Arc::new_from_bulge(&Point::new().set_values(1.0, 0.0), &Point::new().set_values(-1.0, 0.0), 1.0).unwrap();
+    ///
+    /// assert!(arc.center.epsilon_equals(&Point::new(), tol));
+    /// assert!((arc.sweep - PI).abs() < tol);
+    /// assert!(arc.point_at_normalized_parameter(0.5).epsilon_equals(&Point::new().set_values(0.0, 1.0), tol));
+    /// ```
+    pub fn new_from_bulge(from: &Point, to: &Point, bulge: f64) -> Option<Arc> {
+        let chord = Vector::new_from_points(from, to);
+        let len = chord.calculate_length();
+        if bulge == 0.0 || len == 0.0 {
+            return None;
+        }
+
+        let sweep = 4.0 * bulge.atan();
+        let radius = len / (2.0 * (sweep / 2.0).sin().abs());
+
+        // signed distance of the center to the left of the chord
+        let apothem = len * (1.0 - bulge * bulge) / (4.0 * bulge);
+        let center = Point::new().set_values(
+            (from.x + to.x) / 2.0 - chord.y / len * apothem,
+            (from.y + to.y) / 2.0 + chord.x / len * apothem);
+
+        Some(Arc::new(&center, radius, angle_of(&center, from), sweep))
+    }
+
+    pub fn end_angle(&self) -> f64 {
+        self.start_angle + self.sweep
+    }
+
+    pub fn start_point(&self) -> Point {
+        self.point_at_angle(self.start_angle)
+    }
+
+    pub fn end_point(&self) -> Point {
+        self.point_at_angle(self.end_angle())
+    }
+
+    // public evaluation, 0.0 gives the start and 1.0 the end point
+    pub fn point_at_normalized_parameter(&self, t: f64) -> Point {
+        self.point_at_angle(self.start_angle + t * self.sweep)
+    }
+
+    // public unit tangent in running direction, see `point_at_normalized_parameter`
+    pub fn tangent_at_normalized_parameter(&self, t: f64) -> Vector {
+        let angle = self.start_angle + t * self.sweep;
+        let sign = if self.sweep < 0.0 {-1.0} else {1.0};
+        Vector::new().set_values(-sign * angle.sin(), sign * angle.cos())
+    }
+
+    pub fn calculate_length(&self) -> f64 {
+        self.radius * self.sweep.abs()
+    }
+
+    // public full circle check under tol
+    pub fn is_full_circle(&self, tol: f64) -> bool {
+        self.sweep.abs() >= 2.0 * PI - tol
+    }
+
+    // public copy running in the other direction
+    pub fn reversed(&self) -> Arc {
+        Arc::new(&self.center, self.radius, self.end_angle(), -self.sweep)
+    }
+
+    /// Exact axis-aligned bounding box, including the extreme points of the circle inside the sweep
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Arc, Point};
+    /// use std::f64::consts::PI;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// // quarter arc over the top of the unit circle
+    /// let arc = Arc::new(&Point::new(), 1.0, PI / 4.0, PI / 2.0);
+    /// let bbox = arc.bounding_box();
+    ///
+    /// assert!((bbox.max.y - 1.0).abs() < tol);
+    /// assert!((bbox.width() - 2.0_f64.sqrt()).abs() < tol);
+    /// ```
+    pub fn bounding_box(&self) -> BoundingBox {
+        let mut bbox = BoundingBox::new_from_points(&[self.start_point(), self.end_point()]);
+        for n in 0..4 {
+            let angle = n as f64 * PI / 2.0;
+            if self.contains_angle(angle) {
+                bbox.include_point(&self.point_at_angle(angle));
+            }
+        }
+        bbox
+    }
+
+    /// Concentric arc with the radius grown by `distance`, negative distances shrink it
+    /// Returns `None` if the radius would collapse
+    pub fn offset(&self, distance: f64) -> Option<Arc> {
+        if self.radius + distance <= 0.0 {
+            return None;
+        }
+
+        Some(Arc::new(&self.center, self.radius + distance, self.start_angle, self.sweep))
+    }
+
+    /// Number of chords needed so no chord deviates more than `chord_error` from the arc
+    /// A full circle is split into at least three and at most 4096 chords, the upper limit is also used
+    /// for chord errors of zero or below and for arcs without a positive radius
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Arc, Point};
+    ///
+    /// let circle = Arc::circle(&Point::new(), 1.0);
+    /// assert_eq!(circle.segment_count(10.0), 3);
+    /// assert_eq!(circle.segment_count(0.0), 4096);
+    /// ```
+    pub fn segment_count(&self, chord_error: f64) -> usize {
+        // the sagitta r * (1 - cos(step / 2)) of every chord stays below the chord error
+        let ratio = (chord_error / self.radius).clamp(0.0, 1.0);
+        let mut step = (2.0 * (1.0 - ratio).acos()).min(2.0 * PI / 3.0);
+        let min_step = 2.0 * PI / MAX_SEGMENTS_PER_TURN as f64;
+        if step.is_nan() || step < min_step {
+            step = min_step;
+        }
+
+        ((self.sweep.abs() / step).ceil() as usize).max(1)
+    }
+
+    /// Points along the arc, including start and end point, with chords deviating at most `chord_error`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Arc, Point};
+    ///
+    /// let arc = Arc::circle(&Point::new(), 10.0);
+    /// let pts = arc.tessellate(0.1);
+    ///
+    /// assert_eq!(pts.len(), arc.segment_count(0.1) + 1);
+    /// assert_eq!(pts.first(), pts.last());
+    /// ```
+    pub fn tessellate(&self, chord_error: f64) -> Vec<Point> {
+        let count = self.segment_count(chord_error);
+        let mut pts = (0..count)
+            .map(|n| self.point_at_normalized_parameter(n as f64 / count as f64))
+            .collect::<Vec<_>>();
+
+        // hit the end exactly, full circles close on their start point
+        pts.push(if self.is_full_circle(0.0) {pts[0]} else {self.end_point()});
+        pts
+    }
+
+    // public sweep check, true if the ray from the center at the given angle hits the arc
+    pub fn contains_angle(&self, angle: f64) -> bool {
+        if self.sweep >= 0.0 {
+            normalize_angle(angle - self.start_angle) <= self.sweep
+        } else {
+            normalize_angle(self.start_angle - angle) <= -self.sweep
+        }
+    }
+
+    fn point_at_angle(&self, angle: f64) -> Point {
+        Point::new().set_values(
+            self.center.x + self.radius * angle.cos(),
+            self.center.y + self.radius * angle.sin())
+    }
+}
+
+// angle of pt seen from center, to the x-axis
+pub(super) fn angle_of(center: &Point, pt: &Point) -> f64 {
+    (pt.y - center.y).atan2(pt.x - center.x)
+}
+
+// angle mapped into 0 to 2PI
+pub(super) fn normalize_angle(angle: f64) -> f64 {
+    let angle = angle % (2.0 * PI);
+    if angle < 0.0 {angle + 2.0 * PI} else {angle}
+}
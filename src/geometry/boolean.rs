@@ -0,0 +1,233 @@
+use super::{Point, Polygon, PolygonWithHoles, LineSegment};
+
+// boolean operations on polygon with holes
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BooleanOperation {
+    Union,
+    Intersection,
+    Difference // first minus other
+}
+
+// position of a boundary piece relative to the region of the other polygon
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum EdgeClass {
+    Inside,
+    Outside,
+    SameBoundary, // on the boundary of the other polygon, both regions on the same side
+    OppositeBoundary // on the boundary of the other polygon, regions on different sides
+}
+
+impl PolygonWithHoles {
+    /// Union, intersection or difference of two polygon with holes
+    /// All boundaries are split at their crossings, the pieces are selected by their position relative to the
+    /// other region and chained into rings again, which are nested by the even-odd rule.
+    /// The result is a list because unions of disjoint inputs and differences may fall apart
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles, BooleanOperation, Transform};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let first = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// let other = first.transform(&Transform::translation(1.0, 1.0));
+    ///
+    /// let union = first.boolean(&other, BooleanOperation::Union, tol);
+    /// assert_eq!(union.len(), 1);
+    /// assert!((union[0].calculate_area() - 7.0).abs() < tol);
+    ///
+    /// let intersection = first.boolean(&other, BooleanOperation::Intersection, tol);
+    /// assert!((intersection[0].calculate_area() - 1.0).abs() < tol);
+    ///
+    /// let difference = first.boolean(&other, BooleanOperation::Difference, tol);
+    /// assert!((difference[0].calculate_area() - 3.0).abs() < tol);
+    /// assert_eq!(difference[0].outer.points.len(), 6);
+    /// ```
+    pub fn boolean(&self, other: &PolygonWithHoles, operation: BooleanOperation, tol: f64) -> Vec<PolygonWithHoles> {
+        let mut first = self.clone();
+        first.normalize_orientation();
+        let mut other = other.clone();
+        other.normalize_orientation();
+
+        // with the region on the left of every edge, kept pieces chain into correctly oriented rings
+        let mut pieces: Vec<(Point, Point)> = Vec::new();
+        for (from, to) in split_boundary(&first, &other, tol) {
+            let keep = matches!((operation, classify(&from, &to, &other, tol)),
+                (BooleanOperation::Union, EdgeClass::Outside) |
+                (BooleanOperation::Union, EdgeClass::SameBoundary) |
+                (BooleanOperation::Intersection, EdgeClass::Inside) |
+                (BooleanOperation::Intersection, EdgeClass::SameBoundary) |
+                (BooleanOperation::Difference, EdgeClass::Outside) |
+                (BooleanOperation::Difference, EdgeClass::OppositeBoundary));
+            if keep {
+                pieces.push((from, to));
+            }
+        }
+
+        // shared boundaries were taken from first already
+        for (from, to) in split_boundary(&other, &first, tol) {
+            match (operation, classify(&from, &to, &first, tol)) {
+                (BooleanOperation::Union, EdgeClass::Outside) => pieces.push((from, to)),
+                (BooleanOperation::Intersection, EdgeClass::Inside) => pieces.push((from, to)),
+                (BooleanOperation::Difference, EdgeClass::Inside) => pieces.push((to, from)),
+                _ => ()
+            }
+        }
+
+        PolygonWithHoles::from_rings(chain_rings(pieces, tol), tol)
+    }
+}
+
+fn cross(ax: f64, ay: f64, bx: f64, by: f64) -> f64 {
+    ax * by - ay * bx
+}
+
+// private boundary pieces of poly, split at every point where the boundary of other touches or crosses it
+fn split_boundary(poly: &PolygonWithHoles, other: &PolygonWithHoles, tol: f64) -> Vec<(Point, Point)> {
+    let other_edges = other.rings().flat_map(|r| r.calculate_edges()).collect::<Vec<_>>();
+    let mut pieces = Vec::new();
+
+    for edge in poly.rings().flat_map(|r| r.calculate_edges()) {
+        let (a, b) = (edge.from, edge.to);
+        let (rx, ry) = (b.x - a.x, b.y - a.y);
+        let length = (rx * rx + ry * ry).sqrt();
+        if length <= tol {
+            continue;
+        }
+
+        let mut params = vec![0.0, 1.0];
+        for other_edge in other_edges.iter() {
+            let (c, d) = (other_edge.from, other_edge.to);
+            let (sx, sy) = (d.x - c.x, d.y - c.y);
+            let denom = cross(rx, ry, sx, sy);
+            let other_length = (sx * sx + sy * sy).sqrt();
+
+            // distances of the other end points to the carrier line of edge
+            let c_offset = cross(rx, ry, c.x - a.x, c.y - a.y).abs() / length;
+            let d_offset = cross(rx, ry, d.x - a.x, d.y - a.y).abs() / length;
+
+            if c_offset > tol || d_offset > tol {
+                if denom == 0.0 {
+                    continue;
+                }
+
+                // proper crossing or touching in a single point
+                let t = cross(c.x - a.x, c.y - a.y, sx, sy) / denom;
+                let u = cross(c.x - a.x, c.y - a.y, rx, ry) / denom;
+                let (t_slack, u_slack) = (tol / length, tol / other_length.max(tol));
+                if t > t_slack && t < 1.0 - t_slack && u > -u_slack && u < 1.0 + u_slack {
+                    params.push(t);
+                }
+            } else {
+                // colinear, split at the end points of the other edge
+                for p in [c, d].iter() {
+                    let t = ((p.x - a.x) * rx + (p.y - a.y) * ry) / (length * length);
+                    if t * length > tol && (1.0 - t) * length > tol {
+                        params.push(t);
+                    }
+                }
+            }
+        }
+
+        params.sort_by(|x, y| x.partial_cmp(y).unwrap());
+        params.dedup_by(|x, y| (*x - *y) * length <= tol);
+        // the end point may have been merged into a split close to it
+        if let Some(last) = params.last_mut() {
+            *last = 1.0;
+        }
+
+        let at = |t: f64| if t == 1.0 {b} else {Point::new().set_values(a.x + rx * t, a.y + ry * t)};
+        for pair in params.windows(2) {
+            pieces.push((at(pair[0]), at(pair[1])));
+        }
+    }
+
+    pieces
+}
+
+// private position of a boundary piece relative to the region of poly, by its midpoint
+fn classify(from: &Point, to: &Point, poly: &PolygonWithHoles, tol: f64) -> EdgeClass {
+    let mid = Point::new().set_values((from.x + to.x) / 2.0, (from.y + to.y) / 2.0);
+
+    let closest = poly.rings()
+        .flat_map(|r| r.calculate_edges())
+        .map(|e| (e.distance_to_point(&mid), e))
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    if let Some((distance, edge)) = closest {
+        if distance <= tol {
+            let dot = (to.x - from.x) * (edge.to.x - edge.from.x) + (to.y - from.y) * (edge.to.y - edge.from.y);
+            return if dot > 0.0 {EdgeClass::SameBoundary} else {EdgeClass::OppositeBoundary};
+        }
+    }
+
+    let inside = poly.outer.contains_point(&mid, 0.0) && !poly.holes.iter().any(|h| h.contains_point(&mid, 0.0));
+    if inside {EdgeClass::Inside} else {EdgeClass::Outside}
+}
+
+// private chaining of directed pieces into closed rings, at junctions the sharpest left turn is taken
+fn chain_rings(pieces: Vec<(Point, Point)>, tol: f64) -> Vec<Polygon> {
+    let mut used = vec![false; pieces.len()];
+    let mut rings = Vec::new();
+
+    for start in 0..pieces.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+
+        let mut pts = vec![pieces[start].0];
+        let mut current = start;
+        loop {
+            let (from, to) = pieces[current];
+            if to.distance_to(&pts[0]) <= tol {
+                rings.push(remove_colinear(&pts, tol));
+                break;
+            }
+
+            let (dx, dy) = (to.x - from.x, to.y - from.y);
+            let next = (0..pieces.len())
+                .filter(|&i| !used[i] && pieces[i].0.distance_to(&to) <= tol)
+                .max_by(|&i, &j| {
+                    let turn = |k: usize| {
+                        let (ex, ey) = (pieces[k].1.x - pieces[k].0.x, pieces[k].1.y - pieces[k].0.y);
+                        cross(dx, dy, ex, ey).atan2(dx * ex + dy * ey)
+                    };
+                    turn(i).partial_cmp(&turn(j)).unwrap()
+                });
+
+            // open chains come from degenerate input and are dropped
+            match next {
+                Some(i) => {
+                    used[i] = true;
+                    pts.push(pieces[i].0);
+                    current = i;
+                },
+                None => break
+            }
+        }
+    }
+
+    rings
+}
+
+// private removal of corners on a straight line between their neighbours
+fn remove_colinear(pts: &[Point], tol: f64) -> Polygon {
+    let mut result: Vec<Point> = pts.to_vec();
+    let mut i = 0;
+    while result.len() > 3 && i < result.len() {
+        let count = result.len();
+        let prev = result[(i + count - 1) % count];
+        let next = result[(i + 1) % count];
+        if LineSegment::new_from_points(&prev, &next).distance_to_point(&result[i]) <= tol {
+            result.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+
+    Polygon::from_points(&result)
+}
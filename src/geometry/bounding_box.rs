@@ -0,0 +1,102 @@
+use super::{Point, Polygon, PolygonWithHoles};
+
+/// Axis-aligned bounding box
+/// An empty box has `min` at positive and `max` at negative infinity, so including any point makes it valid
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point
+}
+
+impl BoundingBox {
+    // public constructor for a box without any points
+    pub fn empty() -> BoundingBox {
+        BoundingBox{
+            min: Point::new().set_values(f64::INFINITY, f64::INFINITY),
+            max: Point::new().set_values(f64::NEG_INFINITY, f64::NEG_INFINITY)
+        }
+    }
+
+    // public constructor from points, empty for an empty slice
+    pub fn new_from_points(pts: &[Point]) -> BoundingBox {
+        let mut bbox = BoundingBox::empty();
+        for pt in pts.iter() {
+            bbox.include_point(pt);
+        }
+        bbox
+    }
+
+    // public empty check
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y
+    }
+
+    // public growth to contain the given point
+    pub fn include_point(&mut self, pt: &Point) {
+        self.min.x = self.min.x.min(pt.x);
+        self.min.y = self.min.y.min(pt.y);
+        self.max.x = self.max.x.max(pt.x);
+        self.max.y = self.max.y.max(pt.y);
+    }
+
+    // public union of two boxes
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        let mut bbox = *self;
+        if !other.is_empty() {
+            bbox.include_point(&other.min);
+            bbox.include_point(&other.max);
+        }
+        bbox
+    }
+
+    pub fn width(&self) -> f64 {
+        if self.is_empty() {0.0} else {self.max.x - self.min.x}
+    }
+
+    pub fn height(&self) -> f64 {
+        if self.is_empty() {0.0} else {self.max.y - self.min.y}
+    }
+
+    // public containment check under tolerance
+    pub fn contains_point(&self, pt: &Point, tol: f64) -> bool {
+        pt.x > self.min.x - tol && pt.x < self.max.x + tol && pt.y > self.min.y - tol && pt.y < self.max.y + tol
+    }
+
+    // public conversion to a counter-clockwise polygon, empty for an empty box
+    pub fn as_polygon(&self) -> Polygon {
+        if self.is_empty() {
+            return Polygon::new();
+        }
+
+        Polygon::from_points(&vec![
+            self.min,
+            Point::new().set_values(self.max.x, self.min.y),
+            self.max,
+            Point::new().set_values(self.min.x, self.max.y)])
+    }
+}
+
+impl Polygon {
+    /// Axis-aligned bounding box of all polygon corners
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    ///
+    /// let bbox = Polygon::square(2.0).bounding_box();
+    /// assert_eq!(bbox.min, Point::new().set_values(-1.0, -1.0));
+    /// assert_eq!(bbox.width(), 2.0);
+    /// ```
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::new_from_points(&self.points)
+    }
+}
+
+impl PolygonWithHoles {
+    // public bounding box, holes always lie inside the outer boundary
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.outer.bounding_box()
+    }
+}
@@ -0,0 +1,293 @@
+use super::{Point, Polygon, TriangulationError};
+use super::triangulation::{turn, ring_indices};
+
+// available convex decomposition algorithms
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DecompositionMode {
+    HertelMehlhorn, // drops inessential diagonals of a triangulation, at most 4 times the optimal piece count, O(n^2)
+    Minimum // keil-style dynamic programming, the minimal piece count without additional corners, O(n^3)
+}
+
+impl Polygon {
+    /// Decompose the polygon into convex pieces, using only diagonals between its corners
+    /// Returns index lists into `points`, every piece is oriented counter-clockwise
+    /// Duplicate and colinear corners are skipped, just like in `triangulate_indices`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point, DecompositionMode};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// // comb with three teeth
+    /// let comb = Polygon::from_points(&vec![
+    ///     Point::new(),
+    ///     Point::new().set_values(5.0, 0.0),
+    ///     Point::new().set_values(5.0, 3.0),
+    ///     Point::new().set_values(4.0, 3.0),
+    ///     Point::new().set_values(4.0, 1.0),
+    ///     Point::new().set_values(3.0, 1.0),
+    ///     Point::new().set_values(3.0, 3.0),
+    ///     Point::new().set_values(2.0, 3.0),
+    ///     Point::new().set_values(2.0, 1.0),
+    ///     Point::new().set_values(1.0, 1.0),
+    ///     Point::new().set_values(1.0, 3.0),
+    ///     Point::new().set_values(0.0, 3.0)]);
+    ///
+    /// let optimal = comb.decompose_convex_indices(DecompositionMode::Minimum, tol).unwrap();
+    /// assert_eq!(optimal.len(), 4);
+    ///
+    /// let approximated = comb.decompose_convex_indices(DecompositionMode::HertelMehlhorn, tol).unwrap();
+    /// assert!(approximated.len() >= 4 && approximated.len() <= 16);
+    /// ```
+    pub fn decompose_convex_indices(&self, mode: DecompositionMode, tol: f64) -> Result<Vec<Vec<usize>>, TriangulationError> {
+        match mode {
+            DecompositionMode::HertelMehlhorn => hertel_mehlhorn(&self.points, self.triangulate_indices(tol)?, tol),
+            DecompositionMode::Minimum => minimum_decomposition(&self.points, tol)
+        }
+    }
+
+    // public convex decomposition into counter-clockwise pieces, see `decompose_convex_indices`
+    pub fn decompose_convex(&self, mode: DecompositionMode, tol: f64) -> Result<Vec<Polygon>, TriangulationError> {
+        let pieces = self.decompose_convex_indices(mode, tol)?;
+        Ok(pieces.iter()
+            .map(|piece| Polygon::from_points(&piece.iter().map(|&i| self.points[i]).collect()))
+            .collect())
+    }
+}
+
+// private helper testing a corner of an index ring for convexity, colinear corners count as convex
+fn is_convex_at(pts: &[Point], ring: &[usize], i: usize, tol: f64) -> bool {
+    let count = ring.len();
+    turn(&pts[ring[(i + count - 1) % count]], &pts[ring[i]], &pts[ring[(i + 1) % count]]) >= -tol
+}
+
+// private hertel-mehlhorn, removes every diagonal whose removal keeps both of its end points convex
+fn hertel_mehlhorn(pts: &[Point], tris: Vec<[usize; 3]>, tol: f64) -> Result<Vec<Vec<usize>>, TriangulationError> {
+    let mut pieces: Vec<Vec<usize>> = tris.iter().map(|t| t.to_vec()).collect();
+
+    // diagonals are the triangle edges used in both directions
+    let directed = tris.iter()
+        .flat_map(|t| vec![(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+        .collect::<Vec<_>>();
+    let diagonals = directed.iter()
+        .filter(|(from, to)| from < to && directed.contains(&(*to, *from)))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let edge_position = |piece: &Vec<usize>, from: usize, to: usize| (0..piece.len())
+        .find(|&i| piece[i] == from && piece[(i + 1) % piece.len()] == to);
+
+    for (u, v) in diagonals {
+        let first = pieces.iter().position(|p| edge_position(p, u, v).is_some());
+        let other = pieces.iter().position(|p| edge_position(p, v, u).is_some());
+        let (first, other) = match (first, other) {
+            (Some(f), Some(o)) if f != o => (f, o),
+            _ => continue
+        };
+
+        // first rotated to run from v to u, other from u to v, joined without the diagonal
+        let f_pos = edge_position(&pieces[first], u, v).unwrap();
+        let o_pos = edge_position(&pieces[other], v, u).unwrap();
+        let f_len = pieces[first].len();
+        let o_len = pieces[other].len();
+        let mut merged: Vec<usize> = (0..f_len).map(|n| pieces[first][(f_pos + 1 + n) % f_len]).collect();
+        merged.extend((1..(o_len - 1)).map(|n| pieces[other][(o_pos + 1 + n) % o_len]));
+
+        // u is the last corner taken from first, v the first one
+        if !is_convex_at(pts, &merged, f_len - 1, tol) || !is_convex_at(pts, &merged, 0, tol) {
+            continue;
+        }
+
+        pieces[first] = merged;
+        pieces.remove(other);
+    }
+
+    Ok(pieces)
+}
+
+// narrowest corners next to a chord in its convex piece, with the sub-decomposition they came from
+#[derive(Clone)]
+struct ChordPiece {
+    after_from: usize, // corner following the chord start in the piece
+    before_to: usize, // corner preceding the chord end in the piece
+    apex: usize, // corner closing the triangle on the chord
+    merged_with: Option<usize> // index of the piece on chord (from, apex) the triangle was merged into
+}
+
+// private minimum convex decomposition without additional corners, after Keil and Snoeyink
+// for every chord (i, j) the sub-polygon i..j is decomposed optimally, the piece containing the chord is a triangle
+// (i, k, j) either standing alone or merged into the piece on chord (i, k), only optimal sub-decompositions are needed
+fn minimum_decomposition(pts: &[Point], tol: f64) -> Result<Vec<Vec<usize>>, TriangulationError> {
+    let ring = ring_indices(pts, 0, true, tol);
+    let count = ring.len();
+    if count < 3 {
+        return Err(TriangulationError::TooFewPoints);
+    }
+
+    let q = ring.iter().map(|&i| pts[i]).collect::<Vec<_>>();
+    let valid = valid_chords(&q, tol);
+    let is_chord = |i: usize, j: usize| j == i + 1 || (i == 0 && j == count - 1) || valid[i][j];
+
+    // angle at a chord end point between the chord and a piece edge, smaller leaves more room to merge
+    let opening = |apex: &Point, along: &Point, to: &Point| {
+        let a = (along.x - apex.x, along.y - apex.y);
+        let b = (to.x - apex.x, to.y - apex.y);
+        (a.0 * b.1 - a.1 * b.0).abs().atan2(a.0 * b.0 + a.1 * b.1)
+    };
+
+    let mut weights = vec![vec![usize::MAX; count]; count];
+    let mut pieces: Vec<Vec<Vec<ChordPiece>>> = vec![vec![Vec::new(); count]; count];
+    for i in 0..(count - 1) {
+        weights[i][i + 1] = 0;
+    }
+
+    for length in 2..count {
+        for i in 0..(count - length) {
+            let j = i + length;
+            if !is_chord(i, j) {
+                continue;
+            }
+
+            let mut best = usize::MAX;
+            let mut candidates: Vec<ChordPiece> = Vec::new();
+            for k in (i + 1)..j {
+                if !is_chord(i, k) || !is_chord(k, j) || weights[i][k] == usize::MAX || weights[k][j] == usize::MAX {
+                    continue;
+                }
+                if turn(&q[i], &q[k], &q[j]) <= tol {
+                    continue;
+                }
+
+                let base = weights[i][k] + weights[k][j];
+
+                // merge the triangle into the piece on chord (i, k)
+                for (n, piece) in pieces[i][k].iter().enumerate() {
+                    if turn(&q[j], &q[i], &q[piece.after_from]) >= -tol && turn(&q[piece.before_to], &q[k], &q[j]) >= -tol {
+                        if base < best {
+                            best = base;
+                            candidates.clear();
+                        }
+                        if base == best {
+                            candidates.push(ChordPiece{after_from: piece.after_from, before_to: k, apex: k, merged_with: Some(n)});
+                        }
+                    }
+                }
+
+                // triangle as a piece of its own
+                if base + 1 < best {
+                    best = base + 1;
+                    candidates.clear();
+                }
+                if base + 1 == best {
+                    candidates.push(ChordPiece{after_from: k, before_to: k, apex: k, merged_with: None});
+                }
+            }
+
+            // only keep the pairs not dominated at both chord end points
+            let angles = |c: &ChordPiece| (opening(&q[i], &q[j], &q[c.after_from]), opening(&q[j], &q[i], &q[c.before_to]));
+            candidates.sort_by(|a, b| angles(a).partial_cmp(&angles(b)).unwrap());
+            let mut narrowest: Vec<ChordPiece> = Vec::new();
+            let mut best_to_angle = f64::MAX;
+            for candidate in candidates {
+                let (_, to_angle) = angles(&candidate);
+                if to_angle < best_to_angle {
+                    best_to_angle = to_angle;
+                    narrowest.push(candidate);
+                }
+            }
+
+            weights[i][j] = best;
+            pieces[i][j] = narrowest;
+        }
+    }
+
+    if weights[0][count - 1] == usize::MAX {
+        return Err(TriangulationError::NotSimple);
+    }
+
+    // rebuild the pieces from the chosen triangles, mapped back to the original indices
+    let mut result: Vec<Vec<usize>> = Vec::with_capacity(weights[0][count - 1]);
+    let last = collect_pieces(&pieces, 0, count - 1, 0, &mut result);
+    result.push(last);
+
+    Ok(result.into_iter().map(|piece| piece.into_iter().map(|i| ring[i]).collect()).collect())
+}
+
+// private helper collecting all closed pieces of chord (i, j), returns the open piece containing the chord
+fn collect_pieces(pieces: &[Vec<Vec<ChordPiece>>], i: usize, j: usize, n: usize, result: &mut Vec<Vec<usize>>) -> Vec<usize> {
+    let piece = &pieces[i][j][n];
+    let k = piece.apex;
+
+    // sub-polygon on chord (k, j) is always closed off
+    if j > k + 1 {
+        let closed = collect_pieces(pieces, k, j, 0, result);
+        result.push(closed);
+    }
+
+    let mut open = match piece.merged_with {
+        Some(m) => collect_pieces(pieces, i, k, m, result),
+        None => {
+            if k > i + 1 {
+                let closed = collect_pieces(pieces, i, k, 0, result);
+                result.push(closed);
+            }
+            vec![i, k]
+        }
+    };
+    open.push(j);
+    open
+}
+
+// private table of chords between non-adjacent corners running inside the polygon
+fn valid_chords(q: &[Point], tol: f64) -> Vec<Vec<bool>> {
+    let count = q.len();
+    let mut valid = vec![vec![false; count]; count];
+
+    // chord direction has to leave a corner through its interior angle
+    let in_cone = |a: usize, b: usize| {
+        let prev = &q[(a + count - 1) % count];
+        let next = &q[(a + 1) % count];
+        if turn(prev, &q[a], next) >= 0.0 {
+            turn(&q[a], &q[b], prev) > tol && turn(&q[b], &q[a], next) > tol
+        }
+        else {
+            !(turn(&q[a], &q[b], next) >= -tol && turn(&q[b], &q[a], prev) >= -tol)
+        }
+    };
+
+    for i in 0..count {
+        for j in (i + 2)..count {
+            if i == 0 && j == count - 1 {
+                continue;
+            }
+            if !in_cone(i, j) || !in_cone(j, i) {
+                continue;
+            }
+
+            let crossing = (0..count).any(|e| {
+                let f = (e + 1) % count;
+                if e == i || e == j || f == i || f == j {
+                    return false;
+                }
+                segments_touch(&q[i], &q[j], &q[e], &q[f], tol)
+            });
+            valid[i][j] = !crossing;
+        }
+    }
+
+    valid
+}
+
+// private inclusive segment intersection test, touching counts as intersecting
+fn segments_touch(a0: &Point, a1: &Point, b0: &Point, b1: &Point, tol: f64) -> bool {
+    let d0 = turn(a0, a1, b0);
+    let d1 = turn(a0, a1, b1);
+    let d2 = turn(b0, b1, a0);
+    let d3 = turn(b0, b1, a1);
+    d0 * d1 <= tol * tol && d2 * d3 <= tol * tol
+        && a0.x.min(a1.x) <= b0.x.max(b1.x) + tol && b0.x.min(b1.x) <= a0.x.max(a1.x) + tol
+        && a0.y.min(a1.y) <= b0.y.max(b1.y) + tol && b0.y.min(b1.y) <= a0.y.max(a1.y) + tol
+}
@@ -0,0 +1,316 @@
+use super::{Point, LineSegment, BoundingBox};
+
+// deepest subdivision while flattening, 2^16 chords per bezier piece
+const MAX_SUBDIVISION_DEPTH: usize = 16;
+
+// smallest deviation that is flattened for, relative to the size of the control points
+const MIN_RELATIVE_DEVIATION: f64 = 1e-9;
+
+/// Result of flattening a curve into a polyline
+/// `max_deviation` is an upper bound for the distance between the curve and the polyline, derived from the
+/// control polygons of the subdivided pieces. It stays within the requested tolerance unless the subdivision
+/// depth limit of 2^16 chords per bezier piece is reached, then it reports the larger deviation actually achieved
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveFlattening {
+    pub points: Vec<Point>, // polyline from start to end point of the curve
+    pub max_deviation: f64
+}
+
+/// Quadratic bezier curve with a single control point
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuadraticBezier {
+    pub from: Point,
+    pub control: Point,
+    pub to: Point
+}
+
+impl QuadraticBezier {
+    // public constructor from points
+    pub fn new(from: &Point, control: &Point, to: &Point) -> QuadraticBezier {
+        QuadraticBezier{from: *from, control: *control, to: *to}
+    }
+
+    // public bezier parameter evaluation
+    pub fn point_at_normalized_parameter(&self, t: f64) -> Point {
+        de_casteljau(&[self.from, self.control, self.to], t)
+    }
+
+    /// Adaptive flattening, chords are split until the curve deviates at most `max_deviation` from them
+    /// Tolerances below a billionth of the curve size, including zero and negative ones, are raised to it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{QuadraticBezier, Point};
+    ///
+    /// let curve = QuadraticBezier::new(&Point::new(), &Point::new().set_values(1.0, 2.0), &Point::new().set_values(2.0, 0.0));
+    ///
+    /// let flat = curve.flatten(0.01);
+    /// assert!(flat.max_deviation <= 0.01);
+    /// assert_eq!(flat.points.first(), Some(&curve.from));
+    /// assert_eq!(flat.points.last(), Some(&curve.to));
+    /// ```
+    pub fn flatten(&self, max_deviation: f64) -> CurveFlattening {
+        flatten_bezier_pieces(&[vec![self.from, self.control, self.to]], max_deviation)
+    }
+}
+
+/// Cubic bezier curve with two control points
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CubicBezier {
+    pub from: Point,
+    pub control0: Point,
+    pub control1: Point,
+    pub to: Point
+}
+
+impl CubicBezier {
+    // public constructor from points
+    pub fn new(from: &Point, control0: &Point, control1: &Point, to: &Point) -> CubicBezier {
+        CubicBezier{from: *from, control0: *control0, control1: *control1, to: *to}
+    }
+
+    // public degree elevation of a quadratic bezier, describing the same curve
+    pub fn from_quadratic(curve: &QuadraticBezier) -> CubicBezier {
+        let lerp = |a: &Point, b: &Point| LineSegment::new_from_points(a, b).point_at_normalized_parameter(2.0 / 3.0);
+        CubicBezier::new(&curve.from, &lerp(&curve.from, &curve.control), &lerp(&curve.to, &curve.control), &curve.to)
+    }
+
+    // public bezier parameter evaluation
+    pub fn point_at_normalized_parameter(&self, t: f64) -> Point {
+        de_casteljau(&[self.from, self.control0, self.control1, self.to], t)
+    }
+
+    /// Adaptive flattening, chords are split until the curve deviates at most `max_deviation` from them
+    /// Tolerances below a billionth of the curve size, including zero and negative ones, are raised to it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{CubicBezier, Point};
+    ///
+    /// // s-shaped curve
+    /// let curve = CubicBezier::new(
+    ///     &Point::new(),
+    ///     &Point::new().set_values(1.0, 2.0),
+    ///     &Point::new().set_values(2.0, -2.0),
+    ///     &Point::new().set_values(3.0, 0.0));
+    ///
+    /// let coarse = curve.flatten(0.1);
+    /// let fine = curve.flatten(0.001);
+    /// assert!(fine.max_deviation <= 0.001);
+    /// assert!(fine.points.len() > coarse.points.len());
+    /// ```
+    pub fn flatten(&self, max_deviation: f64) -> CurveFlattening {
+        flatten_bezier_pieces(&[vec![self.from, self.control0, self.control1, self.to]], max_deviation)
+    }
+}
+
+/// Non-rational B-spline of any degree, as found in DXF SPLINE entities
+/// The knot vector has `control_points.len() + degree + 1` non-decreasing entries,
+/// the curve is defined between `knots[degree]` and `knots[control_points.len()]`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BSpline {
+    pub degree: usize,
+    pub control_points: Vec<Point>,
+    pub knots: Vec<f64>
+}
+
+impl BSpline {
+    /// Constructor from an explicit knot vector
+    /// Returns `None` if the knot count does not match, the knots are decreasing or the curve domain is empty
+    pub fn new(degree: usize, control_points: &[Point], knots: &[f64]) -> Option<BSpline> {
+        let count = control_points.len();
+        if degree == 0 || count <= degree || knots.len() != count + degree + 1 {
+            return None;
+        }
+        if knots.windows(2).any(|k| k[1] < k[0]) || knots[count] <= knots[degree] {
+            return None;
+        }
+
+        Some(BSpline{degree, control_points: control_points.to_vec(), knots: knots.to_vec()})
+    }
+
+    /// Constructor with a clamped uniform knot vector, the curve starts and ends on the outer control points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{BSpline, Point};
+    ///
+    /// let pts = vec![
+    ///     Point::new(),
+    ///     Point::new().set_values(1.0, 2.0),
+    ///     Point::new().set_values(3.0, 2.0),
+    ///     Point::new().set_values(4.0, 0.0),
+    ///     Point::new().set_values(6.0, 1.0)];
+    ///
+    /// let spline = BSpline::new_clamped_uniform(3, &pts).unwrap();
+    /// assert_eq!(spline.knots, vec![0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0]);
+    /// assert!(spline.point_at_normalized_parameter(1.0).epsilon_equals(&pts[4], 0.000001));
+    /// ```
+    pub fn new_clamped_uniform(degree: usize, control_points: &[Point]) -> Option<BSpline> {
+        let count = control_points.len();
+        if degree == 0 || count <= degree {
+            return None;
+        }
+
+        let spans = count - degree;
+        let knots = (0..(count + degree + 1))
+            .map(|n| (n.saturating_sub(degree).min(spans)) as f64 / spans as f64)
+            .collect::<Vec<_>>();
+
+        BSpline::new(degree, control_points, &knots)
+    }
+
+    // public evaluation, 0.0 and 1.0 map to the start and end of the curve domain
+    pub fn point_at_normalized_parameter(&self, t: f64) -> Point {
+        let start = self.knots[self.degree];
+        let end = self.knots[self.control_points.len()];
+        let u = start + t.clamp(0.0, 1.0) * (end - start);
+
+        // last non-empty span starting at or before u
+        let span = (self.degree..self.control_points.len())
+            .rev()
+            .find(|&i| self.knots[i] <= u && self.knots[i] < self.knots[i + 1])
+            .unwrap_or(self.degree);
+
+        self.blossom(span, &vec![u; self.degree])
+    }
+
+    /// Adaptive flattening, chords are split until the curve deviates at most `max_deviation` from them
+    /// Tolerances below a billionth of the curve size, including zero and negative ones, are raised to it
+    /// Every non-empty knot span is converted into a bezier piece first, so the deviation bound stays exact
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{BSpline, Point};
+    ///
+    /// let pts = vec![
+    ///     Point::new(),
+    ///     Point::new().set_values(1.0, 2.0),
+    ///     Point::new().set_values(3.0, 2.0),
+    ///     Point::new().set_values(4.0, 0.0)];
+    ///
+    /// let spline = BSpline::new_clamped_uniform(2, &pts).unwrap();
+    /// let flat = spline.flatten(0.01);
+    ///
+    /// assert!(flat.max_deviation <= 0.01);
+    /// assert_eq!(flat.points[0], pts[0]);
+    /// assert!(flat.points.last().unwrap().epsilon_equals(&pts[3], 0.000001));
+    /// ```
+    pub fn flatten(&self, max_deviation: f64) -> CurveFlattening {
+        let pieces = (self.degree..self.control_points.len())
+            .filter(|&i| self.knots[i] < self.knots[i + 1])
+            .map(|i| self.bezier_piece(i))
+            .collect::<Vec<_>>();
+
+        flatten_bezier_pieces(&pieces, max_deviation)
+    }
+
+    // private bezier control points of a knot span, the j-th one is the blossom of (u_i, .., u_i, u_i+1, .., u_i+1) with j times u_i+1
+    fn bezier_piece(&self, span: usize) -> Vec<Point> {
+        let (lower, upper) = (self.knots[span], self.knots[span + 1]);
+        (0..=self.degree)
+            .map(|j| {
+                let args = (0..self.degree).map(|r| if r < j {upper} else {lower}).collect::<Vec<_>>();
+                self.blossom(span, &args)
+            })
+            .collect()
+    }
+
+    // private de boor evaluation with one parameter per level, which gives the polar form of the span
+    fn blossom(&self, span: usize, args: &[f64]) -> Point {
+        let p = self.degree;
+        let mut d = self.control_points[(span - p)..=span].to_vec();
+
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let lower = self.knots[j + span - p];
+                let upper = self.knots[j + 1 + span - r];
+                let alpha = if upper > lower {(args[r - 1] - lower) / (upper - lower)} else {0.0};
+                d[j] = lerp(&d[j - 1], &d[j], alpha);
+            }
+        }
+
+        d[p]
+    }
+}
+
+// private linear interpolation, like `LineSegment::point_at_normalized_parameter` without the range check
+fn lerp(from: &Point, to: &Point, t: f64) -> Point {
+    Point::new().set_values(from.x + t * (to.x - from.x), from.y + t * (to.y - from.y))
+}
+
+// private bezier evaluation of any degree
+fn de_casteljau(ctrl: &[Point], t: f64) -> Point {
+    let mut pts = ctrl.to_vec();
+    for level in 1..pts.len() {
+        for n in 0..(pts.len() - level) {
+            pts[n] = lerp(&pts[n], &pts[n + 1], t);
+        }
+    }
+    pts[0]
+}
+
+// private split of a bezier at its parameter midpoint into two halves of the same degree
+fn split_half(ctrl: &[Point]) -> (Vec<Point>, Vec<Point>) {
+    let mut pts = ctrl.to_vec();
+    let mut first = vec![pts[0]];
+    let mut other = vec![pts[pts.len() - 1]];
+    for level in 1..ctrl.len() {
+        for n in 0..(ctrl.len() - level) {
+            pts[n] = lerp(&pts[n], &pts[n + 1], 0.5);
+        }
+        first.push(pts[0]);
+        other.push(pts[ctrl.len() - level - 1]);
+    }
+    other.reverse();
+    (first, other)
+}
+
+// private deviation bound, the curve stays inside the convex hull of its control points
+fn control_polygon_deviation(ctrl: &[Point]) -> f64 {
+    let chord = LineSegment::new_from_points(&ctrl[0], &ctrl[ctrl.len() - 1]);
+    ctrl[1..(ctrl.len() - 1)].iter()
+        .map(|pt| chord.distance_to_point(pt))
+        .fold(0.0, f64::max)
+}
+
+// private recursive flattening, pushes every chord end point
+fn flatten_recursive(ctrl: &[Point], max_deviation: f64, depth: usize, result: &mut CurveFlattening) {
+    let deviation = control_polygon_deviation(ctrl);
+    if deviation <= max_deviation || depth >= MAX_SUBDIVISION_DEPTH {
+        result.points.push(ctrl[ctrl.len() - 1]);
+        result.max_deviation = result.max_deviation.max(deviation);
+        return;
+    }
+
+    let (first, other) = split_half(ctrl);
+    flatten_recursive(&first, max_deviation, depth + 1, result);
+    flatten_recursive(&other, max_deviation, depth + 1, result);
+}
+
+// private flattening of consecutive bezier pieces into one polyline
+// tolerances of zero, below or too small for the size of the curve are raised to a fraction of that size
+fn flatten_bezier_pieces(pieces: &[Vec<Point>], max_deviation: f64) -> CurveFlattening {
+    let bbox = BoundingBox::new_from_points(&pieces.concat());
+    let min_deviation = if bbox.is_empty() {0.0} else {bbox.width().max(bbox.height()) * MIN_RELATIVE_DEVIATION};
+    let max_deviation = if max_deviation > min_deviation {max_deviation} else {min_deviation};
+
+    let mut result = CurveFlattening{points: Vec::new(), max_deviation: 0.0};
+    if let Some(first) = pieces.first() {
+        result.points.push(first[0]);
+    }
+
+    for piece in pieces.iter() {
+        flatten_recursive(piece, max_deviation, 0, &mut result);
+    }
+
+    result
+}
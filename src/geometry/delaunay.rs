@@ -0,0 +1,133 @@
+use super::{Point, Polygon, PolygonWithHoles, TriangulationError};
+use std::collections::HashMap;
+
+impl Polygon {
+    /// Constrained delaunay triangulation of the polygon, all polygon edges are kept
+    /// Starts from the ear-clipping triangulation and flips diagonals until every one is locally delaunay
+    /// Returns index triples into `points`, every triangle is oriented counter-clockwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// // flat hexagon, ear-clipping fans out from a single corner
+    /// let poly = Polygon::from_points(&vec![
+    ///     Point::new(),
+    ///     Point::new().set_values(2.0, -0.5),
+    ///     Point::new().set_values(4.0, 0.0),
+    ///     Point::new().set_values(4.0, 1.0),
+    ///     Point::new().set_values(2.0, 1.5),
+    ///     Point::new().set_values(0.0, 1.0)]);
+    ///
+    /// let tris = poly.triangulate_delaunay_indices(tol).unwrap();
+    /// assert_eq!(tris.len(), 4);
+    /// assert!(tris.iter().any(|t| t.contains(&1) && t.contains(&4))); // short diagonal across the middle
+    /// ```
+    pub fn triangulate_delaunay_indices(&self, tol: f64) -> Result<Vec<[usize; 3]>, TriangulationError> {
+        let tris = self.triangulate_indices(tol)?;
+        Ok(flip_to_delaunay(&self.points, tris, tol))
+    }
+
+    // public constrained delaunay triangulation into counter-clockwise triangles, see `triangulate_delaunay_indices`
+    pub fn triangulate_delaunay(&self, tol: f64) -> Result<Vec<Polygon>, TriangulationError> {
+        let tris = self.triangulate_delaunay_indices(tol)?;
+        Ok(tris.iter().map(|t| Polygon::from_points(&vec![self.points[t[0]], self.points[t[1]], self.points[t[2]]])).collect())
+    }
+}
+
+impl PolygonWithHoles {
+    /// Constrained delaunay triangulation of the polygon with holes, all edges of the outer boundary and the holes are kept
+    /// Returns index triples into `all_points`, every triangle is oriented counter-clockwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+    ///
+    /// let tris = frame.triangulate_delaunay(tol).unwrap();
+    /// let area: f64 = tris.iter().map(|t| t.calculate_signed_area()).sum();
+    /// assert_eq!(tris.len(), 8);
+    /// assert!((area - 12.0).abs() < tol);
+    /// ```
+    pub fn triangulate_delaunay_indices(&self, tol: f64) -> Result<Vec<[usize; 3]>, TriangulationError> {
+        let tris = self.triangulate_indices(tol)?;
+        Ok(flip_to_delaunay(&self.all_points(), tris, tol))
+    }
+
+    // public constrained delaunay triangulation into counter-clockwise triangles, see `triangulate_delaunay_indices`
+    pub fn triangulate_delaunay(&self, tol: f64) -> Result<Vec<Polygon>, TriangulationError> {
+        let pts = self.all_points();
+        let tris = self.triangulate_delaunay_indices(tol)?;
+        Ok(tris.iter().map(|t| Polygon::from_points(&vec![pts[t[0]], pts[t[1]], pts[t[2]]])).collect())
+    }
+}
+
+// private in-circle test, positive if pt lies inside the circumcircle of the counter-clockwise triangle a, b, c
+fn in_circle(a: &Point, b: &Point, c: &Point, pt: &Point) -> f64 {
+    let (ax, ay) = (a.x - pt.x, a.y - pt.y);
+    let (bx, by) = (b.x - pt.x, b.y - pt.y);
+    let (cx, cy) = (c.x - pt.x, c.y - pt.y);
+
+    (ax * ax + ay * ay) * (bx * cy - cx * by)
+        - (bx * bx + by * by) * (ax * cy - cx * ay)
+        + (cx * cx + cy * cy) * (ax * by - bx * ay)
+}
+
+// private lawson flipping, only diagonals shared by two triangles are flipped so the boundary stays constrained
+fn flip_to_delaunay(pts: &[Point], mut tris: Vec<[usize; 3]>, tol: f64) -> Vec<[usize; 3]> {
+    // directed edge -> triangle holding it
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::with_capacity(3 * tris.len());
+    for (n, t) in tris.iter().enumerate() {
+        for e in 0..3 {
+            edges.insert((t[e], t[(e + 1) % 3]), n);
+        }
+    }
+
+    let mut stack = edges.keys()
+        .filter(|(from, to)| from < to && edges.contains_key(&(*to, *from)))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // corner opposite of a directed edge in its triangle
+    let opposite = |t: &[usize; 3], from: usize| t[(t.iter().position(|&c| c == from).unwrap() + 2) % 3];
+
+    while let Some((u, v)) = stack.pop() {
+        let (first, other) = match (edges.get(&(u, v)), edges.get(&(v, u))) {
+            (Some(&f), Some(&o)) => (f, o),
+            _ => continue
+        };
+
+        let a = opposite(&tris[first], u);
+        let b = opposite(&tris[other], v);
+        if in_circle(&pts[u], &pts[v], &pts[a], &pts[b]) <= tol {
+            continue;
+        }
+
+        // quad u, b, v, a gets the diagonal a - b instead
+        for &(from, to) in [(u, v), (v, a), (a, u), (v, u), (u, b), (b, v)].iter() {
+            edges.remove(&(from, to));
+        }
+        tris[first] = [u, b, a];
+        tris[other] = [b, v, a];
+        for &(n, t) in [(first, tris[first]), (other, tris[other])].iter() {
+            for e in 0..3 {
+                edges.insert((t[e], t[(e + 1) % 3]), n);
+            }
+        }
+
+        for &(from, to) in [(u, b), (b, v), (v, a), (a, u)].iter() {
+            if edges.contains_key(&(to, from)) {
+                stack.push((from.min(to), from.max(to)));
+            }
+        }
+    }
+
+    tris
+}
@@ -0,0 +1,162 @@
+use super::{Point, LineSegment, Polygon, Intersection, LineSegmentLineSegmentIntersectionResult};
+
+// closest point pair between two geometries, `first` lies on the first and `other` on the other input
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClosestPoints {
+    pub distance: f64,
+    pub first: Point,
+    pub other: Point
+}
+
+impl ClosestPoints {
+    // public constructor, calculates the distance between the points
+    pub fn new_from_points(first: &Point, other: &Point) -> ClosestPoints {
+        ClosestPoints{
+            distance: first.distance_to(other),
+            first: *first,
+            other: *other
+        }
+    }
+
+    // public swap, for results of symmetric queries called with switched arguments
+    pub fn swapped(self) -> ClosestPoints {
+        ClosestPoints{
+            distance: self.distance,
+            first: self.other,
+            other: self.first
+        }
+    }
+}
+
+pub struct Distance {
+
+}
+
+impl Distance {
+    // public point - line segment distance
+    pub fn point_line_segment(pt: &Point, segment: &LineSegment) -> ClosestPoints {
+        ClosestPoints::new_from_points(pt, &segment.closest_point(pt))
+    }
+
+    /// Minimum distance between two `LineSegment` instances
+    /// Intersecting or overlapping segments have a distance of zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Distance, Point, LineSegment};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let line0 = LineSegment::new_from_points(&Point::new(), &Point::new().set_values(2.0, 0.0));
+    /// let line1 = LineSegment::new_from_points(&Point::new().set_values(1.0, 1.0), &Point::new().set_values(1.0, 3.0));
+    ///
+    /// let closest = Distance::line_segment_line_segment(&line0, &line1, tol);
+    /// assert_eq!(closest.distance, 1.0);
+    /// assert!(closest.first.epsilon_equals(&Point::new().set_values(1.0, 0.0), tol));
+    /// assert!(closest.other.epsilon_equals(&Point::new().set_values(1.0, 1.0), tol));
+    /// ```
+    pub fn line_segment_line_segment(first: &LineSegment, other: &LineSegment, tol: f64) -> ClosestPoints {
+        // crossing segments, parallel ones are handled by their end points below
+        if first.denominator_with_other(other).abs() >= tol {
+            if let LineSegmentLineSegmentIntersectionResult::Point(pt) = Intersection::line_segment_line_segment(first, other, tol) {
+                return ClosestPoints{distance: 0.0, first: pt, other: pt};
+            }
+        }
+
+        // otherwise one of the end points is part of the closest pair
+        let candidates = vec![
+            Distance::point_line_segment(&first.from, other),
+            Distance::point_line_segment(&first.to, other),
+            Distance::point_line_segment(&other.from, first).swapped(),
+            Distance::point_line_segment(&other.to, first).swapped()];
+
+        Distance::closest_of(candidates)
+    }
+
+    /// Minimum distance between a point and a polygon
+    /// Points inside the polygon have a distance of zero, use `point_polygon_boundary` for the distance to the boundary
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Distance, Point, Polygon};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let square = Polygon::square(2.0);
+    ///
+    /// assert_eq!(Distance::point_polygon(&Point::new().set_values(4.0, 0.0), &square, tol).distance, 3.0);
+    /// assert_eq!(Distance::point_polygon(&Point::new(), &square, tol).distance, 0.0);
+    /// ```
+    pub fn point_polygon(pt: &Point, poly: &Polygon, tol: f64) -> ClosestPoints {
+        if poly.contains_point(pt, tol) {
+            return ClosestPoints{distance: 0.0, first: *pt, other: *pt};
+        }
+
+        Distance::point_polygon_boundary(pt, poly)
+    }
+
+    // public point - polygon boundary distance, independent of the point being inside or outside
+    pub fn point_polygon_boundary(pt: &Point, poly: &Polygon) -> ClosestPoints {
+        let candidates = poly.calculate_edges().iter()
+            .map(|edge| Distance::point_line_segment(pt, edge))
+            .collect::<Vec<_>>();
+
+        Distance::closest_of(candidates)
+    }
+
+    /// Minimum distance between two polygons
+    /// Intersecting polygons, or polygons containing one another, have a distance of zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Distance, Point, Polygon};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let square = Polygon::square(2.0);
+    /// let tri = Polygon::from_points(&vec![
+    ///     Point::new().set_values(3.0, 0.0),
+    ///     Point::new().set_values(5.0, -1.0),
+    ///     Point::new().set_values(5.0, 1.0)]);
+    ///
+    /// let closest = Distance::polygon_polygon(&square, &tri, tol);
+    /// assert_eq!(closest.distance, 2.0);
+    /// assert!(closest.first.epsilon_equals(&Point::new().set_values(1.0, 0.0), tol));
+    /// assert!(closest.other.epsilon_equals(&Point::new().set_values(3.0, 0.0), tol));
+    /// ```
+    pub fn polygon_polygon(first: &Polygon, other: &Polygon, tol: f64) -> ClosestPoints {
+        // containment, any corner of one polygon inside the other
+        if let Some(pt) = other.points.first().filter(|pt| first.contains_point(pt, tol)) {
+            return ClosestPoints{distance: 0.0, first: *pt, other: *pt};
+        }
+        if let Some(pt) = first.points.first().filter(|pt| other.contains_point(pt, tol)) {
+            return ClosestPoints{distance: 0.0, first: *pt, other: *pt};
+        }
+
+        let other_edges = other.calculate_edges();
+        let mut candidates: Vec<ClosestPoints> = Vec::with_capacity(first.points.len() * other_edges.len());
+
+        for f_edge in first.calculate_edges().iter() {
+            for o_edge in other_edges.iter() {
+                let closest = Distance::line_segment_line_segment(f_edge, o_edge, tol);
+                if closest.distance == 0.0 {
+                    return closest;
+                }
+                candidates.push(closest);
+            }
+        }
+
+        Distance::closest_of(candidates)
+    }
+
+    // private helper picking the closest pair, panics on empty input
+    fn closest_of(candidates: Vec<ClosestPoints>) -> ClosestPoints {
+        candidates.into_iter()
+            .min_by(|a, b| a.distance.partial_cmp(&b.distance).unwrap())
+            .expect("Distance ERROR: No geometry to measure the distance to!")
+    }
+}
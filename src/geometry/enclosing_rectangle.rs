@@ -0,0 +1,165 @@
+use super::{Point, Polygon, Vector};
+
+/// Oriented rectangle enclosing a polygon
+/// Rotating the polygon by `-angle` around any point turns the rectangle into an axis-aligned box of `width` x `height`
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnclosingRectangle {
+    pub angle: f64, // angle of the width side to the x-axis, between 0 and 2PI
+    pub width: f64, // extent along the angle direction
+    pub height: f64, // extent perpendicular to the angle direction
+    pub corners: [Point; 4] // counter-clockwise, starting at the lower left corner in the rotated frame
+}
+
+impl EnclosingRectangle {
+    // public area calculation
+    pub fn calculate_area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    // public conversion to a polygon
+    pub fn as_polygon(&self) -> Polygon {
+        Polygon::from_points(&self.corners.to_vec())
+    }
+
+    // private helper to construct the rectangle flush with a hull edge
+    fn new_from_frame(origin: &Point, u: &Vector, min_u: f64, max_u: f64, max_n: f64) -> EnclosingRectangle {
+        let x_axis = Vector::new().set_values(1.0, 0.0);
+        let n = Vector::new().set_values(-u.y, u.x);
+
+        let at = |s: f64, t: f64| Point::new().set_values(
+            origin.x + s * u.x + t * n.x,
+            origin.y + s * u.y + t * n.y);
+
+        EnclosingRectangle {
+            angle: x_axis.angle_to(u),
+            width: max_u - min_u,
+            height: max_n,
+            corners: [at(min_u, 0.0), at(max_u, 0.0), at(max_u, max_n), at(min_u, max_n)]
+        }
+    }
+}
+
+impl Polygon {
+    /// Minimum-area rectangle enclosing the polygon, found with rotating calipers on the convex hull
+    /// One side of the optimal rectangle is always flush with an edge of the hull, so all hull edges are visited once
+    /// Returns `None` for a polygon without points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// // square with side length 2.0, rotated by 45 degrees
+    /// let diamond = Polygon::from_points(&vec![
+    ///     Point::new().set_values(1.0, 0.0),
+    ///     Point::new().set_values(2.0, 1.0),
+    ///     Point::new().set_values(1.0, 2.0),
+    ///     Point::new().set_values(0.0, 1.0)]);
+    ///
+    /// let rect = diamond.minimum_area_rectangle(tol).unwrap();
+    /// assert!((rect.calculate_area() - 2.0).abs() < tol);
+    /// assert!((rect.width - 2.0_f64.sqrt()).abs() < tol);
+    /// ```
+    pub fn minimum_area_rectangle(&self, tol: f64) -> Option<EnclosingRectangle> {
+        self.rotating_calipers(tol, |rect| rect.calculate_area())
+    }
+
+    /// Minimum-width strip enclosing the polygon, found with rotating calipers on the convex hull
+    /// The strip width is the `height` of the returned rectangle, so rotating the polygon by `-angle`
+    /// gives the lowest possible orientation of the part
+    /// Returns `None` for a polygon without points
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let tri = Polygon::from_points(&vec![
+    ///     Point::new(),
+    ///     Point::new().set_values(4.0, 0.0),
+    ///     Point::new().set_values(0.0, 3.0)]);
+    ///
+    /// let strip = tri.minimum_width_rectangle(tol).unwrap();
+    /// assert!((strip.height - 2.4).abs() < tol); // height on the hypotenuse
+    /// assert!((strip.width - 5.0).abs() < tol);
+    /// ```
+    pub fn minimum_width_rectangle(&self, tol: f64) -> Option<EnclosingRectangle> {
+        self.rotating_calipers(tol, |rect| rect.height)
+    }
+
+    // private rotating calipers, returns the rectangle minimizing the given measure
+    fn rotating_calipers<F: Fn(&EnclosingRectangle) -> f64>(&self, tol: f64, measure: F) -> Option<EnclosingRectangle> {
+        let hull = self.convex_hull(tol);
+        let pts = &hull.points;
+        let n = pts.len();
+
+        match n {
+            0 => return None,
+            1 => {
+                let u = Vector::new().set_values(1.0, 0.0);
+                return Some(EnclosingRectangle::new_from_frame(&pts[0], &u, 0.0, 0.0, 0.0));
+            },
+            _ => ()
+        };
+
+        // projections of a hull point on the frame of an edge
+        let proj = |origin: &Point, dir: &Vector, pt: &Point| Vector::new_from_points(origin, pt).dot_product(dir);
+
+        let mut best: Option<EnclosingRectangle> = None;
+        let mut best_measure = f64::MAX;
+
+        // caliper indices, right and left along the edge, top perpendicular to it
+        let mut right = 0;
+        let mut top = 0;
+        let mut left = 0;
+
+        for (i, origin) in pts.iter().enumerate() {
+            let u = Vector::new_from_points(origin, &pts[(i + 1) % n]).as_normalized();
+            let nrm = Vector::new().set_values(-u.y, u.x);
+
+            if i == 0 {
+                // initial caliper positions from a full scan
+                for (j, pt) in pts.iter().enumerate() {
+                    if proj(origin, &u, pt) > proj(origin, &u, &pts[right]) {right = j;}
+                    if proj(origin, &nrm, pt) > proj(origin, &nrm, &pts[top]) {top = j;}
+                    if proj(origin, &u, pt) < proj(origin, &u, &pts[left]) {left = j;}
+                }
+            }
+            else {
+                // calipers only ever rotate counter-clockwise with the edge
+                for _ in 0..n {
+                    if proj(origin, &u, &pts[(right + 1) % n]) > proj(origin, &u, &pts[right]) {right = (right + 1) % n;}
+                    else {break;}
+                }
+                for _ in 0..n {
+                    if proj(origin, &nrm, &pts[(top + 1) % n]) > proj(origin, &nrm, &pts[top]) {top = (top + 1) % n;}
+                    else {break;}
+                }
+                for _ in 0..n {
+                    if proj(origin, &u, &pts[(left + 1) % n]) < proj(origin, &u, &pts[left]) {left = (left + 1) % n;}
+                    else {break;}
+                }
+            }
+
+            let rect = EnclosingRectangle::new_from_frame(
+                origin,
+                &u,
+                proj(origin, &u, &pts[left]),
+                proj(origin, &u, &pts[right]),
+                proj(origin, &nrm, &pts[top]));
+
+            let current = measure(&rect);
+            if current < best_measure {
+                best_measure = current;
+                best = Some(rect);
+            }
+        }
+
+        best
+    }
+}
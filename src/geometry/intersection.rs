@@ -1,5 +1,6 @@
 use super::{Line, Point, LineSegment, Polygon};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineSegmentLineSegmentIntersectionResult {
     None,
     Point(Point),
@@ -8,12 +9,14 @@ pub enum LineSegmentLineSegmentIntersectionResult {
 
 // possible result cases for line intersections
 #[derive(PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineLineIntersectionResult {
     None, // No intersection, lines are parallel
     Point(Point), // intersection in a single point
     Equal, // input lines are parallel (or anti-parallel) and have the same y offset
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PolygonPolygonIntersectionResult {
     None, // No intersection
     Point(Point), // intersection in a single point
@@ -30,7 +33,7 @@ impl Intersection {
     pub fn line_line(first: &Line, other: &Line, tol: f64) -> LineLineIntersectionResult {
         // test parallel
         if first.is_parallel_to(other, tol){
-            println!("Parallel lines, {:?} and {:?}", first, other);
+            log_trace!("line line: parallel lines");
             // pick any point on first line and see if it lies on other line
             let pt_test = first.point_at(0.0);
             if other.is_point_on(&pt_test, tol){
@@ -152,8 +155,7 @@ impl Intersection {
         let mut int_pts: Vec<Point> = Vec::new();
         let mut found_intersection = false;
 
-        println!("first edges are: {:?}", first_edges);
-        println!("other edges are: {:?}", other_edges);
+        log_debug!(first_edges = first_edges.len(), other_edges = other_edges.len(); "polygon polygon intersection");
 
         for f_edge in &first_edges {
             for o_edge in &other_edges {
@@ -1,6 +1,7 @@
 use super::{Point, constants};
 use std::f64::{INFINITY, NEG_INFINITY};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LinePointRelation {
     Left,
     Right,
@@ -8,6 +9,7 @@ pub enum LinePointRelation {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Line {
     pub m: f64, // rate of change
     pub b: f64 // y offset
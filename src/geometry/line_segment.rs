@@ -1,6 +1,7 @@
 use super::{Line, Point, Vector};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineSegment{
     pub line: Line,
     pub from: Point,
@@ -63,6 +64,33 @@ impl LineSegment{
             y: self.from.y + t * (self.to.y - self.from.y)}
     }
 
+    /// Closest point on the line segment to a given test point
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::geometry::{Point, LineSegment};
+    /// 
+    /// let line = LineSegment::new_from_points(&Point::new(), &Point::new().set_values(2.0, 0.0));
+    /// 
+    /// assert_eq!(line.closest_point(&Point::new().set_values(1.0, 3.0)), Point::new().set_values(1.0, 0.0));
+    /// assert_eq!(line.closest_point(&Point::new().set_values(5.0, 1.0)), Point::new().set_values(2.0, 0.0));
+    /// ```
+    pub fn closest_point(&self, pt: &Point) -> Point {
+        let len_squared = self.direction.dot_product(&self.direction);
+        if len_squared == 0.0 {
+            return self.from;
+        }
+
+        let t = Vector::new_from_points(&self.from, pt).dot_product(&self.direction) / len_squared;
+        self.point_at_normalized_parameter(t.clamp(0.0, 1.0))
+    }
+
+    // public point distance, see `closest_point`
+    pub fn distance_to_point(&self, pt: &Point) -> f64 {
+        pt.distance_to(&self.closest_point(pt))
+    }
+
     pub fn is_from_to_coincident(&self, other: &LineSegment, tol: f64) -> bool {
         (self.from.epsilon_equals(&other.from, tol) | self.from.epsilon_equals(&other.to, tol)) &&
         (self.to.epsilon_equals(&other.from, tol) | self.to.epsilon_equals(&other.to, tol))
@@ -0,0 +1,181 @@
+use super::{Point, Polygon, PolygonWithHoles};
+use std::f64::consts::PI;
+
+/// Area, perimeter, centroid and second moments of area of a polygon
+/// All second moments are taken about axes through the centroid
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MassProperties {
+    pub area: f64,
+    pub perimeter: f64, // summed over all rings, holes included
+    pub centroid: Point,
+    pub ixx: f64, // second moment about the horizontal axis, integral of y^2
+    pub iyy: f64, // second moment about the vertical axis, integral of x^2
+    pub ixy: f64, // product of inertia, integral of x * y
+    pub principal_moments: (f64, f64), // minimum and maximum second moment
+    pub principal_angle: f64 // angle of the axis with the minimum second moment to the x-axis, between 0 and PI
+}
+
+// private running sums of the shoelace integrals over a single ring
+#[derive(Default)]
+struct RingIntegrals {
+    area: f64,
+    first_x: f64,
+    first_y: f64,
+    second_xx: f64,
+    second_yy: f64,
+    second_xy: f64,
+    perimeter: f64
+}
+
+impl RingIntegrals {
+    // all integrals in a single pass over the ring, signed by its orientation
+    fn new_from_ring(ring: &Polygon) -> RingIntegrals {
+        let mut sums = RingIntegrals::default();
+        let pt_count = ring.points.len();
+
+        for (i, pt) in ring.points.iter().enumerate() {
+            let next = &ring.points[(i + 1) % pt_count];
+            let cross = pt.x * next.y - next.x * pt.y;
+
+            sums.area += cross;
+            sums.first_x += (pt.x + next.x) * cross;
+            sums.first_y += (pt.y + next.y) * cross;
+            sums.second_xx += (pt.x * pt.x + pt.x * next.x + next.x * next.x) * cross;
+            sums.second_yy += (pt.y * pt.y + pt.y * next.y + next.y * next.y) * cross;
+            sums.second_xy += (pt.x * next.y + 2.0 * pt.x * pt.y + 2.0 * next.x * next.y + next.x * pt.y) * cross;
+            sums.perimeter += pt.distance_to(next);
+        }
+
+        sums.area /= 2.0;
+        sums.first_x /= 6.0;
+        sums.first_y /= 6.0;
+        sums.second_xx /= 12.0;
+        sums.second_yy /= 12.0;
+        sums.second_xy /= 24.0;
+        sums
+    }
+
+    // add another ring, either as filled area or as hole
+    fn accumulate(&mut self, other: &RingIntegrals, is_hole: bool) {
+        // flip sign so filled rings count positive and holes negative, whatever their orientation
+        let mut sign = other.area.signum();
+        if is_hole {
+            sign *= -1.0;
+        }
+
+        self.area += sign * other.area;
+        self.first_x += sign * other.first_x;
+        self.first_y += sign * other.first_y;
+        self.second_xx += sign * other.second_xx;
+        self.second_yy += sign * other.second_yy;
+        self.second_xy += sign * other.second_xy;
+        self.perimeter += other.perimeter;
+    }
+}
+
+impl MassProperties {
+    /// Mass properties of a single polygon ring
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point, MassProperties};
+    /// 
+    /// let tol = 0.000001; // geometric tolerance
+    /// 
+    /// let rect = Polygon::from_points(&vec![
+    ///     Point::new().set_values(1.0, 1.0),
+    ///     Point::new().set_values(5.0, 1.0),
+    ///     Point::new().set_values(5.0, 3.0),
+    ///     Point::new().set_values(1.0, 3.0)]);
+    /// 
+    /// let props = MassProperties::from_polygon(&rect);
+    /// assert_eq!(props.area, 8.0);
+    /// assert_eq!(props.perimeter, 12.0);
+    /// assert!(props.centroid.epsilon_equals(&Point::new().set_values(3.0, 2.0), tol));
+    /// assert!((props.ixx - 4.0 * 8.0 / 12.0).abs() < tol); // b * h^3 / 12
+    /// assert!((props.iyy - 2.0 * 64.0 / 12.0).abs() < tol); // h * b^3 / 12
+    /// assert!(props.principal_angle.abs() < tol); // long side is horizontal
+    /// ```
+    pub fn from_polygon(poly: &Polygon) -> MassProperties {
+        let mut sums = RingIntegrals::default();
+        sums.accumulate(&RingIntegrals::new_from_ring(poly), false);
+        MassProperties::from_integrals(&sums, poly)
+    }
+
+    /// Mass properties of a polygon with holes, holes are subtracted independent of their orientation
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles, MassProperties};
+    /// 
+    /// let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+    /// 
+    /// let props = MassProperties::from_polygon_with_holes(&frame);
+    /// assert_eq!(props.area, 12.0);
+    /// assert_eq!(props.perimeter, 24.0);
+    /// assert_eq!(props.ixx, (256.0 - 16.0) / 12.0);
+    /// ```
+    pub fn from_polygon_with_holes(poly: &PolygonWithHoles) -> MassProperties {
+        let mut sums = RingIntegrals::default();
+        sums.accumulate(&RingIntegrals::new_from_ring(&poly.outer), false);
+        for hole in poly.holes.iter() {
+            sums.accumulate(&RingIntegrals::new_from_ring(hole), true);
+        }
+        MassProperties::from_integrals(&sums, &poly.outer)
+    }
+
+    // private helper shifting the integrals to the centroid and solving for the principal axes
+    fn from_integrals(sums: &RingIntegrals, outer: &Polygon) -> MassProperties {
+        let centroid = if sums.area.abs() > 0.0 {
+            Point::new().set_values(sums.first_x / sums.area, sums.first_y / sums.area)
+        }
+        else {
+            // degenerate polygon, fall back to the average corner
+            let count = outer.points.len().max(1) as f64;
+            Point::new().set_values(
+                outer.points.iter().map(|p| p.x).sum::<f64>() / count,
+                outer.points.iter().map(|p| p.y).sum::<f64>() / count)
+        };
+
+        // parallel axis theorem
+        let ixx = sums.second_yy - sums.area * centroid.y * centroid.y;
+        let iyy = sums.second_xx - sums.area * centroid.x * centroid.x;
+        let ixy = sums.second_xy - sums.area * centroid.x * centroid.y;
+
+        // eigenvalues of the second moment tensor
+        let average = (ixx + iyy) / 2.0;
+        let radius = (((ixx - iyy) / 2.0).powi(2) + ixy * ixy).sqrt();
+        let mut principal_angle = 0.5 * (2.0 * ixy).atan2(iyy - ixx);
+        if principal_angle < 0.0 {
+            principal_angle += PI;
+        }
+
+        MassProperties {
+            area: sums.area,
+            perimeter: sums.perimeter,
+            centroid,
+            ixx,
+            iyy,
+            ixy,
+            principal_moments: (average - radius, average + radius),
+            principal_angle
+        }
+    }
+}
+
+impl Polygon {
+    // public mass properties, see `MassProperties::from_polygon`
+    pub fn calculate_mass_properties(&self) -> MassProperties {
+        MassProperties::from_polygon(self)
+    }
+}
+
+impl PolygonWithHoles {
+    // public mass properties, see `MassProperties::from_polygon_with_holes`
+    pub fn calculate_mass_properties(&self) -> MassProperties {
+        MassProperties::from_polygon_with_holes(self)
+    }
+}
@@ -12,6 +12,9 @@ mod polygon;
 
 pub use self::polygon::{PolygonEdgeRelation, PolygonMergeResult};
 
+pub use self::polygon_with_holes::PolygonWithHoles;
+mod polygon_with_holes;
+
 pub use self::line_segment::LineSegment;
 mod line_segment;
 
@@ -23,6 +26,47 @@ pub use self::intersection::{LineLineIntersectionResult, PolygonPolygonIntersect
 pub use self::vector::Vector;
 mod vector;
 
+pub use self::enclosing_rectangle::EnclosingRectangle;
+mod enclosing_rectangle;
+
+pub use self::simplify::SimplifyMode;
+mod simplify;
+
+pub use self::mass_properties::MassProperties;
+mod mass_properties;
+
+pub use self::distance::{Distance, ClosestPoints};
+mod distance;
+
+pub use self::triangulation::TriangulationError;
+mod triangulation;
+
+pub use self::convex_decomposition::DecompositionMode;
+mod convex_decomposition;
+
+mod delaunay;
+
+pub use self::bounding_box::BoundingBox;
+mod bounding_box;
+
+pub use self::arc::Arc;
+mod arc;
+
+pub use self::path::{Path, PathSegment};
+mod path;
+
+pub use self::curve::{QuadraticBezier, CubicBezier, BSpline, CurveFlattening};
+mod curve;
+
+pub use self::transform::Transform;
+mod transform;
+
+pub use self::validation::PolygonIssue;
+mod validation;
+
+pub use self::boolean::BooleanOperation;
+mod boolean;
+
 pub mod constants{
     // unset value for undefined geometry
     pub const UNSET_VALUE: f64 = -123456.789;
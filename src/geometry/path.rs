@@ -0,0 +1,325 @@
+use super::{Point, Vector, Polygon, LineSegment, Arc, BoundingBox};
+use super::arc::{angle_of, normalize_angle};
+
+/// Single piece of a `Path`, either a straight line or a circular arc
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PathSegment {
+    Line(LineSegment),
+    Arc(Arc)
+}
+
+impl PathSegment {
+    pub fn start_point(&self) -> Point {
+        match self {
+            PathSegment::Line(line) => line.from,
+            PathSegment::Arc(arc) => arc.start_point()
+        }
+    }
+
+    pub fn end_point(&self) -> Point {
+        match self {
+            PathSegment::Line(line) => line.to,
+            PathSegment::Arc(arc) => arc.end_point()
+        }
+    }
+
+    // public unit tangent in running direction at the start point
+    pub fn start_tangent(&self) -> Vector {
+        match self {
+            PathSegment::Line(line) => line.direction.as_normalized(),
+            PathSegment::Arc(arc) => arc.tangent_at_normalized_parameter(0.0)
+        }
+    }
+
+    // public unit tangent in running direction at the end point
+    pub fn end_tangent(&self) -> Vector {
+        match self {
+            PathSegment::Line(line) => line.direction.as_normalized(),
+            PathSegment::Arc(arc) => arc.tangent_at_normalized_parameter(1.0)
+        }
+    }
+
+    pub fn calculate_length(&self) -> f64 {
+        match self {
+            PathSegment::Line(line) => line.calculate_length(),
+            PathSegment::Arc(arc) => arc.calculate_length()
+        }
+    }
+
+    pub fn bounding_box(&self) -> BoundingBox {
+        match self {
+            PathSegment::Line(line) => BoundingBox::new_from_points(&[line.from, line.to]),
+            PathSegment::Arc(arc) => arc.bounding_box()
+        }
+    }
+
+    // public points along the segment, including both end points, see `Arc::tessellate`
+    pub fn tessellate(&self, chord_error: f64) -> Vec<Point> {
+        match self {
+            PathSegment::Line(line) => vec![line.from, line.to],
+            PathSegment::Arc(arc) => arc.tessellate(chord_error)
+        }
+    }
+
+    // private parallel copy, moved to the right of the running direction by distance
+    // returns None if an arc would collapse
+    fn offset_right(&self, distance: f64) -> Option<PathSegment> {
+        match self {
+            PathSegment::Line(line) => {
+                let n = line.direction.as_normalized();
+                let v = Vector::new().set_values(n.y * distance, -n.x * distance);
+                Some(PathSegment::Line(LineSegment::new_from_points(&line.from.copy_along_vector(&v), &line.to.copy_along_vector(&v))))
+            },
+            PathSegment::Arc(arc) => {
+                // counter-clockwise arcs have their center on the left
+                let grow = if arc.sweep >= 0.0 {distance} else {-distance};
+                arc.offset(grow).map(PathSegment::Arc)
+            }
+        }
+    }
+
+    // private trim of the end to a point on the segment's line or circle
+    fn set_end_point(&mut self, pt: &Point) {
+        match self {
+            PathSegment::Line(line) => *line = LineSegment::new_from_points(&line.from, pt),
+            PathSegment::Arc(arc) => {
+                let delta = angle_of(&arc.center, pt) - arc.start_angle;
+                arc.sweep = if arc.sweep >= 0.0 {normalize_angle(delta)} else {-normalize_angle(-delta)};
+            }
+        }
+    }
+
+    // private trim of the start to a point on the segment's line or circle
+    fn set_start_point(&mut self, pt: &Point) {
+        match self {
+            PathSegment::Line(line) => *line = LineSegment::new_from_points(pt, &line.to),
+            PathSegment::Arc(arc) => {
+                let end_angle = arc.end_angle();
+                arc.start_angle = angle_of(&arc.center, pt);
+                let delta = end_angle - arc.start_angle;
+                arc.sweep = if arc.sweep >= 0.0 {normalize_angle(delta)} else {-normalize_angle(-delta)};
+            }
+        }
+    }
+}
+
+/// Closed outline made of line and arc segments, each segment starts where the previous one ends
+/// Arcs are kept exact until the path is tessellated into a `Polygon`
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path {
+    pub segments: Vec<PathSegment>
+}
+
+impl Path {
+    // public new helper
+    pub fn new() -> Path {
+        Path{segments: Vec::new()}
+    }
+
+    // public constructor from a polygon, one line segment per edge
+    pub fn from_polygon(poly: &Polygon) -> Path {
+        Path{segments: poly.calculate_edges().into_iter().map(PathSegment::Line).collect()}
+    }
+
+    // public constructor for a full circle
+    pub fn circle(center: &Point, radius: f64) -> Path {
+        Path{segments: vec![PathSegment::Arc(Arc::circle(center, radius))]}
+    }
+
+    // public append of a line from the current end point
+    pub fn line_to(&mut self, pt: &Point) {
+        let from = self.end_point().expect("Path ERROR: Can not draw a line from an empty path!");
+        self.segments.push(PathSegment::Line(LineSegment::new_from_points(&from, pt)));
+    }
+
+    pub fn start_point(&self) -> Option<Point> {
+        self.segments.first().map(|s| s.start_point())
+    }
+
+    pub fn end_point(&self) -> Option<Point> {
+        self.segments.last().map(|s| s.end_point())
+    }
+
+    // public closed check under tol, true if every segment starts where the previous one ends
+    pub fn is_closed(&self, tol: f64) -> bool {
+        let count = self.segments.len();
+        count > 0 && (0..count).all(|n| self.segments[n].end_point().epsilon_equals(&self.segments[(n + 1) % count].start_point(), tol))
+    }
+
+    pub fn calculate_length(&self) -> f64 {
+        self.segments.iter().map(|s| s.calculate_length()).sum()
+    }
+
+    // public exact bounding box, see `Arc::bounding_box`
+    pub fn bounding_box(&self) -> BoundingBox {
+        self.segments.iter().fold(BoundingBox::empty(), |bbox, s| bbox.union(&s.bounding_box()))
+    }
+
+    /// Exact signed area of the closed path, positive if it runs counter-clockwise
+    /// Every arc adds the circular segment between itself and its chord to the shoelace sum
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Path, Point};
+    /// use std::f64::consts::PI;
+    ///
+    /// let circle = Path::circle(&Point::new(), 2.0);
+    /// assert!((circle.calculate_signed_area() - 4.0 * PI).abs() < 0.000001);
+    /// ```
+    pub fn calculate_signed_area(&self) -> f64 {
+        self.segments.iter()
+            .map(|s| {
+                let from = s.start_point();
+                let to = s.end_point();
+                let chord = (from.x * to.y - to.x * from.y) / 2.0;
+                match s {
+                    PathSegment::Line(_) => chord,
+                    PathSegment::Arc(arc) => chord + arc.radius * arc.radius / 2.0 * (arc.sweep - arc.sweep.sin())
+                }
+            })
+            .sum()
+    }
+
+    /// Polygon approximation of the path, no arc chord deviates more than `chord_error`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Path, Point};
+    ///
+    /// let path = Path::circle(&Point::new(), 1.0);
+    ///
+    /// let poly = path.tessellate(0.01);
+    /// assert!(poly.points.len() > 8);
+    /// assert!((poly.calculate_area() - path.calculate_signed_area()).abs() < 0.1);
+    /// ```
+    pub fn tessellate(&self, chord_error: f64) -> Polygon {
+        let mut pts: Vec<Point> = Vec::new();
+        for segment in self.segments.iter() {
+            let seg_pts = segment.tessellate(chord_error);
+            pts.extend_from_slice(&seg_pts[..seg_pts.len() - 1]);
+        }
+
+        Polygon::from_points(&pts)
+    }
+
+    /// Offset of the closed path, positive distances grow and negative ones shrink the enclosed area
+    /// Convex corners are rounded with arcs, concave corners are trimmed to the intersection of their neighbours,
+    /// so the result is exact as long as the distance stays below the size of the smallest feature
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Path, Polygon};
+    /// use std::f64::consts::PI;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let square = Path::from_polygon(&Polygon::square(2.0));
+    ///
+    /// let grown = square.offset(0.5, tol);
+    /// assert_eq!(grown.segments.len(), 8);
+    /// assert!((grown.calculate_signed_area() - (4.0 + 4.0 + 0.25 * PI)).abs() < tol);
+    ///
+    /// let shrunk = square.offset(-0.5, tol);
+    /// assert!((shrunk.calculate_signed_area() - 1.0).abs() < tol);
+    /// ```
+    pub fn offset(&self, distance: f64, tol: f64) -> Path {
+        // outward is on the right of counter-clockwise paths
+        let right = if self.calculate_signed_area() >= 0.0 {distance} else {-distance};
+
+        let mut pieces: Vec<(PathSegment, Point)> = self.segments.iter()
+            .filter_map(|s| s.offset_right(right).map(|o| (o, s.end_point())))
+            .collect();
+
+        let count = pieces.len();
+        let mut joints: Vec<Option<PathSegment>> = vec![None; count];
+        for n in 0..count {
+            let next = (n + 1) % count;
+            let corner = pieces[n].1;
+            let end = pieces[n].0.end_point();
+            let start = pieces[next].0.start_point();
+            if end.epsilon_equals(&start, tol) {
+                continue;
+            }
+
+            let turn = Vector::cross_product(&pieces[n].0.end_tangent(), &pieces[next].0.start_tangent());
+            if turn * right > 0.0 {
+                // gap at a convex corner, round it around the original corner
+                let sweep = pieces[n].0.end_tangent().angle_to(&pieces[next].0.start_tangent());
+                let sweep = if turn < 0.0 {sweep - 2.0 * std::f64::consts::PI} else {sweep};
+                joints[n] = Some(PathSegment::Arc(Arc::new(&corner, right.abs(), angle_of(&corner, &end), sweep)));
+                continue;
+            }
+
+            // overlap at a concave corner, trim both pieces to their intersection closest to the corner
+            let hit = intersect_carriers(&pieces[n].0, &pieces[next].0, tol).into_iter()
+                .min_by(|a, b| a.distance_to(&corner).partial_cmp(&b.distance_to(&corner)).unwrap());
+            match hit {
+                Some(pt) => {
+                    pieces[n].0.set_end_point(&pt);
+                    pieces[next].0.set_start_point(&pt);
+                },
+                None => joints[n] = Some(PathSegment::Line(LineSegment::new_from_points(&end, &start)))
+            }
+        }
+
+        let mut segments = Vec::with_capacity(2 * count);
+        for (piece, joint) in pieces.into_iter().zip(joints) {
+            segments.push(piece.0);
+            if let Some(joint) = joint {
+                segments.push(joint);
+            }
+        }
+
+        Path{segments}
+    }
+}
+
+// private intersection of the infinite line or full circle carrying each segment
+fn intersect_carriers(first: &PathSegment, other: &PathSegment, tol: f64) -> Vec<Point> {
+    match (first, other) {
+        (PathSegment::Line(l0), PathSegment::Line(l1)) => {
+            let denom = Vector::cross_product(&l0.direction, &l1.direction);
+            if denom.abs() < tol {
+                return Vec::new();
+            }
+            let t = Vector::cross_product(&Vector::new_from_points(&l0.from, &l1.from), &l1.direction) / denom;
+            vec![Point::new().set_values(l0.from.x + t * l0.direction.x, l0.from.y + t * l0.direction.y)]
+        },
+        (PathSegment::Line(line), PathSegment::Arc(arc)) | (PathSegment::Arc(arc), PathSegment::Line(line)) => {
+            // solve |from + t * d - center| = r
+            let d = line.direction;
+            let f = Vector::new_from_points(&arc.center, &line.from);
+            let a = d.dot_product(&d);
+            let b = 2.0 * f.dot_product(&d);
+            let c = f.dot_product(&f) - arc.radius * arc.radius;
+            let disc = b * b - 4.0 * a * c;
+            if a == 0.0 || disc < 0.0 {
+                return Vec::new();
+            }
+            [-1.0, 1.0].iter()
+                .map(|sign| (-b + sign * disc.sqrt()) / (2.0 * a))
+                .map(|t| Point::new().set_values(line.from.x + t * d.x, line.from.y + t * d.y))
+                .collect()
+        },
+        (PathSegment::Arc(a0), PathSegment::Arc(a1)) => {
+            let dist = a0.center.distance_to(&a1.center);
+            if dist < tol || dist > a0.radius + a1.radius || dist < (a0.radius - a1.radius).abs() {
+                return Vec::new();
+            }
+            // foot of the chord between both intersections, measured from the first center
+            let along = (dist * dist + a0.radius * a0.radius - a1.radius * a1.radius) / (2.0 * dist);
+            let half = (a0.radius * a0.radius - along * along).max(0.0).sqrt();
+            let u = Vector::new_from_points(&a0.center, &a1.center).as_normalized();
+            [-1.0, 1.0].iter()
+                .map(|sign| Point::new().set_values(
+                    a0.center.x + along * u.x - sign * half * u.y,
+                    a0.center.y + along * u.y + sign * half * u.x))
+                .collect()
+        }
+    }
+}
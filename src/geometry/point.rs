@@ -2,6 +2,7 @@ use super::{Vector, constants};
 
 #[repr(C)]
 #[derive(Debug, Clone, PartialEq, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point{
     pub x: f64,
     pub y: f64
@@ -1,19 +1,20 @@
-use super::{Point, Line, LineSegment, Vector, Intersection, LineSegmentLineSegmentIntersectionResult};
-use std::iter::FromIterator;
+use super::{Point, Line, LineSegment, Vector, TriangulationError, DecompositionMode};
 use std::f64::consts::PI;
-use std::collections::HashSet;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PolygonEdgeRelation {
     None,
     Shared(usize, usize)
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PolygonMergeResult {
     None,
     Merged(Polygon)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polygon{
     pub points: Vec<Point>,
 }
@@ -84,6 +85,24 @@ impl Polygon {
 
     // public area calculation
     pub fn calculate_area(&self) -> f64 {
+        // keep area positive
+        self.calculate_signed_area().abs()
+    }
+
+    /// Signed area of the polygon, positive for counter-clockwise and negative for clockwise orientation
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::geometry::{Polygon};
+    /// 
+    /// let mut poly = Polygon::square(2.0); // counter-clockwise square
+    /// assert_eq!(poly.calculate_signed_area(), 4.0);
+    /// 
+    /// poly.reverse_orientation();
+    /// assert_eq!(poly.calculate_signed_area(), -4.0);
+    /// ```
+    pub fn calculate_signed_area(&self) -> f64 {
         let mut area: f64 = 0.0;
         let pt_count = self.points.len();
 
@@ -94,11 +113,6 @@ impl Polygon {
             let int_value = pt.x * next_point.y - pt.y * next_point.x;
             area += int_value;
         }
-            
-        // keep area positive
-        if area < 0.0 {
-            area *= -1.0
-            };
 
         area / 2.0
     }
@@ -126,9 +140,26 @@ impl Polygon {
         LineSegment::new_from_points(&self.points[index], &self.points[next_index])
     }
 
-    // public concavity test
+    // public concavity test, a convex polygon turns to the same side at every corner
+    // collinear corners do not count, so convex polygons with obtuse angles are not concave
     pub fn is_concave(&self) -> bool {
-        self.calculate_angles().iter().any(|&x| x > std::f64::consts::PI / 2.0)
+        let corner_count = self.points.len();
+        let mut turns_left = false;
+        let mut turns_right = false;
+
+        for i in 0..corner_count {
+            let prev_index = (i + corner_count - 1) % corner_count;
+            let next_index = (i + 1) % corner_count;
+
+            let v_from_last_corner = Vector::new_from_points(&self.points[prev_index], &self.points[i]);
+            let v_to_next_corner = Vector::new_from_points(&self.points[i], &self.points[next_index]);
+
+            let turn = Vector::cross_product(&v_from_last_corner, &v_to_next_corner);
+            turns_left |= turn > 0.0;
+            turns_right |= turn < 0.0;
+        }
+
+        turns_left && turns_right
     }
 
     // public convex test
@@ -165,6 +196,52 @@ impl Polygon {
         false
     }
 
+    /// Point in polygon test by counting crossings of a horizontal ray, works for both orientations
+    /// Points closer than tol to the boundary are considered inside
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    /// 
+    /// let tol = 0.0001; // geometric tolerance
+    /// 
+    /// let square = Polygon::square(2.0);
+    /// assert!(square.contains_point(&Point::new(), tol));
+    /// assert!(square.contains_point(&Point::new().set_values(1.0, 0.5), tol)); // on boundary
+    /// assert!(!square.contains_point(&Point::new().set_values(1.5, 0.5), tol));
+    /// ```
+    pub fn contains_point(&self, pt_test: &Point, tol: f64) -> bool {
+        let pt_count = self.points.len();
+        let mut inside = false;
+
+        for i in 0..pt_count {
+            let from = &self.points[i];
+            let to = &self.points[(i + 1) % pt_count];
+
+            if LineSegment::new_from_points(from, to).distance_to_point(pt_test) < tol {
+                return true;
+            }
+
+            // edge straddles the ray, compare the crossing with the test point
+            if (from.y > pt_test.y) != (to.y > pt_test.y) {
+                let x_crossing = from.x + (pt_test.y - from.y) / (to.y - from.y) * (to.x - from.x);
+                if pt_test.x < x_crossing {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+
+    // public translated copy
+    pub fn copy_along_vector(&self, v: &Vector) -> Polygon {
+        Polygon{
+            points: self.points.iter().map(|p| p.copy_along_vector(v)).collect()
+        }
+    }
+
     // public reverse orientation
     pub fn reverse_orientation(&mut self) {
         self.points.reverse()
@@ -249,7 +326,7 @@ impl Polygon {
             return corners;
         }
 
-        println!("WithoutEdge: trying to take slice from {} to {} on {:?}", start_index, end_index, &self.points);
+        log_trace!(start = start_index, end = end_index, corners = self.points.len(); "corners without edge");
         self.points[start_index..end_index].into_iter().cloned().collect::<Vec<Point>>()
         
     }
@@ -278,112 +355,64 @@ impl Polygon {
         Polygon::from_points(&corners)
     }
 
-    pub fn triangulate(&self, tol: f64) -> Vec<Polygon> {
-        let mut convex_parts: Vec<Polygon> = Vec::new();
+    /// Convex hull of the polygon corners, using Andrew's monotone chain
+    /// The hull is oriented counter-clockwise and colinear corners (under tol) are dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let pt0 = Point::new();
+    /// let pt1 = Point::new().set_values(2.0, 0.0);
+    /// let pt2 = Point::new().set_values(1.0, 1.0); // notch, will be dropped
+    /// let pt3 = Point::new().set_values(2.0, 2.0);
+    /// let pt4 = Point::new().set_values(0.0, 2.0);
+    /// let poly = Polygon::from_points(&vec![pt0, pt1, pt2, pt3, pt4]);
+    ///
+    /// let hull = poly.convex_hull(tol);
+    /// assert_eq!(hull.points.len(), 4);
+    /// assert!((hull.calculate_area() - 4.0).abs() < tol);
+    /// ```
+    pub fn convex_hull(&self, tol: f64) -> Polygon {
+        let mut pts = self.points.clone();
+        pts.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap().then(a.y.partial_cmp(&b.y).unwrap()));
+        pts.dedup_by(|a, b| a.epsilon_equals(b, tol));
 
-        // helper struct
-        #[derive(Debug)]
-        struct PartitionLine {
-            pub from: usize,
-            pub to: usize,
-            pub middle: usize,
-            pub length: f64
+        if pts.len() < 3 {
+            return Polygon::from_points(&pts);
         }
 
-        impl PartitionLine {
-            pub fn all_indices(&self) -> HashSet<usize> {
-                let mut all: HashSet<usize> = [self.from, self.middle, self.to].iter().cloned().collect();
-                all
-            }
+        // cross product of (o -> a) and (o -> b), positive for a left turn
+        let turn = |o: &Point, a: &Point, b: &Point| {
+            Vector::cross_product(&Vector::new_from_points(o, a), &Vector::new_from_points(o, b))
+        };
 
-            pub fn is_index_intersecting(&self, other: &PartitionLine) -> bool {
-                self.all_indices().is_disjoint(&other.all_indices())
-            }
+        let mut hull: Vec<Point> = Vec::with_capacity(2 * pts.len());
 
-            pub fn contains_other_middle(&self, other: &PartitionLine) -> bool {
-                self.all_indices().contains(&other.middle)
+        // lower hull
+        for pt in pts.iter() {
+            while hull.len() >= 2 && turn(&hull[hull.len() - 2], &hull[hull.len() - 1], pt) <= tol {
+                hull.pop();
             }
+            hull.push(*pt);
         }
 
-        // copy points in array that can be manipulated
-        let mut corners = Vec::from_iter(self.points[..].iter().cloned());
-
-        while corners.len() > 3 {
-            let poly = Polygon::from_points(&corners);
-            let corner_count = poly.points.len();
-            let angles = poly.calculate_angles();
-            let mut possible_partition_lines: Vec<PartitionLine> = Vec::new();
-            let edges = poly.calculate_edges();
-
-            println!("Current poly is: {:?}", poly);
-
-            // iterate over all corners
-            for n in 0..corner_count {
-                // inner angle > 180° -> cannot be a valid partition line
-                if angles[n] > PI {
-                    continue;
-                }
-
-                let prev_index = (n + corner_count - 1) % corner_count;
-                let next_index = (n + 1) % corner_count;
-                let mut is_intersecting = false;
-
-                let line = LineSegment::new_from_points(&poly.points[prev_index], &poly.points[next_index]);
-                for i in 0..corner_count {
-                    if (i == prev_index) | (i == n) {
-                        continue
-                    }
-                    let edge = &edges[i];
-                    match Intersection::line_segment_line_segment(&line, edge, tol) {
-                        LineSegmentLineSegmentIntersectionResult::None => continue,
-                        LineSegmentLineSegmentIntersectionResult::Overlap(_) => {
-                            is_intersecting = true;
-                            break;
-                        },
-                        LineSegmentLineSegmentIntersectionResult::Point(pt) => {
-                            if pt.epsilon_equals(&edge.from, tol) | pt.epsilon_equals(&edge.to, tol) {
-                                // println!("Line for corner {}, intersecting edge {}, Point was fine {:?}", n, i, pt);
-                                continue;
-                            }
-                            else {
-                                // println!("Line for corner {}, intersecting edge {}, Point was NOT fine {:?} \nLines in question are {:?} and \n{:?}", n, i, pt, line, edge);
-                                is_intersecting = true;
-                                break;
-                            }
-                        }
-                    }
-                }
-
-                if is_intersecting{
-                    continue;
-                }
-                // TODO: We can calculate the angles here explicitly, so we don't have to iterate the corners twice
-
-                // push partition line
-                possible_partition_lines.push(PartitionLine{
-                    from: prev_index, 
-                    to: next_index,
-                    middle: n,
-                    length: poly.points[prev_index].distance_to_squared(&poly.points[next_index])});
+        // upper hull
+        let lower_len = hull.len() + 1;
+        for pt in pts.iter().rev().skip(1) {
+            while hull.len() >= lower_len && turn(&hull[hull.len() - 2], &hull[hull.len() - 1], pt) <= tol {
+                hull.pop();
             }
-
-            // sort partition lines by their length
-            possible_partition_lines.sort_by(|a, b| b.length.partial_cmp(&a.length).unwrap());
-
-            // println!("Partition lines sorted are: {:?}", possible_partition_lines);
-
-            // shortest partition line is what we want
-            let found_partition = possible_partition_lines.pop().unwrap();
-            convex_parts.push(Polygon::from_points(&vec![poly.points[found_partition.from], poly.points[found_partition.middle], poly.points[found_partition.to]]));
-
-            // remove used vertex and start over
-            corners.remove(found_partition.middle);
+            hull.push(*pt);
         }
 
-        // corners now has only 3 points left -> convex poly
-        convex_parts.push(Polygon::from_points(&corners));
+        // last point is the first one again
+        hull.pop();
 
-        return convex_parts;
+        Polygon{points: hull}
     }
 
     pub fn merge_convex_polygon(first: &Polygon, other: &Polygon, tol: f64) -> PolygonMergeResult {
@@ -398,7 +427,7 @@ impl Polygon {
             }
         };
 
-        println!("Passed shared edge test...");
+        log_trace!(first_edge = first_edge_index, other_edge = other_edge_index; "merge convex: shared edge");
 
         let first_shared_vertex_indices = first.edge_from_to(first_edge_index);
         let other_shared_vertex_indices = other.edge_from_to(other_edge_index);
@@ -413,14 +442,12 @@ impl Polygon {
             &first.points[first_shared_vertex_indices.1],
             tol) {
                 LinePointRelation::Right =>{
-                    println!("Got right for first: {:?}, other: {:?} and test: {:?} ", &first.points[prev], &other.points[next], &first.points[first_shared_vertex_indices.1]);
+                    log_trace!(corner = first_shared_vertex_indices.1; "merge convex: merged corner would be reflex");
                     return PolygonMergeResult::None},
                 LinePointRelation::On => return PolygonMergeResult::None,
                 LinePointRelation::Left => (),//do nothing
             };
         
-        println!("Passed first point relation test...");
-        
         // second shared vertex
         match Line::line_point_relation_fast(
             &other.points[next],
@@ -432,64 +459,24 @@ impl Polygon {
                 LinePointRelation::Left => (), // do nothing
             };
         
-        println!("Passed second vertex relation test...");
-        
         // if we get here we can finally merge
         let mut other_corners = other.corners_without_edge(other_edge_index);
         let mut corners: Vec<Point> = first.points.iter().cloned().collect();
 
-        println!("other_corners are: {:?}", other_corners);
-
-        println!("Attempting to take slice from {}, to {} on vec: {:?}", first_shared_vertex_indices.0, first_shared_vertex_indices.1, corners);
-        
         if first_shared_vertex_indices.1 == 0 {
             corners.append(&mut other_corners);
-            println!("Appending... corners are now: {:?}", &corners);
         }
         else {
             corners.splice(first_shared_vertex_indices.0..first_shared_vertex_indices.1, other_corners.into_iter());
-            println!("Splicing... corners are now: {:?}", &corners);
         }
+        log_trace!(first_corners = first.points.len(), other_corners = other.points.len(), merged_corners = corners.len(); "merge convex: merged");
 
         return PolygonMergeResult::Merged(Polygon::from_points(&corners));
 
     }
 
-    pub fn subdivide_concave_polygon_in_convex_pieces(&self, tol: f64) -> Vec<Polygon> {
-        // triangulate
-        let mut tris = self.triangulate(tol);
-
-        let mut merge_occured = true;
-
-        while merge_occured {
-            merge_occured = false; // set merge flag to false
-            let len_tris = tris.len();
-            let mut merged: Vec<Polygon> = Vec::with_capacity(len_tris); // empty vec to hold all merged polys
-            let mut merged_indices: Vec<usize> = Vec::with_capacity(len_tris); // same thing for indices of merged to clean up at end of loop
-            for i in 0..len_tris {
-                for j in 0..len_tris {
-                    if i == j {continue;} // dont merge same polys :)
-                    if merged_indices.contains(&i) | merged_indices.contains(&j) {continue;} // if already merged dont merge again
-                    match Polygon::merge_convex_polygon(&tris[i], &tris[j], tol) {
-                        PolygonMergeResult::None => continue,
-                        PolygonMergeResult::Merged(m) => {
-                            merge_occured = true;
-                            merged_indices.push(i);
-                            merged_indices.push(j);
-                            merged.push(m);
-                        }
-                    }
-                }
-            }
-            merged_indices.sort(); // sort merged indices
-            merged_indices.reverse();
-            // remove all polys at merged indices
-            for index in merged_indices {
-                tris.remove(index);
-            }
-            tris.append(&mut merged); // move all merged polys into tri vec
-        }
-
-        return tris;
+    // public convex subdivision, see `decompose_convex` for a choice of algorithms
+    pub fn subdivide_concave_polygon_in_convex_pieces(&self, tol: f64) -> Result<Vec<Polygon>, TriangulationError> {
+        self.decompose_convex(DecompositionMode::HertelMehlhorn, tol)
     }
 }
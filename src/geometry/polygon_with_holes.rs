@@ -0,0 +1,114 @@
+use super::{Polygon, Distance};
+
+/// Polygon with an outer boundary and any number of holes
+/// The orientation of the rings is not enforced, all calculations normalize it themselves
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonWithHoles {
+    pub outer: Polygon,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub holes: Vec<Polygon>
+}
+
+impl PolygonWithHoles {
+    // public constructor from an outer boundary and holes
+    pub fn new(outer: Polygon, holes: Vec<Polygon>) -> PolygonWithHoles {
+        PolygonWithHoles{outer, holes}
+    }
+
+    // public constructor for a polygon without holes
+    pub fn from_outer(outer: Polygon) -> PolygonWithHoles {
+        PolygonWithHoles{outer, holes: Vec::new()}
+    }
+
+    // public hole check
+    pub fn has_holes(&self) -> bool {
+        !self.holes.is_empty()
+    }
+
+    /// Area of the outer boundary minus the area of all holes
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// 
+    /// let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+    /// assert_eq!(frame.calculate_area(), 12.0);
+    /// ```
+    pub fn calculate_area(&self) -> f64 {
+        self.outer.calculate_area() - self.holes.iter().map(|h| h.calculate_area()).sum::<f64>()
+    }
+
+    // public orientation fix, the outer boundary runs counter-clockwise and all holes clockwise
+    pub fn normalize_orientation(&mut self) {
+        if self.outer.calculate_signed_area() < 0.0 {
+            self.outer.reverse_orientation();
+        }
+        for hole in self.holes.iter_mut().filter(|h| h.calculate_signed_area() > 0.0) {
+            hole.reverse_orientation();
+        }
+    }
+
+    // public iterator over all rings, outer boundary first
+    pub fn rings(&self) -> impl Iterator<Item = &Polygon> {
+        std::iter::once(&self.outer).chain(self.holes.iter())
+    }
+
+    /// Nests closed rings into polygons with holes by the even-odd rule
+    /// A ring inside an even number of other rings is an outer boundary, a ring inside an odd number
+    /// is a hole of the smallest ring around it. Rings with less than 3 points or an area below tol are dropped
+    /// Outer boundaries are oriented counter-clockwise, holes clockwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// // island inside a hole inside a frame
+    /// let rings = vec![Polygon::square(2.0), Polygon::square(6.0), Polygon::square(4.0)];
+    ///
+    /// let nested = PolygonWithHoles::from_rings(rings, tol);
+    /// assert_eq!(nested.len(), 2);
+    /// assert_eq!(nested[0].holes.len(), 1);
+    /// assert_eq!(nested[0].calculate_area(), 20.0);
+    /// assert!(!nested[1].has_holes());
+    /// ```
+    pub fn from_rings(rings: Vec<Polygon>, tol: f64) -> Vec<PolygonWithHoles> {
+        let mut rings = rings.into_iter()
+            .filter(|r| r.points.len() >= 3 && r.calculate_area() >= tol)
+            .collect::<Vec<_>>();
+        rings.sort_by(|a, b| b.calculate_area().partial_cmp(&a.calculate_area()).unwrap());
+
+        // index into the result for outer rings, None for holes
+        let mut owners: Vec<Option<usize>> = Vec::with_capacity(rings.len());
+        let mut result: Vec<PolygonWithHoles> = Vec::new();
+
+        for (i, ring) in rings.iter().enumerate() {
+            // smallest larger ring containing a corner that is not on its boundary
+            let parent = (0..i).rev().find(|&j| {
+                ring.points.iter()
+                    .find(|pt| Distance::point_polygon_boundary(pt, &rings[j]).distance >= tol)
+                    .is_some_and(|pt| rings[j].contains_point(pt, tol))
+            });
+
+            match parent.and_then(|p| owners[p]) {
+                Some(owner) => {
+                    result[owner].holes.push(ring.clone());
+                    owners.push(None);
+                },
+                None => {
+                    result.push(PolygonWithHoles::from_outer(ring.clone()));
+                    owners.push(Some(result.len() - 1));
+                }
+            }
+        }
+
+        for poly in result.iter_mut() {
+            poly.normalize_orientation();
+        }
+        result
+    }
+}
@@ -0,0 +1,333 @@
+use super::{Point, Polygon, Vector, LineSegment};
+
+// available simplification algorithms
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SimplifyMode {
+    DouglasPeucker, // recursive split at the farthest corner, fast for long near-straight runs
+    Visvalingam, // iterative removal of the corner spanning the smallest triangle, smoother results
+    Outer // only ever grows the polygon, so the simplified ring contains the original one
+}
+
+impl Polygon {
+    /// Simplify the polygon ring, keeping every removed corner within `tolerance` of the simplified boundary
+    /// `SimplifyMode::Outer` additionally guarantees that the simplified polygon contains the original one,
+    /// which keeps no-fit polygons computed from it conservative
+    /// Polygons with 3 corners or less are returned unchanged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point, SimplifyMode};
+    ///
+    /// let tolerance = 0.1;
+    ///
+    /// // square with a lot of noisy corners on its bottom edge
+    /// let mut pts: Vec<Point> = (0..20).map(|i| Point::new().set_values(i as f64 * 0.1, 0.01 * (i % 2) as f64)).collect();
+    /// pts.push(Point::new().set_values(2.0, 0.0));
+    /// pts.push(Point::new().set_values(2.0, 2.0));
+    /// pts.push(Point::new().set_values(0.0, 2.0));
+    /// let noisy = Polygon::from_points(&pts);
+    ///
+    /// let simplified = noisy.simplify(tolerance, SimplifyMode::DouglasPeucker);
+    /// assert_eq!(simplified.points.len(), 4);
+    ///
+    /// let outer = noisy.simplify(tolerance, SimplifyMode::Outer);
+    /// assert!(outer.calculate_area() >= noisy.calculate_area());
+    /// ```
+    pub fn simplify(&self, tolerance: f64, mode: SimplifyMode) -> Polygon {
+        if self.points.len() <= 3 {
+            return self.clone();
+        }
+
+        match mode {
+            SimplifyMode::DouglasPeucker => self.simplify_douglas_peucker(tolerance),
+            SimplifyMode::Visvalingam => self.simplify_visvalingam(tolerance),
+            SimplifyMode::Outer => self.simplify_outer(tolerance)
+        }
+    }
+
+    // private douglas-peucker on a closed ring, split at the corner farthest away from the first one
+    fn simplify_douglas_peucker(&self, tolerance: f64) -> Polygon {
+        let pts = &self.points;
+        let count = pts.len();
+
+        let far = (1..count)
+            .max_by(|&a, &b| pts[0].distance_to_squared(&pts[a]).partial_cmp(&pts[0].distance_to_squared(&pts[b])).unwrap())
+            .unwrap();
+
+        let mut keep = vec![false; count];
+        keep[0] = true;
+        keep[far] = true;
+
+        // index ranges to split, the end index may be count to close the ring
+        let mut stack: Vec<(usize, usize)> = vec![(0, far), (far, count)];
+        while let Some((first, last)) = stack.pop() {
+            let segment = LineSegment::new_from_points(&pts[first], &pts[last % count]);
+            let mut max_distance = 0.0;
+            let mut max_index = first;
+            for (i, pt) in pts.iter().enumerate().take(last).skip(first + 1) {
+                let distance = segment.distance_to_point(pt);
+                if distance > max_distance {
+                    max_distance = distance;
+                    max_index = i;
+                }
+            }
+
+            if max_distance > tolerance {
+                keep[max_index] = true;
+                stack.push((first, max_index));
+                stack.push((max_index, last));
+            }
+        }
+
+        // never collapse into a line, keep the corner farthest from the split line instead
+        if keep.iter().filter(|&&k| k).count() < 3 {
+            let segment = LineSegment::new_from_points(&pts[0], &pts[far]);
+            let third = (1..count)
+                .filter(|&i| i != far)
+                .max_by(|&a, &b| segment.distance_to_point(&pts[a]).partial_cmp(&segment.distance_to_point(&pts[b])).unwrap())
+                .unwrap();
+            keep[third] = true;
+        }
+
+        let kept = pts.iter().zip(keep.iter()).filter(|(_, &k)| k).map(|(pt, _)| *pt).collect::<Vec<_>>();
+        Polygon::from_points(&kept)
+    }
+
+    // private visvalingam-whyatt, only removing corners while all original corners stay within tolerance
+    fn simplify_visvalingam(&self, tolerance: f64) -> Polygon {
+        let mut ring = Ring::new(&self.points);
+
+        while ring.len() > 3 {
+            let mut best: Option<(usize, f64)> = None;
+
+            for node in ring.alive() {
+                let prev = ring.prev[node];
+                let next = ring.next[node];
+                let area = triangle_area(&ring.pts[prev], &ring.pts[node], &ring.pts[next]).abs();
+                if best.is_some_and(|(_, a)| a <= area) {
+                    continue;
+                }
+                if ring.deviation(prev, next, &[]) > tolerance {
+                    continue;
+                }
+                best = Some((node, area));
+            }
+
+            match best {
+                None => break,
+                Some((node, _)) => ring.remove(node)
+            }
+        }
+
+        ring.as_polygon()
+    }
+
+    // private outer simplification, removes reflex corners and collapses convex edges outward
+    fn simplify_outer(&self, tolerance: f64) -> Polygon {
+        let orientation = self.calculate_signed_area().signum();
+        let mut ring = Ring::new(&self.points);
+
+        // possible ring modifications, reflex corners go first, then ordered by the area they add
+        enum Step {
+            Remove(usize), // remove a reflex corner
+            Collapse(usize, Point) // replace an edge by the intersection of its neighbouring edges
+        }
+
+        while ring.len() > 3 {
+            let mut candidates: Vec<(f64, Step)> = Vec::new();
+
+            for node in ring.alive() {
+                let prev = ring.prev[node];
+                let next = ring.next[node];
+                let turn = orientation * triangle_area(&ring.pts[prev], &ring.pts[node], &ring.pts[next]);
+
+                if turn <= 0.0 {
+                    // reflex or colinear corner, cutting it off grows the polygon
+                    if ring.deviation(prev, next, &[]) <= tolerance {
+                        candidates.push((-turn, Step::Remove(node)));
+                    }
+                    continue;
+                }
+
+                // convex edge from node to next, extend both neighbouring edges until they meet
+                let after = ring.next[next];
+                let next_turn = orientation * triangle_area(&ring.pts[node], &ring.pts[next], &ring.pts[after]);
+                if next_turn <= 0.0 || after == prev {
+                    continue;
+                }
+                if let Some(pt) = extended_intersection(&ring.pts[prev], &ring.pts[node], &ring.pts[after], &ring.pts[next]) {
+                    let added = (orientation * triangle_area(&ring.pts[node], &pt, &ring.pts[next])).abs();
+                    if ring.deviation(prev, after, &[pt]) <= tolerance {
+                        candidates.push((added, Step::Collapse(node, pt)));
+                    }
+                }
+            }
+
+            candidates.sort_by(|a, b| {
+                let a_collapse = matches!(a.1, Step::Collapse(_, _));
+                let b_collapse = matches!(b.1, Step::Collapse(_, _));
+                a_collapse.cmp(&b_collapse).then(a.0.partial_cmp(&b.0).unwrap())
+            });
+
+            let mut applied = false;
+            for (_, step) in candidates {
+                match step {
+                    Step::Remove(node) => {
+                        let (prev, next) = (ring.prev[node], ring.next[node]);
+                        if ring.crosses(&ring.pts[prev], &ring.pts[next], &[prev, node, next]) {
+                            continue;
+                        }
+                        ring.remove(node);
+                    },
+                    Step::Collapse(node, pt) => {
+                        let (prev, next) = (ring.prev[node], ring.next[node]);
+                        let after = ring.next[next];
+                        let ignored = [prev, node, next, after];
+                        if ring.crosses(&ring.pts[prev], &pt, &ignored) || ring.crosses(&pt, &ring.pts[after], &ignored) {
+                            continue;
+                        }
+                        ring.pts[node] = pt;
+                        ring.remove(next);
+                    }
+                }
+                applied = true;
+                break;
+            }
+
+            if !applied {
+                break;
+            }
+        }
+
+        ring.as_polygon()
+    }
+}
+
+// private doubly linked ring of corners, remembering where each corner came from in the original polygon
+struct Ring<'a> {
+    original: &'a [Point],
+    pts: Vec<Point>,
+    prev: Vec<usize>,
+    next: Vec<usize>,
+    removed: Vec<bool>,
+    count: usize
+}
+
+impl<'a> Ring<'a> {
+    fn new(original: &'a [Point]) -> Ring<'a> {
+        let count = original.len();
+        Ring {
+            original,
+            pts: original.to_vec(),
+            prev: (0..count).map(|i| (i + count - 1) % count).collect(),
+            next: (0..count).map(|i| (i + 1) % count).collect(),
+            removed: vec![false; count],
+            count
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    fn alive(&self) -> Vec<usize> {
+        (0..self.pts.len()).filter(|&i| !self.removed[i]).collect()
+    }
+
+    fn remove(&mut self, node: usize) {
+        let prev = self.prev[node];
+        let next = self.next[node];
+        self.next[prev] = next;
+        self.prev[next] = prev;
+        self.removed[node] = true;
+        self.count -= 1;
+    }
+
+    // maximum distance between the original corners from `from` to `to` and the polyline over `via` replacing them
+    fn deviation(&self, from: usize, to: usize, via: &[Point]) -> f64 {
+        let count = self.original.len();
+        let mut corners = vec![self.pts[from]];
+        corners.extend_from_slice(via);
+        corners.push(self.pts[to]);
+        let polyline = corners.windows(2).map(|w| LineSegment::new_from_points(&w[0], &w[1])).collect::<Vec<_>>();
+
+        let mut chain_corners: Vec<Point> = Vec::new();
+        let mut i = from;
+        loop {
+            chain_corners.push(self.original[i]);
+            if i == to {
+                break;
+            }
+            i = (i + 1) % count;
+        }
+        let chain = chain_corners.windows(2).map(|w| LineSegment::new_from_points(&w[0], &w[1])).collect::<Vec<_>>();
+
+        // original corners to new boundary
+        let to_polyline = chain_corners.iter()
+            .map(|pt| polyline.iter().map(|segment| segment.distance_to_point(pt)).fold(f64::MAX, f64::min))
+            .fold(0.0, f64::max);
+
+        // new corners to original boundary
+        let to_chain = via.iter()
+            .map(|pt| chain.iter().map(|segment| segment.distance_to_point(pt)).fold(f64::MAX, f64::min))
+            .fold(0.0, f64::max);
+
+        to_polyline.max(to_chain)
+    }
+
+    // test if a new edge would cross any current edge not touching the ignored corners
+    fn crosses(&self, from: &Point, to: &Point, ignored: &[usize]) -> bool {
+        self.alive().into_iter()
+            .filter(|i| !ignored.contains(i) && !ignored.contains(&self.next[*i]))
+            .any(|i| segments_cross(from, to, &self.pts[i], &self.pts[self.next[i]]))
+    }
+
+    fn as_polygon(&self) -> Polygon {
+        let start = self.alive()[0];
+        let mut pts: Vec<Point> = Vec::with_capacity(self.count);
+        let mut i = start;
+        loop {
+            pts.push(self.pts[i]);
+            i = self.next[i];
+            if i == start {
+                break;
+            }
+        }
+        Polygon::from_points(&pts)
+    }
+}
+
+// private signed double area of a triangle, positive for a left turn
+fn triangle_area(pt0: &Point, pt1: &Point, pt2: &Point) -> f64 {
+    Vector::cross_product(&Vector::new_from_points(pt0, pt1), &Vector::new_from_points(pt0, pt2))
+}
+
+// private intersection of the ray a0 -> a1 beyond a1 with the ray b0 -> b1 beyond b1
+fn extended_intersection(a0: &Point, a1: &Point, b0: &Point, b1: &Point) -> Option<Point> {
+    let da = Vector::new_from_points(a0, a1);
+    let db = Vector::new_from_points(b0, b1);
+    let denominator = Vector::cross_product(&da, &db);
+    if denominator.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let between = Vector::new_from_points(a1, b1);
+    let t = Vector::cross_product(&between, &db) / denominator;
+    let s = Vector::cross_product(&between, &da) / denominator;
+    if t < 0.0 || s < 0.0 {
+        return None;
+    }
+
+    Some(Point::new().set_values(a1.x + t * da.x, a1.y + t * da.y))
+}
+
+// private strict crossing test of two line segments, touching end points do not count
+fn segments_cross(a0: &Point, a1: &Point, b0: &Point, b1: &Point) -> bool {
+    let d0 = triangle_area(a0, a1, b0);
+    let d1 = triangle_area(a0, a1, b1);
+    let d2 = triangle_area(b0, b1, a0);
+    let d3 = triangle_area(b0, b1, a1);
+    (d0 * d1 < 0.0) && (d2 * d3 < 0.0)
+}
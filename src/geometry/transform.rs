@@ -0,0 +1,105 @@
+use super::{Point, Polygon, PolygonWithHoles};
+
+/// Affine transformation of the plane, in the column order used by SVG `matrix(a b c d e f)`
+/// A point (x, y) is mapped to (a * x + c * y + e, b * x + d * y + f)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64
+}
+
+impl Transform {
+    // public identity constructor
+    pub fn identity() -> Transform {
+        Transform{a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0}
+    }
+
+    pub fn translation(x: f64, y: f64) -> Transform {
+        Transform{e: x, f: y, ..Transform::identity()}
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Transform {
+        Transform{a: sx, d: sy, ..Transform::identity()}
+    }
+
+    // public counter-clockwise rotation around the origin, angle in radians
+    pub fn rotation(angle: f64) -> Transform {
+        let (sin, cos) = angle.sin_cos();
+        Transform{a: cos, b: sin, c: -sin, d: cos, e: 0.0, f: 0.0}
+    }
+
+    // public counter-clockwise rotation around a center point, angle in radians
+    pub fn rotation_around(angle: f64, center: &Point) -> Transform {
+        Transform::translation(-center.x, -center.y)
+            .then(&Transform::rotation(angle))
+            .then(&Transform::translation(center.x, center.y))
+    }
+
+    // public skew along the x-axis, angle in radians
+    pub fn skew_x(angle: f64) -> Transform {
+        Transform{c: angle.tan(), ..Transform::identity()}
+    }
+
+    // public skew along the y-axis, angle in radians
+    pub fn skew_y(angle: f64) -> Transform {
+        Transform{b: angle.tan(), ..Transform::identity()}
+    }
+
+    /// Combined transformation, applying self first and other second
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Transform, Point};
+    /// use std::f64::consts::PI;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let t = Transform::rotation(PI / 2.0).then(&Transform::translation(1.0, 0.0));
+    /// assert!(t.apply(&Point::new().set_values(1.0, 0.0)).epsilon_equals(&Point::new().set_values(1.0, 1.0), tol));
+    /// ```
+    pub fn then(&self, other: &Transform) -> Transform {
+        Transform{
+            a: other.a * self.a + other.c * self.b,
+            b: other.b * self.a + other.d * self.b,
+            c: other.a * self.c + other.c * self.d,
+            d: other.b * self.c + other.d * self.d,
+            e: other.a * self.e + other.c * self.f + other.e,
+            f: other.b * self.e + other.d * self.f + other.f
+        }
+    }
+
+    pub fn apply(&self, pt: &Point) -> Point {
+        Point::new().set_values(
+            self.a * pt.x + self.c * pt.y + self.e,
+            self.b * pt.x + self.d * pt.y + self.f)
+    }
+
+    pub fn determinant(&self) -> f64 {
+        self.a * self.d - self.b * self.c
+    }
+
+    // public upper bound of the length change of any vector under the transformation, the frobenius norm
+    pub fn max_scale(&self) -> f64 {
+        (self.a * self.a + self.b * self.b + self.c * self.c + self.d * self.d).sqrt()
+    }
+}
+
+impl Polygon {
+    // public transformed copy, mirroring transformations flip the orientation
+    pub fn transform(&self, t: &Transform) -> Polygon {
+        Polygon::from_points(&self.points.iter().map(|pt| t.apply(pt)).collect())
+    }
+}
+
+impl PolygonWithHoles {
+    // public transformed copy of the outer boundary and all holes
+    pub fn transform(&self, t: &Transform) -> PolygonWithHoles {
+        PolygonWithHoles::new(self.outer.transform(t), self.holes.iter().map(|h| h.transform(t)).collect())
+    }
+}
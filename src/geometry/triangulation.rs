@@ -0,0 +1,308 @@
+use super::{Point, Polygon, PolygonWithHoles, Vector};
+
+// possible reasons for a triangulation to fail
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TriangulationError {
+    TooFewPoints, // less than 3 distinct, non colinear corners on the outer boundary
+    HoleOutside(usize), // the hole at the given index does not lie inside the outer boundary
+    NoEarFound(usize), // no ear left with the given number of corners remaining, the input is most likely self-intersecting
+    NotSimple // no decomposition into valid diagonals exists, the outer boundary intersects itself
+}
+
+impl Polygon {
+    /// Triangulate the polygon by ear-clipping in O(n^2)
+    /// Works for both orientations, duplicate and colinear corners are skipped
+    /// Returns index triples into `points`, every triangle is oriented counter-clockwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, Point};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let mut poly = Polygon::from_points(&vec![
+    ///     Point::new(),
+    ///     Point::new().set_values(2.0, 0.0),
+    ///     Point::new().set_values(1.0, 1.0), // reflex corner
+    ///     Point::new().set_values(2.0, 2.0),
+    ///     Point::new().set_values(0.0, 2.0)]);
+    /// poly.reverse_orientation(); // clockwise input is fine as well
+    ///
+    /// let tris = poly.triangulate_indices(tol).unwrap();
+    /// assert_eq!(tris.len(), 3);
+    /// let area: f64 = tris.iter()
+    ///     .map(|t| Polygon::from_points(&vec![poly.points[t[0]], poly.points[t[1]], poly.points[t[2]]]).calculate_signed_area())
+    ///     .sum();
+    /// assert!((area - poly.calculate_area()).abs() < tol);
+    /// ```
+    pub fn triangulate_indices(&self, tol: f64) -> Result<Vec<[usize; 3]>, TriangulationError> {
+        let outer = ring_indices(&self.points, 0, true, tol);
+        if outer.len() < 3 {
+            return Err(TriangulationError::TooFewPoints);
+        }
+
+        ear_clip(&self.points, outer, tol)
+    }
+
+    // public triangulation into counter-clockwise triangles, see `triangulate_indices`
+    pub fn triangulate(&self, tol: f64) -> Result<Vec<Polygon>, TriangulationError> {
+        let tris = self.triangulate_indices(tol)?;
+        Ok(tris_to_polygons(&self.points, &tris))
+    }
+}
+
+impl PolygonWithHoles {
+    /// Triangulate the polygon with holes by ear-clipping, holes are joined to the outer boundary by bridge edges first
+    /// Returns index triples into the corners of the outer boundary followed by the corners of all holes in order,
+    /// as returned by `all_points`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+    ///
+    /// let tris = frame.triangulate_indices(tol).unwrap();
+    /// assert_eq!(tris.len(), 8);
+    /// ```
+    pub fn triangulate_indices(&self, tol: f64) -> Result<Vec<[usize; 3]>, TriangulationError> {
+        let all_points = self.all_points();
+
+        let mut ring = ring_indices(&self.outer.points, 0, true, tol);
+        if ring.len() < 3 {
+            return Err(TriangulationError::TooFewPoints);
+        }
+
+        // holes run clockwise, degenerate ones are dropped
+        let mut offset = self.outer.points.len();
+        let mut holes: Vec<(usize, Vec<usize>)> = Vec::with_capacity(self.holes.len());
+        for (n, hole) in self.holes.iter().enumerate() {
+            if !hole.points.iter().all(|pt| self.outer.contains_point(pt, tol)) {
+                return Err(TriangulationError::HoleOutside(n));
+            }
+
+            let hole_ring = ring_indices(&hole.points, offset, false, tol);
+            offset += hole.points.len();
+            if hole_ring.len() >= 3 {
+                holes.push((n, hole_ring));
+            }
+        }
+
+        // bridge holes from right to left, so every bridge only sees holes already merged
+        let max_x = |hole: &Vec<usize>| hole.iter().map(|&i| all_points[i].x).fold(f64::MIN, f64::max);
+        holes.sort_by(|a, b| max_x(&b.1).partial_cmp(&max_x(&a.1)).unwrap());
+        for (n, hole) in holes.iter() {
+            ring = bridge_hole(&all_points, ring, hole).ok_or(TriangulationError::HoleOutside(*n))?;
+        }
+
+        ear_clip(&all_points, ring, tol)
+    }
+
+    // public triangulation into counter-clockwise triangles, see `triangulate_indices`
+    pub fn triangulate(&self, tol: f64) -> Result<Vec<Polygon>, TriangulationError> {
+        let tris = self.triangulate_indices(tol)?;
+        Ok(tris_to_polygons(&self.all_points(), &tris))
+    }
+
+    // public list of the corners of all rings, outer boundary first
+    pub fn all_points(&self) -> Vec<Point> {
+        self.rings().flat_map(|ring| ring.points.iter().cloned()).collect()
+    }
+}
+
+// private helper turning index triples into triangles
+fn tris_to_polygons(pts: &[Point], tris: &[[usize; 3]]) -> Vec<Polygon> {
+    tris.iter()
+        .map(|t| Polygon::from_points(&vec![pts[t[0]], pts[t[1]], pts[t[2]]]))
+        .collect()
+}
+
+// signed double area of a triangle, positive for a left turn
+pub(super) fn turn(pt0: &Point, pt1: &Point, pt2: &Point) -> f64 {
+    Vector::cross_product(&Vector::new_from_points(pt0, pt1), &Vector::new_from_points(pt1, pt2))
+}
+
+// helper collecting the indices of a ring in the wanted orientation, without duplicate or colinear corners
+pub(super) fn ring_indices(pts: &[Point], offset: usize, counter_clockwise: bool, tol: f64) -> Vec<usize> {
+    let mut ring: Vec<usize> = (0..pts.len()).collect();
+    if (Polygon::from_points(&pts.to_vec()).calculate_signed_area() > 0.0) != counter_clockwise {
+        ring.reverse();
+    }
+
+    // remove degenerate corners until none are left
+    let mut removed = true;
+    while removed && ring.len() >= 3 {
+        removed = false;
+        let mut i = 0;
+        while i < ring.len() && ring.len() >= 3 {
+            let count = ring.len();
+            let prev = &pts[ring[(i + count - 1) % count]];
+            let current = &pts[ring[i]];
+            let next = &pts[ring[(i + 1) % count]];
+            if current.epsilon_equals(next, tol) || Point::are_colinear(prev, current, next, tol) {
+                ring.remove(i);
+                removed = true;
+            }
+            else {
+                i += 1;
+            }
+        }
+    }
+
+    ring.into_iter().map(|i| i + offset).collect()
+}
+
+// private helper joining a clockwise hole into the counter-clockwise ring with a bridge edge, after David Eberly
+// returns None if no ring edge is found right of the hole
+fn bridge_hole(pts: &[Point], ring: Vec<usize>, hole: &[usize]) -> Option<Vec<usize>> {
+    let count = ring.len();
+    let (hole_start, &hole_corner) = hole.iter().enumerate()
+        .max_by(|a, b| pts[*a.1].x.partial_cmp(&pts[*b.1].x).unwrap())?;
+    let m = pts[hole_corner];
+
+    // closest ring edge hit by a ray from the hole corner towards +x
+    let mut hit: Option<(f64, usize)> = None;
+    for i in 0..count {
+        let a = &pts[ring[i]];
+        let b = &pts[ring[(i + 1) % count]];
+        // only upward edges face the hole from the inside
+        if !(a.y <= m.y && m.y <= b.y && a.y < b.y) {
+            continue;
+        }
+
+        let x = a.x + (m.y - a.y) / (b.y - a.y) * (b.x - a.x);
+        let closer = match hit {
+            Some((best, _)) => x < best,
+            None => true
+        };
+        if x >= m.x && closer {
+            hit = Some((x, i));
+        }
+    }
+    let (hit_x, edge) = hit?;
+    let hit_point = Point::new().set_values(hit_x, m.y);
+
+    // edge end point farther right is visible, unless a reflex corner is hiding it
+    let mut visible = if pts[ring[edge]].x > pts[ring[(edge + 1) % count]].x {edge} else {(edge + 1) % count};
+    let p = pts[ring[visible]];
+
+    let mut best_tan = f64::MAX;
+    let mut best_distance = f64::MAX;
+    for i in 0..count {
+        let v = &pts[ring[i]];
+        if i == visible || v.x < m.x {
+            continue;
+        }
+        let prev = &pts[ring[(i + count - 1) % count]];
+        let next = &pts[ring[(i + 1) % count]];
+        if turn(prev, v, next) >= 0.0 {
+            continue;
+        }
+        if !point_in_triangle(v, &m, &hit_point, &p) {
+            continue;
+        }
+
+        let tan = (v.y - m.y).abs() / (v.x - m.x).max(f64::MIN_POSITIVE);
+        let distance = m.distance_to_squared(v);
+        if tan < best_tan || (tan == best_tan && distance < best_distance) {
+            best_tan = tan;
+            best_distance = distance;
+            visible = i;
+        }
+    }
+
+    // ring up to the visible corner, around the hole back to its start, then back to the visible corner
+    let mut bridged: Vec<usize> = Vec::with_capacity(count + hole.len() + 2);
+    bridged.extend_from_slice(&ring[..=visible]);
+    bridged.extend_from_slice(&hole[hole_start..]);
+    bridged.extend_from_slice(&hole[..=hole_start]);
+    bridged.push(ring[visible]);
+    bridged.extend_from_slice(&ring[(visible + 1)..]);
+
+    Some(bridged)
+}
+
+// private inclusive point in triangle test, works for both triangle orientations
+fn point_in_triangle(pt: &Point, a: &Point, b: &Point, c: &Point) -> bool {
+    let d0 = turn(a, b, pt);
+    let d1 = turn(b, c, pt);
+    let d2 = turn(c, a, pt);
+    let has_negative = d0 < 0.0 || d1 < 0.0 || d2 < 0.0;
+    let has_positive = d0 > 0.0 || d1 > 0.0 || d2 > 0.0;
+    !(has_negative && has_positive)
+}
+
+// ear test strictness, relaxed only when no strict ear is left
+#[derive(Clone, Copy, PartialEq)]
+enum EarRule {
+    Strict, // convex corner and no other corner inside the ear
+    Degenerate // zero area corner, clipped without emitting a triangle
+}
+
+// private ear test for the corner at ring position i
+fn is_ear(pts: &[Point], ring: &[usize], i: usize, rule: EarRule, tol: f64) -> bool {
+    let count = ring.len();
+    let a = &pts[ring[(i + count - 1) % count]];
+    let b = &pts[ring[i]];
+    let c = &pts[ring[(i + 1) % count]];
+    let area = turn(a, b, c);
+
+    match rule {
+        EarRule::Degenerate => area.abs() <= tol,
+        EarRule::Strict => {
+            if area <= tol {
+                return false;
+            }
+            // corners at the same position as the ear corners are bridge duplicates and never block
+            !ring.iter()
+                .map(|&j| &pts[j])
+                .filter(|v| !v.epsilon_equals(a, tol) && !v.epsilon_equals(b, tol) && !v.epsilon_equals(c, tol))
+                .any(|v| point_in_triangle(v, a, b, c))
+        }
+    }
+}
+
+// private ear clipping, ear flags are only updated for the neighbours of a clipped ear, giving O(n^2)
+fn ear_clip(pts: &[Point], mut ring: Vec<usize>, tol: f64) -> Result<Vec<[usize; 3]>, TriangulationError> {
+    let mut tris: Vec<[usize; 3]> = Vec::with_capacity(ring.len());
+    let mut ears: Vec<bool> = (0..ring.len()).map(|i| is_ear(pts, &ring, i, EarRule::Strict, tol)).collect();
+    log_debug!(corners = ring.len(), ears = ears.iter().filter(|&&e| e).count(); "ear clipping");
+
+    while ring.len() > 3 {
+        let count = ring.len();
+
+        // without a strict or degenerate ear the ring overlaps itself, clipping any other corner would emit overlapping triangles
+        let found = ears.iter().position(|&e| e).map(|i| (i, EarRule::Strict))
+            .or_else(|| (0..count).find(|&i| is_ear(pts, &ring, i, EarRule::Degenerate, tol)).map(|i| (i, EarRule::Degenerate)));
+
+        let (i, rule) = found.ok_or(TriangulationError::NoEarFound(count))?;
+        if rule != EarRule::Degenerate {
+            tris.push([ring[(i + count - 1) % count], ring[i], ring[(i + 1) % count]]);
+        }
+        ring.remove(i);
+        ears.remove(i);
+
+        // only the neighbours of the clipped ear can change
+        let count = ring.len();
+        let prev = (i + count - 1) % count;
+        let next = i % count;
+        ears[prev] = is_ear(pts, &ring, prev, EarRule::Strict, tol);
+        ears[next] = is_ear(pts, &ring, next, EarRule::Strict, tol);
+    }
+
+    // a clockwise last triangle is left over from a ring that overlaps itself
+    let area = turn(&pts[ring[0]], &pts[ring[1]], &pts[ring[2]]);
+    if area < -tol {
+        return Err(TriangulationError::NoEarFound(3));
+    }
+    if area > tol {
+        tris.push([ring[0], ring[1], ring[2]]);
+    }
+
+    Ok(tris)
+}
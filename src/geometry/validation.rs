@@ -0,0 +1,109 @@
+use super::{Polygon, PolygonWithHoles, Distance};
+
+// problems found by polygon validation, rings are numbered with 0 for the outer boundary and 1.. for the holes
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolygonIssue {
+    TooFewPoints(usize), // ring with less than 3 corners
+    ZeroArea(usize), // ring with an area below the tolerance
+    DuplicatePoint(usize, usize), // ring and index of a corner equal to the corner before it
+    SelfIntersection(usize, usize, usize), // ring and indices of two non-adjacent edges that touch
+    HoleOutside(usize), // hole ring not inside the outer boundary
+    RingsIntersect(usize, usize) // boundaries of two different rings touch
+}
+
+impl PolygonWithHoles {
+    /// Checks the polygon for problems that break nfp and nesting calculations
+    /// An empty result means every ring is simple, has an area and all holes lie strictly inside the outer boundary
+    /// without touching it or each other
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Point, Polygon, PolygonWithHoles, PolygonIssue};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+    /// assert!(frame.validate(tol).is_empty());
+    ///
+    /// // bow tie, the first and third edge cross
+    /// let bow_tie = PolygonWithHoles::from_outer(Polygon::from_points(&vec![
+    ///     Point::new(), Point::new().set_values(3.0, 2.0), Point::new().set_values(3.0, 0.0), Point::new().set_values(0.0, 3.0)]));
+    /// assert_eq!(bow_tie.validate(tol), vec![PolygonIssue::SelfIntersection(0, 0, 2)]);
+    /// ```
+    pub fn validate(&self, tol: f64) -> Vec<PolygonIssue> {
+        let mut issues = Vec::new();
+        let rings = self.rings().collect::<Vec<_>>();
+
+        for (index, ring) in rings.iter().enumerate() {
+            validate_ring(ring, index, tol, &mut issues);
+        }
+
+        // relations between rings are only meaningful for valid rings
+        if !issues.is_empty() {
+            return issues;
+        }
+
+        for (index, hole) in self.holes.iter().enumerate() {
+            let probe = hole.points.iter()
+                .find(|pt| Distance::point_polygon_boundary(pt, &self.outer).distance > tol);
+            if !probe.is_some_and(|pt| self.outer.contains_point(pt, tol)) {
+                issues.push(PolygonIssue::HoleOutside(index + 1));
+            }
+        }
+
+        for i in 0..rings.len() {
+            for j in (i + 1)..rings.len() {
+                if boundaries_touch(rings[i], rings[j], tol) {
+                    issues.push(PolygonIssue::RingsIntersect(i, j));
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+// private checks of a single ring
+fn validate_ring(ring: &Polygon, index: usize, tol: f64, issues: &mut Vec<PolygonIssue>) {
+    let count = ring.points.len();
+    if count < 3 {
+        issues.push(PolygonIssue::TooFewPoints(index));
+        return;
+    }
+
+    let mut has_duplicates = false;
+    for i in 0..count {
+        if ring.points[i].distance_to(&ring.points[(i + count - 1) % count]) <= tol {
+            issues.push(PolygonIssue::DuplicatePoint(index, i));
+            has_duplicates = true;
+        }
+    }
+
+    // the zero length edge of a duplicate point would touch both of its neighbours
+    let edges = if has_duplicates {Vec::new()} else {ring.calculate_edges()};
+    for i in 0..edges.len() {
+        // skip the neighbours of edge i, the last edge is adjacent to the first one
+        for j in (i + 2)..edges.len() {
+            if i == 0 && j == edges.len() - 1 {
+                continue;
+            }
+            if Distance::line_segment_line_segment(&edges[i], &edges[j], tol).distance <= tol {
+                issues.push(PolygonIssue::SelfIntersection(index, i, j));
+            }
+        }
+    }
+
+    // the signed area of self-intersecting rings cancels out, so they may be reported here as well
+    if ring.calculate_area() <= tol {
+        issues.push(PolygonIssue::ZeroArea(index));
+    }
+}
+
+// private boundary test, unlike the polygon distance a ring inside another one does not touch it
+fn boundaries_touch(first: &Polygon, other: &Polygon, tol: f64) -> bool {
+    let other_edges = other.calculate_edges();
+    first.calculate_edges().iter()
+        .any(|e| other_edges.iter().any(|o| Distance::line_segment_line_segment(e, o, tol).distance <= tol))
+}
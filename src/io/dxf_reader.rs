@@ -0,0 +1,301 @@
+use super::super::geometry::{Point, Polygon, PolygonWithHoles, Arc, BSpline};
+use std::f64::consts::PI;
+
+#[derive(Debug, PartialEq)]
+pub enum DxfError {
+    Io(String), // reading the file failed, holds the io error message
+    GroupCode(usize), // line number of a group code that is not an integer
+    Number(usize), // line number of a value that should be a number
+    Radius(usize), // line number of an arc or circle radius that is not positive
+    Entity(String), // entity type with an incomplete or unsupported definition
+    Tolerance(f64) // the chaining and flattening tolerance is not a positive number
+}
+
+// single group code / value pair with the line number of its value
+struct Group {
+    code: i32,
+    value: String,
+    line: usize
+}
+
+// entity type with all its groups, in file order
+struct Entity {
+    kind: String,
+    groups: Vec<Group>
+}
+
+impl Entity {
+    fn number(&self, code: i32) -> Result<Option<f64>, DxfError> {
+        match self.groups.iter().find(|g| g.code == code) {
+            Some(group) => parse_number(group).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    fn required(&self, code: i32) -> Result<f64, DxfError> {
+        self.number(code)?.ok_or_else(|| DxfError::Entity(self.kind.clone()))
+    }
+
+    fn point(&self, x_code: i32) -> Result<Point, DxfError> {
+        Ok(Point::new().set_values(self.required(x_code)?, self.required(x_code + 10)?))
+    }
+
+    fn radius(&self) -> Result<f64, DxfError> {
+        let radius = self.required(40)?;
+        match self.groups.iter().find(|g| g.code == 40) {
+            Some(group) if radius.is_nan() || radius <= 0.0 => Err(DxfError::Radius(group.line)),
+            _ => Ok(radius)
+        }
+    }
+
+    fn flags(&self) -> Result<i64, DxfError> {
+        Ok(self.number(70)?.unwrap_or(0.0) as i64)
+    }
+}
+
+pub struct DxfReader {
+
+}
+
+impl DxfReader {
+    // public file import, see `read_str`
+    pub fn read_file<P: AsRef<std::path::Path>>(path: P, tol: f64) -> Result<Vec<PolygonWithHoles>, DxfError> {
+        let text = std::fs::read_to_string(path).map_err(|e| DxfError::Io(e.to_string()))?;
+        DxfReader::read_str(&text, tol)
+    }
+
+    /// Imports all closed loops of an ascii dxf file as polygons with holes
+    /// Reads LINE, LWPOLYLINE, POLYLINE, ARC, CIRCLE and SPLINE entities of the ENTITIES section, ignoring layers and blocks.
+    /// Open entities are chained into loops where their end points meet within tol, chains that never close are dropped.
+    /// Arcs, bulges and splines are flattened so they deviate at most tol from the result, splines need control points.
+    /// A tolerance that is not positive is rejected with `DxfError::Tolerance`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::io::DxfReader;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// // triangle made of three lines, the last one drawn backwards
+    /// let dxf = "0\nSECTION\n2\nENTITIES\n\
+    ///     0\nLINE\n8\n0\n10\n0.0\n20\n0.0\n11\n4.0\n21\n0.0\n\
+    ///     0\nLINE\n8\n0\n10\n4.0\n20\n0.0\n11\n0.0\n21\n3.0\n\
+    ///     0\nLINE\n8\n0\n10\n0.0\n20\n0.0\n11\n0.0\n21\n3.0\n\
+    ///     0\nENDSEC\n0\nEOF\n";
+    ///
+    /// let parts = DxfReader::read_str(dxf, tol).unwrap();
+    /// assert_eq!(parts.len(), 1);
+    /// assert_eq!(parts[0].calculate_area(), 6.0);
+    /// ```
+    pub fn read_str(text: &str, tol: f64) -> Result<Vec<PolygonWithHoles>, DxfError> {
+        Ok(PolygonWithHoles::from_rings(DxfReader::read_rings(text, tol)?, tol))
+    }
+
+    // public import of all closed loops, without nesting them, see `read_str`
+    pub fn read_rings(text: &str, tol: f64) -> Result<Vec<Polygon>, DxfError> {
+        if tol.is_nan() || tol <= 0.0 {
+            return Err(DxfError::Tolerance(tol));
+        }
+
+        let entities = parse_entities(text)?;
+
+        let mut rings: Vec<Vec<Point>> = Vec::new();
+        let mut pieces: Vec<Vec<Point>> = Vec::new();
+        let mut index = 0;
+
+        while index < entities.len() {
+            let entity = &entities[index];
+            index += 1;
+
+            let (pts, closed) = match entity.kind.as_str() {
+                "LINE" => (vec![entity.point(10)?, entity.point(11)?], false),
+                "ARC" => {
+                    let start = entity.required(50)?.to_radians();
+                    let end = entity.required(51)?.to_radians();
+                    let mut sweep = (end - start) % (2.0 * PI);
+                    if sweep <= 0.0 {
+                        sweep += 2.0 * PI;
+                    }
+                    (Arc::new(&entity.point(10)?, entity.radius()?, start, sweep).tessellate(tol), false)
+                },
+                "CIRCLE" => {
+                    let mut pts = Arc::circle(&entity.point(10)?, entity.radius()?).tessellate(tol);
+                    pts.pop();
+                    (pts, true)
+                },
+                "LWPOLYLINE" => {
+                    let mut vertices: Vec<(Point, f64)> = Vec::new();
+                    for group in entity.groups.iter() {
+                        match (group.code, vertices.last_mut()) {
+                            (10, _) => vertices.push((Point::new().set_values(parse_number(group)?, 0.0), 0.0)),
+                            (20, Some(vertex)) => vertex.0.y = parse_number(group)?,
+                            (42, Some(vertex)) => vertex.1 = parse_number(group)?,
+                            _ => ()
+                        }
+                    }
+                    let closed = entity.flags()? & 1 == 1;
+                    (bulge_polyline(&vertices, closed, tol), closed)
+                },
+                "POLYLINE" => {
+                    // vertices follow as separate entities up to SEQEND
+                    let mut vertices: Vec<(Point, f64)> = Vec::new();
+                    while index < entities.len() && entities[index].kind == "VERTEX" {
+                        let vertex = &entities[index];
+                        vertices.push((vertex.point(10)?, vertex.number(42)?.unwrap_or(0.0)));
+                        index += 1;
+                    }
+                    if index < entities.len() && entities[index].kind == "SEQEND" {
+                        index += 1;
+                    }
+                    let closed = entity.flags()? & 1 == 1;
+                    (bulge_polyline(&vertices, closed, tol), closed)
+                },
+                "SPLINE" => spline_points(entity, tol)?,
+                _ => continue
+            };
+
+            if closed {
+                rings.push(pts);
+            } else if pts.len() > 1 {
+                pieces.push(pts);
+            }
+        }
+
+        rings.extend(chain_pieces(pieces, tol));
+        Ok(rings.into_iter()
+            .map(|pts| {
+                let mut pts = pts;
+                pts.dedup_by(|a, b| a.epsilon_equals(b, tol));
+                while pts.len() > 1 && pts[0].epsilon_equals(pts.last().unwrap(), tol) {
+                    pts.pop();
+                }
+                Polygon::from_points(&pts)
+            })
+            .filter(|r| r.points.len() >= 3)
+            .collect())
+    }
+}
+
+fn parse_number(group: &Group) -> Result<f64, DxfError> {
+    group.value.parse::<f64>().map_err(|_| DxfError::Number(group.line))
+}
+
+// private split of the ENTITIES section into entities
+fn parse_entities(text: &str) -> Result<Vec<Entity>, DxfError> {
+    let lines = text.lines().map(|l| l.trim()).collect::<Vec<_>>();
+    let mut groups: Vec<Group> = Vec::with_capacity(lines.len() / 2);
+    for (n, pair) in lines.chunks(2).enumerate() {
+        if pair.len() < 2 {
+            // trailing empty line
+            if pair[0].is_empty() {
+                break;
+            }
+            return Err(DxfError::GroupCode(2 * n + 1));
+        }
+        let code = pair[0].parse::<i32>().map_err(|_| DxfError::GroupCode(2 * n + 1))?;
+        groups.push(Group{code, value: pair[1].to_string(), line: 2 * n + 2});
+    }
+
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut in_entities = false;
+    let mut iter = groups.into_iter().peekable();
+    while let Some(group) = iter.next() {
+        if group.code != 0 {
+            if let Some(entity) = entities.last_mut().filter(|_| in_entities) {
+                entity.groups.push(group);
+            }
+            continue;
+        }
+
+        match group.value.as_str() {
+            "SECTION" => {
+                in_entities = iter.peek().is_some_and(|g| g.code == 2 && g.value == "ENTITIES");
+            },
+            "ENDSEC" | "EOF" => in_entities = false,
+            kind if in_entities => entities.push(Entity{kind: kind.to_string(), groups: Vec::new()}),
+            _ => ()
+        }
+    }
+
+    Ok(entities)
+}
+
+// private polyline points with arcs for all non-zero bulges, the bulge of a vertex belongs to the segment after it
+fn bulge_polyline(vertices: &[(Point, f64)], closed: bool, tol: f64) -> Vec<Point> {
+    let count = vertices.len();
+    let mut pts = Vec::with_capacity(count);
+    for (n, (pt, bulge)) in vertices.iter().enumerate() {
+        pts.push(*pt);
+        if n + 1 == count && !closed {
+            break;
+        }
+
+        let next = &vertices[(n + 1) % count].0;
+        if let Some(arc) = Arc::new_from_bulge(pt, next, *bulge) {
+            let arc_pts = arc.tessellate(tol);
+            pts.extend_from_slice(&arc_pts[1..(arc_pts.len() - 1)]);
+        }
+    }
+    pts
+}
+
+// private spline flattening from control points and knots, splines given only by fit points are not supported
+fn spline_points(entity: &Entity, tol: f64) -> Result<(Vec<Point>, bool), DxfError> {
+    let closed = entity.flags()? & 1 == 1;
+    let degree = entity.number(71)?.unwrap_or(3.0) as usize;
+    let error = || DxfError::Entity(entity.kind.clone());
+
+    let mut knots: Vec<f64> = Vec::new();
+    let mut weights: Vec<f64> = Vec::new();
+    let mut controls: Vec<Point> = Vec::new();
+    for group in entity.groups.iter() {
+        match group.code {
+            40 => knots.push(parse_number(group)?),
+            41 => weights.push(parse_number(group)?),
+            10 => controls.push(Point::new().set_values(parse_number(group)?, 0.0)),
+            20 => controls.last_mut().ok_or_else(error)?.y = parse_number(group)?,
+            _ => ()
+        }
+    }
+
+    // rational splines are only supported with uniform weights
+    if weights.windows(2).any(|w| (w[0] - w[1]).abs() > f64::EPSILON) {
+        return Err(error());
+    }
+
+    let spline = BSpline::new(degree, &controls, &knots).ok_or_else(error)?;
+    Ok((spline.flatten(tol).points, closed))
+}
+
+// private greedy chaining of open pieces into closed loops
+fn chain_pieces(mut pieces: Vec<Vec<Point>>, tol: f64) -> Vec<Vec<Point>> {
+    let mut rings = Vec::new();
+
+    while let Some(mut chain) = pieces.pop() {
+        loop {
+            let start = chain[0];
+            let end = *chain.last().unwrap();
+            if chain.len() > 2 && start.epsilon_equals(&end, tol) {
+                chain.pop();
+                rings.push(chain);
+                break;
+            }
+
+            let next = pieces.iter().position(|p| p[0].epsilon_equals(&end, tol) || p.last().unwrap().epsilon_equals(&end, tol));
+            match next {
+                Some(n) => {
+                    let mut piece = pieces.swap_remove(n);
+                    if !piece[0].epsilon_equals(&end, tol) {
+                        piece.reverse();
+                    }
+                    chain.extend_from_slice(&piece[1..]);
+                },
+                // never closes, dropped
+                None => break
+            }
+        }
+    }
+
+    rings
+}
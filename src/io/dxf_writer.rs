@@ -0,0 +1,123 @@
+use super::super::geometry::{Polygon, PolygonWithHoles, Transform};
+use super::super::nesting::Layout;
+use std::fmt::Write;
+
+/// Collects closed outlines on named layers and renders them as an ascii dxf file
+/// Outlines are written as R12 POLYLINE entities, which every cutting machine software can read
+///
+/// # Examples
+///
+/// ```
+/// use rust_nfp::geometry::Polygon;
+/// use rust_nfp::io::{DxfWriter, DxfReader};
+///
+/// let mut writer = DxfWriter::new();
+/// writer.add_polygon(&Polygon::square(2.0), "parts");
+///
+/// let dxf = writer.render();
+/// assert!(dxf.contains("POLYLINE"));
+/// assert_eq!(DxfReader::read_str(&dxf, 0.0001).unwrap()[0].calculate_area(), 4.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DxfWriter {
+    pub sheet_gap: f64, // space between sheets of a written layout
+    layers: Vec<String>,
+    entities: String
+}
+
+impl Default for DxfWriter {
+    fn default() -> DxfWriter {
+        DxfWriter::new()
+    }
+}
+
+impl DxfWriter {
+    // public constructor for an empty file
+    pub fn new() -> DxfWriter {
+        DxfWriter{sheet_gap: 10.0, layers: Vec::new(), entities: String::new()}
+    }
+
+    // public closed polyline on the given layer, characters invalid in layer names are replaced
+    pub fn add_polygon(&mut self, poly: &Polygon, layer: &str) {
+        if poly.points.is_empty() {
+            return;
+        }
+
+        let layer = self.use_layer(layer);
+        let _ = write!(self.entities, "0\nPOLYLINE\n8\n{}\n66\n1\n70\n1\n10\n0.0\n20\n0.0\n30\n0.0\n", layer);
+        for pt in poly.points.iter() {
+            let _ = write!(self.entities, "0\nVERTEX\n8\n{}\n10\n{}\n20\n{}\n30\n0.0\n", layer, pt.x, pt.y);
+        }
+        let _ = write!(self.entities, "0\nSEQEND\n8\n{}\n", layer);
+    }
+
+    // public outer boundary and holes as separate closed polylines on the same layer
+    pub fn add_polygon_with_holes(&mut self, poly: &PolygonWithHoles, layer: &str) {
+        for ring in poly.rings() {
+            self.add_polygon(ring, layer);
+        }
+    }
+
+    /// Writes a nesting layout with one layer per sheet, named `sheet-<index>-<name>` after the sheet index and name
+    /// Sheets are placed side by side in the order of the layout, each with its outline and all placed parts.
+    /// Defect zones of a sheet go on a layer of their own, named like the sheet layer with a `-defects` suffix
+    pub fn add_layout(&mut self, layout: &Layout) {
+        let mut cursor = 0.0;
+
+        for (index, sheet) in layout.sheets.iter().enumerate() {
+            let bbox = sheet.shape.bounding_box();
+            if bbox.is_empty() {
+                continue;
+            }
+            let shift = Transform::translation(cursor - bbox.min.x, -bbox.min.y);
+
+            let layer = format!("sheet-{}-{}", index, sheet.name);
+            self.add_polygon_with_holes(&sheet.shape.transform(&shift), &layer);
+            for defect in sheet.defects.iter() {
+                self.add_polygon(&defect.shape.transform(&shift), &format!("{}-defects", layer));
+            }
+            for placement in layout.placements_on_sheet(index) {
+                self.add_polygon_with_holes(&placement.shape.transform(&shift), &layer);
+            }
+
+            cursor += bbox.width() + self.sheet_gap;
+        }
+    }
+
+    // public complete dxf file with a layer table and all entities added so far
+    pub fn render(&self) -> String {
+        let mut dxf = String::new();
+        dxf.push_str("0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1009\n0\nENDSEC\n");
+
+        let _ = write!(dxf, "0\nSECTION\n2\nTABLES\n0\nTABLE\n2\nLAYER\n70\n{}\n", self.layers.len());
+        for layer in self.layers.iter() {
+            let _ = write!(dxf, "0\nLAYER\n2\n{}\n70\n0\n62\n7\n6\nCONTINUOUS\n", layer);
+        }
+        dxf.push_str("0\nENDTAB\n0\nENDSEC\n");
+
+        dxf.push_str("0\nSECTION\n2\nENTITIES\n");
+        dxf.push_str(&self.entities);
+        dxf.push_str("0\nENDSEC\n0\nEOF\n");
+        dxf
+    }
+
+    // public file export, see `render`
+    pub fn write_file<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.render())
+    }
+
+    // private layer registration, returns the sanitized name
+    fn use_layer(&mut self, layer: &str) -> String {
+        let mut name = layer.chars()
+            .map(|c| if "<>/\\\":;?*|=`".contains(c) || c.is_control() {'_'} else {c})
+            .collect::<String>();
+        if name.trim().is_empty() {
+            name = "0".to_string();
+        }
+
+        if !self.layers.contains(&name) {
+            self.layers.push(name.clone());
+        }
+        name
+    }
+}
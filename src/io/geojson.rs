@@ -0,0 +1,136 @@
+use super::json::JsonValue;
+use super::super::geometry::{Point, Polygon, PolygonWithHoles};
+
+#[derive(Debug, PartialEq)]
+pub enum GeoJsonError {
+    Json(usize), // invalid json at the given byte offset
+    UnsupportedType(String), // geometry type other than Polygon and MultiPolygon
+    Coordinates // missing or malformed coordinates member
+}
+
+pub struct GeoJson {
+
+}
+
+impl GeoJson {
+    /// GeoJSON geometry object, a Polygon for a single input and a MultiPolygon otherwise
+    /// Rings are closed by repeating their first position, the outer ring runs counter-clockwise and holes clockwise as in RFC 7946
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// use rust_nfp::io::GeoJson;
+    ///
+    /// let square = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// assert_eq!(
+    ///     GeoJson::write_geometry(&[square]),
+    ///     r#"{"type":"Polygon","coordinates":[[[-1,-1],[1,-1],[1,1],[-1,1],[-1,-1]]]}"#);
+    /// ```
+    pub fn write_geometry(polys: &[PolygonWithHoles]) -> String {
+        let geometry = if polys.len() == 1 {
+            JsonValue::Object(vec![
+                ("type".to_string(), JsonValue::String("Polygon".to_string())),
+                ("coordinates".to_string(), polygon_coordinates(&polys[0]))])
+        } else {
+            JsonValue::Object(vec![
+                ("type".to_string(), JsonValue::String("MultiPolygon".to_string())),
+                ("coordinates".to_string(), JsonValue::Array(polys.iter().map(polygon_coordinates).collect()))])
+        };
+
+        geometry.to_json()
+    }
+
+    /// Reads the polygons of a GeoJSON Polygon or MultiPolygon geometry
+    /// Features, feature collections and geometry collections are searched for polygonal geometries,
+    /// other geometry types inside collections are skipped. Extra ordinates and the closing positions are dropped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::io::GeoJson;
+    ///
+    /// let feature = r#"{"type": "Feature", "properties": {"name": "plate"},
+    ///     "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [4, 0], [4, 4], [0, 4], [0, 0]]]}}"#;
+    ///
+    /// let polys = GeoJson::read_geometry(feature).unwrap();
+    /// assert_eq!(polys[0].calculate_area(), 16.0);
+    /// ```
+    pub fn read_geometry(text: &str) -> Result<Vec<PolygonWithHoles>, GeoJsonError> {
+        let value = JsonValue::parse(text).map_err(GeoJsonError::Json)?;
+        read_object(&value, false)
+    }
+}
+
+fn position(pt: &Point) -> JsonValue {
+    JsonValue::Array(vec![JsonValue::Number(pt.x), JsonValue::Number(pt.y)])
+}
+
+fn polygon_coordinates(poly: &PolygonWithHoles) -> JsonValue {
+    let mut poly = poly.clone();
+    poly.normalize_orientation();
+
+    JsonValue::Array(poly.rings()
+        .filter(|r| !r.points.is_empty())
+        .map(|r| JsonValue::Array(r.points.iter().chain(r.points.first()).map(position).collect()))
+        .collect())
+}
+
+// private geometry lookup, inside collections unsupported geometry types are skipped
+fn read_object(value: &JsonValue, in_collection: bool) -> Result<Vec<PolygonWithHoles>, GeoJsonError> {
+    let kind = value.get("type").and_then(|t| t.as_str()).ok_or(GeoJsonError::Coordinates)?;
+    let coordinates = || value.get("coordinates").ok_or(GeoJsonError::Coordinates);
+
+    match kind {
+        "Polygon" => Ok(vec![read_polygon(coordinates()?)?]),
+        "MultiPolygon" => coordinates()?.as_array().ok_or(GeoJsonError::Coordinates)?
+            .iter()
+            .map(read_polygon)
+            .collect(),
+        "Feature" => match value.get("geometry") {
+            Some(JsonValue::Null) | None => Ok(Vec::new()),
+            Some(geometry) => read_object(geometry, in_collection)
+        },
+        "FeatureCollection" | "GeometryCollection" => {
+            let key = if kind == "FeatureCollection" {"features"} else {"geometries"};
+            let mut result = Vec::new();
+            for item in value.get(key).and_then(|i| i.as_array()).ok_or(GeoJsonError::Coordinates)?.iter() {
+                result.extend(read_object(item, true)?);
+            }
+            Ok(result)
+        },
+        _ if in_collection => Ok(Vec::new()),
+        _ => Err(GeoJsonError::UnsupportedType(kind.to_string()))
+    }
+}
+
+fn read_polygon(value: &JsonValue) -> Result<PolygonWithHoles, GeoJsonError> {
+    let mut rings = value.as_array().ok_or(GeoJsonError::Coordinates)?
+        .iter()
+        .map(read_ring)
+        .collect::<Result<Vec<_>, _>>()?;
+    if rings.is_empty() {
+        return Err(GeoJsonError::Coordinates);
+    }
+
+    let outer = rings.remove(0);
+    Ok(PolygonWithHoles::new(outer, rings))
+}
+
+fn read_ring(value: &JsonValue) -> Result<Polygon, GeoJsonError> {
+    let mut pts = value.as_array().ok_or(GeoJsonError::Coordinates)?
+        .iter()
+        .map(|p| {
+            let ordinates = p.as_array().filter(|o| o.len() >= 2).ok_or(GeoJsonError::Coordinates)?;
+            match (ordinates[0].as_f64(), ordinates[1].as_f64()) {
+                (Some(x), Some(y)) => Ok(Point::new().set_values(x, y)),
+                _ => Err(GeoJsonError::Coordinates)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if pts.len() > 1 && pts.first() == pts.last() {
+        pts.pop();
+    }
+    Ok(Polygon::from_points(&pts))
+}
@@ -0,0 +1,20 @@
+pub use self::svg_reader::{SvgReader, SvgError};
+mod svg_reader;
+
+pub use self::svg_writer::{SvgWriter, SvgStyle, SvgStyles};
+mod svg_writer;
+
+pub use self::dxf_reader::{DxfReader, DxfError};
+mod dxf_reader;
+
+pub use self::dxf_writer::DxfWriter;
+mod dxf_writer;
+
+pub use self::wkt::{Wkt, WktError};
+mod wkt;
+
+pub use self::geojson::{GeoJson, GeoJsonError};
+mod geojson;
+
+pub(crate) mod json;
+mod xml;
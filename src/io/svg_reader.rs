@@ -0,0 +1,510 @@
+use super::xml::{self, XmlEvent, XmlElement};
+use super::super::geometry::{Point, Polygon, PolygonWithHoles, Transform, Arc, QuadraticBezier, CubicBezier};
+use std::f64::consts::PI;
+
+// containers whose content is never rendered directly
+const SKIPPED_ELEMENTS: [&str; 7] = ["defs", "clipPath", "mask", "symbol", "pattern", "marker", "metadata"];
+
+#[derive(Debug, PartialEq)]
+pub enum SvgError {
+    Io(String), // reading the file failed, holds the io error message
+    Xml(usize), // malformed markup at the given byte offset
+    Attribute(String, String), // element and name of an attribute with an invalid value
+    PathData(String), // invalid path data, holds the offending `d` attribute
+    Tolerance(f64) // the flattening tolerance is not a positive number
+}
+
+pub struct SvgReader {
+
+}
+
+impl SvgReader {
+    // public file import, see `read_str`
+    pub fn read_file<P: AsRef<std::path::Path>>(path: P, tol: f64) -> Result<Vec<PolygonWithHoles>, SvgError> {
+        let text = std::fs::read_to_string(path).map_err(|e| SvgError::Io(e.to_string()))?;
+        SvgReader::read_str(&text, tol)
+    }
+
+    /// Imports all filled shapes of an svg document as polygons with holes
+    /// Supports `path`, `polygon`, `polyline`, `rect`, `circle` and `ellipse` elements with nested `transform` attributes.
+    /// Curves and arcs are flattened so they deviate at most `tol` from the result, rings are nested by the even-odd rule.
+    /// A tolerance that is not positive is rejected with `SvgError::Tolerance`.
+    /// Coordinates are kept in svg user units with the y-axis pointing down, `viewBox` and styles are ignored
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::io::SvgReader;
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+    ///     <g transform="translate(10, 0)">
+    ///         <path d="M0 0 H4 V4 H0 Z M1 1 V3 H3 V1 Z"/>
+    ///     </g>
+    /// </svg>"#;
+    ///
+    /// let parts = SvgReader::read_str(svg, tol).unwrap();
+    /// assert_eq!(parts.len(), 1);
+    /// assert_eq!(parts[0].holes.len(), 1);
+    /// assert_eq!(parts[0].outer.bounding_box().min.x, 10.0);
+    /// assert!((parts[0].calculate_area() - 12.0).abs() < tol);
+    /// ```
+    pub fn read_str(text: &str, tol: f64) -> Result<Vec<PolygonWithHoles>, SvgError> {
+        Ok(PolygonWithHoles::from_rings(SvgReader::read_rings(text, tol)?, tol))
+    }
+
+    // public import of all closed rings in document order, without nesting them, see `read_str`
+    pub fn read_rings(text: &str, tol: f64) -> Result<Vec<Polygon>, SvgError> {
+        if tol.is_nan() || tol <= 0.0 {
+            return Err(SvgError::Tolerance(tol));
+        }
+
+        let events = xml::parse(text).map_err(SvgError::Xml)?;
+
+        // transform and skip flag of every open element
+        let mut stack: Vec<(Transform, bool)> = Vec::new();
+        let mut rings: Vec<Polygon> = Vec::new();
+
+        for event in events.iter() {
+            match event {
+                XmlEvent::Start(element) => {
+                    let (parent, parent_skipped) = stack.last().cloned().unwrap_or((Transform::identity(), false));
+                    let name = local_name(&element.name);
+                    let transform = match element.attribute("transform") {
+                        Some(value) => parse_transform(value)
+                            .ok_or_else(|| SvgError::Attribute(name.to_string(), "transform".to_string()))?
+                            .then(&parent),
+                        None => parent
+                    };
+                    let skipped = parent_skipped || SKIPPED_ELEMENTS.contains(&name);
+                    stack.push((transform, skipped));
+
+                    if !skipped {
+                        // flatten in local coordinates, finer by the scale of the transform
+                        let local_tol = tol / transform.max_scale().max(f64::MIN_POSITIVE);
+                        rings.extend(element_rings(element, local_tol)?.into_iter()
+                            .map(|pts| Polygon::from_points(&pts.iter().map(|pt| transform.apply(pt)).collect())));
+                    }
+                },
+                XmlEvent::End(_) => {
+                    stack.pop();
+                }
+            }
+        }
+
+        Ok(rings.into_iter().map(|r| clean_ring(r, tol)).filter(|r| r.points.len() >= 3).collect())
+    }
+}
+
+// element name without a namespace prefix
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+// private point lists of one element in local coordinates
+fn element_rings(element: &XmlElement, tol: f64) -> Result<Vec<Vec<Point>>, SvgError> {
+    let name = local_name(&element.name);
+    let number = |attribute: &str, default: f64| -> Result<f64, SvgError> {
+        match element.attribute(attribute) {
+            Some(value) => parse_length(value).ok_or_else(|| SvgError::Attribute(name.to_string(), attribute.to_string())),
+            None => Ok(default)
+        }
+    };
+
+    match name {
+        "path" => {
+            let d = element.attribute("d").unwrap_or("");
+            parse_path_data(d, tol).ok_or_else(|| SvgError::PathData(d.to_string()))
+        },
+        "polygon" | "polyline" => {
+            let coords = parse_numbers(element.attribute("points").unwrap_or(""))
+                .ok_or_else(|| SvgError::Attribute(name.to_string(), "points".to_string()))?;
+            Ok(vec![coords.chunks_exact(2).map(|c| Point::new().set_values(c[0], c[1])).collect()])
+        },
+        "rect" => {
+            let (x, y) = (number("x", 0.0)?, number("y", 0.0)?);
+            let (width, height) = (number("width", 0.0)?, number("height", 0.0)?);
+            // negative sizes are errors, a zero size disables the rect
+            for &(attribute, size) in [("width", width), ("height", height)].iter() {
+                if size.is_nan() || size < 0.0 {
+                    return Err(SvgError::Attribute(name.to_string(), attribute.to_string()));
+                }
+            }
+            if width <= 0.0 || height <= 0.0 {
+                return Ok(Vec::new());
+            }
+
+            let rx = element.attribute("rx").map(|_| number("rx", 0.0)).transpose()?;
+            let ry = element.attribute("ry").map(|_| number("ry", 0.0)).transpose()?;
+            let rx = rx.or(ry).unwrap_or(0.0).clamp(0.0, width / 2.0);
+            let ry = ry.or(Some(rx)).unwrap_or(0.0).clamp(0.0, height / 2.0);
+            Ok(vec![rounded_rect(x, y, width, height, rx, ry, tol)])
+        },
+        "circle" => {
+            let r = number("r", 0.0)?;
+            let center = Point::new().set_values(number("cx", 0.0)?, number("cy", 0.0)?);
+            if r <= 0.0 {
+                return Ok(Vec::new());
+            }
+            Ok(vec![ellipse_points(&center, r, r, 0.0, 0.0, 2.0 * PI, tol)])
+        },
+        "ellipse" => {
+            let (rx, ry) = (number("rx", 0.0)?, number("ry", 0.0)?);
+            let center = Point::new().set_values(number("cx", 0.0)?, number("cy", 0.0)?);
+            if rx <= 0.0 || ry <= 0.0 {
+                return Ok(Vec::new());
+            }
+            Ok(vec![ellipse_points(&center, rx, ry, 0.0, 0.0, 2.0 * PI, tol)])
+        },
+        _ => Ok(Vec::new())
+    }
+}
+
+// private removal of consecutive duplicates, including a closing point equal to the start
+fn clean_ring(mut ring: Polygon, tol: f64) -> Polygon {
+    ring.points.dedup_by(|a, b| a.epsilon_equals(b, tol));
+    while ring.points.len() > 1 && ring.points[0].epsilon_equals(ring.points.last().unwrap(), tol) {
+        ring.points.pop();
+    }
+    ring
+}
+
+// private points along an elliptical arc, excluding the start and including the end point
+// the angles are parametric angles of the ellipse before its rotation by phi
+fn ellipse_points(center: &Point, rx: f64, ry: f64, phi: f64, start: f64, sweep: f64, tol: f64) -> Vec<Point> {
+    // the ellipse is a scaled circle, so chords on the larger radius bound the deviation
+    let count = Arc::new(center, rx.max(ry), start, sweep).segment_count(tol);
+    let (sin_phi, cos_phi) = phi.sin_cos();
+
+    (1..=count)
+        .map(|n| {
+            let (sin, cos) = (start + sweep * n as f64 / count as f64).sin_cos();
+            Point::new().set_values(
+                center.x + rx * cos_phi * cos - ry * sin_phi * sin,
+                center.y + rx * sin_phi * cos + ry * cos_phi * sin)
+        })
+        .collect()
+}
+
+// private rect outline, corners rounded by elliptical quarter arcs
+fn rounded_rect(x: f64, y: f64, width: f64, height: f64, rx: f64, ry: f64, tol: f64) -> Vec<Point> {
+    if rx <= 0.0 || ry <= 0.0 {
+        return vec![
+            Point::new().set_values(x, y),
+            Point::new().set_values(x + width, y),
+            Point::new().set_values(x + width, y + height),
+            Point::new().set_values(x, y + height)];
+    }
+
+    let corners = [
+        (x + width - rx, y + ry, -PI / 2.0),
+        (x + width - rx, y + height - ry, 0.0),
+        (x + rx, y + height - ry, PI / 2.0),
+        (x + rx, y + ry, PI)];
+
+    let mut pts = vec![Point::new().set_values(x + rx, y)];
+    for (cx, cy, start) in corners.iter() {
+        let center = Point::new().set_values(*cx, *cy);
+        pts.push(Point::new().set_values(cx + rx * start.cos(), cy + ry * start.sin()));
+        pts.extend(ellipse_points(&center, rx, ry, 0.0, *start, PI / 2.0, tol));
+    }
+    pts
+}
+
+// private svg length, plain numbers and pixels only
+fn parse_length(value: &str) -> Option<f64> {
+    value.trim().trim_end_matches("px").trim().parse::<f64>().ok()
+}
+
+// private list of numbers separated by whitespace and commas
+fn parse_numbers(text: &str) -> Option<Vec<f64>> {
+    let mut scanner = Scanner::new(text);
+    let mut numbers = Vec::new();
+    while !scanner.at_end() {
+        numbers.push(scanner.number()?);
+    }
+    Some(numbers)
+}
+
+// private transform list like "translate(10 20) rotate(45)", the rightmost transformation is applied first
+fn parse_transform(text: &str) -> Option<Transform> {
+    let mut result = Transform::identity();
+    let mut rest = text.trim();
+
+    while !rest.is_empty() {
+        let open = rest.find('(')?;
+        let close = rest.find(')')?;
+        let name = rest[..open].trim().trim_start_matches(',').trim();
+        let args = parse_numbers(&rest[(open + 1)..close])?;
+
+        let t = match (name, args.as_slice()) {
+            ("matrix", [a, b, c, d, e, f]) => Transform{a: *a, b: *b, c: *c, d: *d, e: *e, f: *f},
+            ("translate", [x]) => Transform::translation(*x, 0.0),
+            ("translate", [x, y]) => Transform::translation(*x, *y),
+            ("scale", [s]) => Transform::scale(*s, *s),
+            ("scale", [sx, sy]) => Transform::scale(*sx, *sy),
+            ("rotate", [angle]) => Transform::rotation(angle.to_radians()),
+            ("rotate", [angle, cx, cy]) => Transform::rotation_around(angle.to_radians(), &Point::new().set_values(*cx, *cy)),
+            ("skewX", [angle]) => Transform::skew_x(angle.to_radians()),
+            ("skewY", [angle]) => Transform::skew_y(angle.to_radians()),
+            _ => return None
+        };
+
+        result = t.then(&result);
+        rest = rest[(close + 1)..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+
+    Some(result)
+}
+
+// private tokenizer for path data and number lists
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Scanner<'a> {
+    fn new(text: &'a str) -> Scanner<'a> {
+        let mut scanner = Scanner{bytes: text.as_bytes(), pos: 0};
+        scanner.skip_separators();
+        scanner
+    }
+
+    fn skip_separators(&mut self) {
+        while self.pos < self.bytes.len() && (self.bytes[self.pos].is_ascii_whitespace() || self.bytes[self.pos] == b',') {
+            self.pos += 1;
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    // next path command letter, if the next token is one
+    fn command(&mut self) -> Option<u8> {
+        let c = *self.bytes.get(self.pos)?;
+        if c.is_ascii_alphabetic() && c != b'e' && c != b'E' {
+            self.pos += 1;
+            self.skip_separators();
+            return Some(c);
+        }
+        None
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        let start = self.pos;
+        let digits = |s: &mut Scanner| {
+            let from = s.pos;
+            while s.pos < s.bytes.len() && s.bytes[s.pos].is_ascii_digit() {
+                s.pos += 1;
+            }
+            s.pos - from
+        };
+
+        if self.pos < self.bytes.len() && (self.bytes[self.pos] == b'+' || self.bytes[self.pos] == b'-') {
+            self.pos += 1;
+        }
+        let mut count = digits(self);
+        if self.pos < self.bytes.len() && self.bytes[self.pos] == b'.' {
+            self.pos += 1;
+            count += digits(self);
+        }
+        if count == 0 {
+            self.pos = start;
+            return None;
+        }
+
+        // exponent only if digits follow
+        if self.pos < self.bytes.len() && (self.bytes[self.pos] == b'e' || self.bytes[self.pos] == b'E') {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if self.pos < self.bytes.len() && (self.bytes[self.pos] == b'+' || self.bytes[self.pos] == b'-') {
+                self.pos += 1;
+            }
+            if digits(self) == 0 {
+                self.pos = mantissa_end;
+            }
+        }
+
+        let value = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse::<f64>().ok();
+        self.skip_separators();
+        value
+    }
+
+    // single digit arc flag, which may be written without separators
+    fn flag(&mut self) -> Option<bool> {
+        let c = *self.bytes.get(self.pos)?;
+        if c != b'0' && c != b'1' {
+            return None;
+        }
+        self.pos += 1;
+        self.skip_separators();
+        Some(c == b'1')
+    }
+
+    fn point(&mut self, relative_to: Option<&Point>) -> Option<Point> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Some(match relative_to {
+            Some(origin) => Point::new().set_values(origin.x + x, origin.y + y),
+            None => Point::new().set_values(x, y)
+        })
+    }
+}
+
+// private path data flattening, returns every subpath as a ring or None for invalid data
+fn parse_path_data(d: &str, tol: f64) -> Option<Vec<Vec<Point>>> {
+    let mut scanner = Scanner::new(d);
+    let mut rings: Vec<Vec<Point>> = Vec::new();
+    let mut ring: Vec<Point> = Vec::new();
+    let mut current = Point::new();
+    let mut start = Point::new();
+    // reflected control point of the last curve, for the smooth curve commands
+    let mut last_cubic: Option<Point> = None;
+    let mut last_quadratic: Option<Point> = None;
+    let mut command: Option<u8> = None;
+
+    while !scanner.at_end() {
+        if let Some(c) = scanner.command() {
+            command = Some(c);
+        }
+        let c = command?;
+        let origin = if c.is_ascii_lowercase() {Some(current)} else {None};
+        if ring.is_empty() && !c.eq_ignore_ascii_case(&b'M') {
+            ring.push(current);
+        }
+
+        let mut next_cubic = None;
+        let mut next_quadratic = None;
+        match c.to_ascii_uppercase() {
+            b'M' => {
+                if ring.len() > 1 {
+                    rings.push(std::mem::take(&mut ring));
+                }
+                ring.clear();
+                current = scanner.point(origin.as_ref())?;
+                start = current;
+                ring.push(current);
+                // further coordinate pairs are implicit line commands
+                command = Some(if c == b'm' {b'l'} else {b'L'});
+            },
+            b'L' => {
+                current = scanner.point(origin.as_ref())?;
+                ring.push(current);
+            },
+            b'H' => {
+                let x = scanner.number()?;
+                current = Point::new().set_values(if c == b'h' {current.x + x} else {x}, current.y);
+                ring.push(current);
+            },
+            b'V' => {
+                let y = scanner.number()?;
+                current = Point::new().set_values(current.x, if c == b'v' {current.y + y} else {y});
+                ring.push(current);
+            },
+            b'C' | b'S' => {
+                let control0 = if c.eq_ignore_ascii_case(&b'C') {
+                    scanner.point(origin.as_ref())?
+                } else {
+                    last_cubic.map(|p| reflect(&p, &current)).unwrap_or(current)
+                };
+                let control1 = scanner.point(origin.as_ref())?;
+                let to = scanner.point(origin.as_ref())?;
+                ring.extend_from_slice(&CubicBezier::new(&current, &control0, &control1, &to).flatten(tol).points[1..]);
+                next_cubic = Some(control1);
+                current = to;
+            },
+            b'Q' | b'T' => {
+                let control = if c.eq_ignore_ascii_case(&b'Q') {
+                    scanner.point(origin.as_ref())?
+                } else {
+                    last_quadratic.map(|p| reflect(&p, &current)).unwrap_or(current)
+                };
+                let to = scanner.point(origin.as_ref())?;
+                ring.extend_from_slice(&QuadraticBezier::new(&current, &control, &to).flatten(tol).points[1..]);
+                next_quadratic = Some(control);
+                current = to;
+            },
+            b'A' => {
+                let (rx, ry, angle) = (scanner.number()?, scanner.number()?, scanner.number()?);
+                let (large_arc, sweep) = (scanner.flag()?, scanner.flag()?);
+                let to = scanner.point(origin.as_ref())?;
+                ring.extend(svg_arc_points(&current, &to, rx.abs(), ry.abs(), angle.to_radians(), large_arc, sweep, tol));
+                current = to;
+            },
+            b'Z' => {
+                if ring.len() > 1 {
+                    rings.push(std::mem::take(&mut ring));
+                }
+                ring.clear();
+                current = start;
+                // no arguments may follow, a new command is needed
+                command = None;
+            },
+            _ => return None
+        }
+
+        last_cubic = next_cubic;
+        last_quadratic = next_quadratic;
+    }
+
+    if ring.len() > 1 {
+        rings.push(ring);
+    }
+    Some(rings)
+}
+
+// private reflection of a control point at the current point
+fn reflect(control: &Point, at: &Point) -> Point {
+    Point::new().set_values(2.0 * at.x - control.x, 2.0 * at.y - control.y)
+}
+
+// private elliptical arc in svg end point notation, converted to center notation as in the svg specification
+#[allow(clippy::too_many_arguments)]
+fn svg_arc_points(from: &Point, to: &Point, rx: f64, ry: f64, phi: f64, large_arc: bool, sweep: bool, tol: f64) -> Vec<Point> {
+    if from == to {
+        return Vec::new();
+    }
+    if rx == 0.0 || ry == 0.0 {
+        return vec![*to];
+    }
+
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let dx = (from.x - to.x) / 2.0;
+    let dy = (from.y - to.y) / 2.0;
+    let x1 = cos_phi * dx + sin_phi * dy;
+    let y1 = -sin_phi * dx + cos_phi * dy;
+
+    // radii too small to reach the end point are scaled up
+    let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+    let (rx, ry) = if lambda > 1.0 {(rx * lambda.sqrt(), ry * lambda.sqrt())} else {(rx, ry)};
+
+    let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+    let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+    let sign = if large_arc != sweep {1.0} else {-1.0};
+    let coef = sign * (num / den).max(0.0).sqrt();
+    let cx1 = coef * rx * y1 / ry;
+    let cy1 = -coef * ry * x1 / rx;
+
+    let center = Point::new().set_values(
+        cos_phi * cx1 - sin_phi * cy1 + (from.x + to.x) / 2.0,
+        sin_phi * cx1 + cos_phi * cy1 + (from.y + to.y) / 2.0);
+
+    let start_angle = ((y1 - cy1) / ry).atan2((x1 - cx1) / rx);
+    let end_angle = ((-y1 - cy1) / ry).atan2((-x1 - cx1) / rx);
+    let mut delta = (end_angle - start_angle) % (2.0 * PI);
+    if !sweep && delta > 0.0 {
+        delta -= 2.0 * PI;
+    }
+    if sweep && delta < 0.0 {
+        delta += 2.0 * PI;
+    }
+
+    let mut pts = ellipse_points(&center, rx, ry, phi, start_angle, delta, tol);
+    // hit the end exactly
+    if let Some(last) = pts.last_mut() {
+        *last = *to;
+    }
+    pts
+}
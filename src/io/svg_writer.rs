@@ -0,0 +1,355 @@
+use super::xml::encode_entities;
+use super::super::geometry::{Point, Polygon, PolygonWithHoles, BoundingBox, Transform, LineSegment};
+use super::super::nesting::Layout;
+use super::super::trace::{NfpTrace, NfpTraceStep};
+use std::fmt::Write;
+
+/// Presentation attributes of rendered shapes, colors are any svg color value
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgStyle {
+    pub fill: String,
+    pub stroke: String,
+    pub stroke_width: f64,
+    pub opacity: f64
+}
+
+impl SvgStyle {
+    // public constructor, fully opaque
+    pub fn new(fill: &str, stroke: &str, stroke_width: f64) -> SvgStyle {
+        SvgStyle{fill: fill.to_string(), stroke: stroke.to_string(), stroke_width, opacity: 1.0}
+    }
+
+    pub fn set_opacity(mut self, opacity: f64) -> SvgStyle {
+        self.opacity = opacity;
+        self
+    }
+
+    fn attributes(&self) -> String {
+        format!("fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\" opacity=\"{}\"",
+            encode_entities(&self.fill), encode_entities(&self.stroke), self.stroke_width, self.opacity)
+    }
+}
+
+/// Styles used by the higher level `SvgWriter` functions
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgStyles {
+    pub sheet: SvgStyle,
+    pub defect: SvgStyle, // defect zones of a sheet
+    pub part: SvgStyle,
+    pub nfp: SvgStyle,
+    pub orbiting: SvgStyle, // moving polygon of an nfp
+    pub reference_point: SvgStyle,
+    pub label: SvgStyle,
+    pub point_radius: f64,
+    pub font_size: f64
+}
+
+impl Default for SvgStyles {
+    fn default() -> SvgStyles {
+        SvgStyles{
+            sheet: SvgStyle::new("#f4f1e8", "#555555", 0.5),
+            defect: SvgStyle::new("#e8a0a0", "#a02c2c", 0.3).set_opacity(0.6),
+            part: SvgStyle::new("#7aa6d6", "#1f4e79", 0.3).set_opacity(0.8),
+            nfp: SvgStyle::new("none", "#d62728", 0.3),
+            orbiting: SvgStyle::new("#98df8a", "#2ca02c", 0.3).set_opacity(0.6),
+            reference_point: SvgStyle::new("#d62728", "none", 0.0),
+            label: SvgStyle::new("#222222", "none", 0.0),
+            point_radius: 0.5,
+            font_size: 4.0
+        }
+    }
+}
+
+/// Collects shapes and renders them into a standalone svg document
+/// The view box is fitted to everything added. With `flip_y` the y-axis points up, as in the nfp math,
+/// otherwise coordinates are written unchanged, so `SvgReader` reads them back identically
+///
+/// # Examples
+///
+/// ```
+/// use rust_nfp::geometry::{Polygon, Point};
+/// use rust_nfp::io::{SvgWriter, SvgStyles};
+/// use rust_nfp::lib::calculate_convex_nfp;
+///
+/// let square = Polygon::square(2.0);
+/// let tri = Polygon::from_points(&vec![Point::new(), Point::new().set_values(2.0, 2.0), Point::new().set_values(-2.0, 2.0)]);
+/// let nfp = calculate_convex_nfp(&square, tri.clone());
+///
+/// let mut writer = SvgWriter::new(SvgStyles::default());
+/// writer.add_nfp(&square, &tri, &nfp);
+///
+/// let svg = writer.render();
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<circle").count(), nfp.points.len());
+/// ```
+pub struct SvgWriter {
+    pub styles: SvgStyles,
+    pub flip_y: bool,
+    pub margin: f64, // space around the fitted view box
+    pub sheet_gap: f64, // space between sheets of a rendered layout
+    elements: Vec<String>,
+    bbox: BoundingBox
+}
+
+impl SvgWriter {
+    // public constructor for an empty document
+    pub fn new(styles: SvgStyles) -> SvgWriter {
+        SvgWriter{styles, flip_y: false, margin: 5.0, sheet_gap: 10.0, elements: Vec::new(), bbox: BoundingBox::empty()}
+    }
+
+    pub fn add_polygon(&mut self, poly: &Polygon, style: &SvgStyle) {
+        if poly.points.is_empty() {
+            return;
+        }
+
+        let pts = poly.points.iter()
+            .map(|pt| self.map(pt))
+            .map(|pt| format!("{},{}", pt.x, pt.y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.elements.push(format!("<polygon points=\"{}\" {}/>", pts, style.attributes()));
+    }
+
+    // public polygon with holes, rendered as a single even-odd path
+    pub fn add_polygon_with_holes(&mut self, poly: &PolygonWithHoles, style: &SvgStyle) {
+        let mut d = String::new();
+        for ring in poly.rings().filter(|r| !r.points.is_empty()) {
+            for (n, pt) in ring.points.iter().enumerate() {
+                let pt = self.map(pt);
+                let _ = write!(d, "{}{} {} ", if n == 0 {"M"} else {"L"}, pt.x, pt.y);
+            }
+            d.push('Z');
+        }
+        if d.is_empty() {
+            return;
+        }
+
+        self.elements.push(format!("<path d=\"{}\" fill-rule=\"evenodd\" {}/>", d, style.attributes()));
+    }
+
+    // public line segment, only the stroke of the style is used
+    pub fn add_line_segment(&mut self, seg: &LineSegment, style: &SvgStyle) {
+        let (from, to) = (self.map(&seg.from), self.map(&seg.to));
+        self.elements.push(format!("<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>", from.x, from.y, to.x, to.y, style.attributes()));
+    }
+
+    // public point marker, drawn as a circle with the radius of the styles
+    pub fn add_point(&mut self, pt: &Point, style: &SvgStyle) {
+        let r = self.styles.point_radius;
+        let center = self.map(pt);
+        self.bbox.include_point(&Point::new().set_values(center.x - r, center.y - r));
+        self.bbox.include_point(&Point::new().set_values(center.x + r, center.y + r));
+        self.elements.push(format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>", center.x, center.y, r, style.attributes()));
+    }
+
+    // public text label centered on the given point
+    pub fn add_label(&mut self, pt: &Point, text: &str, style: &SvgStyle) {
+        let at = self.map(pt);
+        self.elements.push(format!(
+            "<text x=\"{}\" y=\"{}\" font-size=\"{}\" font-family=\"sans-serif\" text-anchor=\"middle\" dominant-baseline=\"middle\" {}>{}</text>",
+            at.x, at.y, self.styles.font_size, style.attributes(), encode_entities(text)));
+    }
+
+    /// Renders an nfp together with the polygons it was calculated from
+    /// The stationary polygon and the nfp are drawn as they are, the orbiting polygon is drawn touching
+    /// at the first nfp corner and every nfp corner is marked as a possible reference point
+    pub fn add_nfp(&mut self, first: &Polygon, other: &Polygon, nfp: &Polygon) {
+        let styles = self.styles.clone();
+        self.add_polygon(first, &styles.part);
+        if let Some(pt) = nfp.points.first() {
+            self.add_polygon(&other.transform(&Transform::translation(pt.x, pt.y)), &styles.orbiting);
+        }
+        self.add_polygon(nfp, &styles.nfp);
+        for pt in nfp.points.iter() {
+            self.add_point(pt, &styles.reference_point);
+        }
+    }
+
+    /// Renders the state of a traced nfp calculation after the given step
+    /// The convex pieces of first are drawn as sheets, finished piece nfps as nfps. While a convex nfp is built,
+    /// its stationary piece, the edges chained so far and the orbiting piece at the current reference point are shown.
+    /// Sorted edges are labeled with their order, touching points and the penetration translation from the origin
+    /// are marked as reference points, the final step of an nfp shows the complete nfp
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::Polygon;
+    /// use rust_nfp::io::{SvgWriter, SvgStyles};
+    /// use rust_nfp::lib::calculate_nfp_traced;
+    /// use rust_nfp::trace::NfpTrace;
+    ///
+    /// let mut trace = NfpTrace::new();
+    /// calculate_nfp_traced(&Polygon::square(2.0), &Polygon::square(1.0), 0.0001, &mut trace);
+    ///
+    /// // one document per step
+    /// let frames = (0..trace.steps.len())
+    ///     .map(|step| {
+    ///         let mut writer = SvgWriter::new(SvgStyles::default());
+    ///         writer.add_nfp_trace(&trace, step);
+    ///         writer.render()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(frames[6].matches("<line").count(), 2); // after the first two chained edges
+    /// ```
+    pub fn add_nfp_trace(&mut self, trace: &NfpTrace, step: usize) {
+        let styles = self.styles.clone();
+        let steps = &trace.steps[..(step + 1).min(trace.steps.len())];
+
+        // replay up to the step
+        let mut pieces: &[Polygon] = &[];
+        let mut finished: Vec<&Polygon> = Vec::new();
+        let mut current: Option<(&Polygon, &Polygon, Point)> = None;
+        let mut chained: Vec<&LineSegment> = Vec::new();
+        for s in steps.iter() {
+            match s {
+                NfpTraceStep::ConvexPieces{first, ..} => pieces = first,
+                NfpTraceStep::ConvexStart{first, other, start} => {
+                    current = Some((first, other, *start));
+                    chained.clear();
+                },
+                NfpTraceStep::EdgeTranslated{edge, ..} => {
+                    chained.push(edge);
+                    if let Some(c) = current.as_mut() {
+                        c.2 = edge.to;
+                    }
+                },
+                NfpTraceStep::ConvexResult{nfp} => {
+                    finished.push(nfp);
+                    current = None;
+                    chained.clear();
+                },
+                _ => ()
+            }
+        }
+
+        for piece in pieces.iter() {
+            self.add_polygon(piece, &styles.sheet);
+        }
+        for nfp in finished.iter() {
+            self.add_polygon(nfp, &styles.nfp);
+        }
+        if let Some((first, other, reference)) = current {
+            self.add_polygon(first, &styles.part);
+            for edge in chained.iter() {
+                self.add_line_segment(edge, &styles.nfp);
+            }
+            self.add_polygon(&other.transform(&Transform::translation(reference.x, reference.y)), &styles.orbiting);
+            self.add_point(&reference, &styles.reference_point);
+        }
+
+        match steps.last() {
+            Some(NfpTraceStep::ConvexPieces{other, ..}) => {
+                for piece in other.iter() {
+                    self.add_polygon(piece, &styles.orbiting);
+                }
+            },
+            Some(NfpTraceStep::SortedEdges{edges}) => {
+                for (n, edge) in edges.iter().enumerate() {
+                    self.add_line_segment(edge, &styles.nfp);
+                    let mid = Point::new().set_values((edge.from.x + edge.to.x) / 2.0, (edge.from.y + edge.to.y) / 2.0);
+                    self.add_label(&mid, &n.to_string(), &styles.label);
+                }
+            },
+            Some(NfpTraceStep::TouchingPoints{contacts, ..}) => {
+                for contact in contacts.iter() {
+                    self.add_point(contact, &styles.reference_point);
+                }
+            },
+            Some(NfpTraceStep::NfpResult{nfp}) => {
+                for piece in nfp.iter() {
+                    self.add_polygon(piece, &styles.nfp);
+                }
+            },
+            Some(NfpTraceStep::Penetration{translation, ..}) => {
+                let exit = Point::new().set_values(translation.x, translation.y);
+                self.add_line_segment(&LineSegment::new_from_points(&Point::new(), &exit), &styles.orbiting);
+                self.add_point(&exit, &styles.reference_point);
+            },
+            _ => ()
+        }
+    }
+
+    /// Renders all steps of a traced nfp calculation as frames of an svg animation, shown one after another
+    /// for the given seconds each, the last frame stays visible
+    pub fn add_nfp_trace_animation(&mut self, trace: &NfpTrace, seconds_per_step: f64) {
+        let count = trace.steps.len();
+        for step in 0..count {
+            let timing = if step + 1 == count {
+                "fill=\"freeze\"".to_string()
+            } else {
+                format!("dur=\"{}s\"", seconds_per_step)
+            };
+            self.elements.push(format!(
+                "<g id=\"step-{}\" visibility=\"hidden\"><set attributeName=\"visibility\" to=\"visible\" begin=\"{}s\" {}/>",
+                step, step as f64 * seconds_per_step, timing));
+            self.add_nfp_trace(trace, step);
+            self.elements.push("</g>".to_string());
+        }
+    }
+
+    /// Renders a nesting layout, sheets are placed side by side in the order of the layout
+    /// Every sheet is labeled with its name and every placed part with the part name at its centroid
+    /// Defect zones are drawn on the sheet below the parts
+    pub fn add_layout(&mut self, layout: &Layout) {
+        let styles = self.styles.clone();
+        let mut cursor = 0.0;
+
+        for (index, sheet) in layout.sheets.iter().enumerate() {
+            let sheet_bbox = sheet.shape.bounding_box();
+            if sheet_bbox.is_empty() {
+                continue;
+            }
+            let shift = Transform::translation(cursor - sheet_bbox.min.x, -sheet_bbox.min.y);
+
+            self.elements.push(format!("<g id=\"sheet-{}\">", index));
+            self.add_polygon_with_holes(&sheet.shape.transform(&shift), &styles.sheet);
+            for defect in sheet.defects.iter() {
+                self.add_polygon(&defect.shape.transform(&shift), &styles.defect);
+            }
+            for placement in layout.placements_on_sheet(index) {
+                let shape = placement.shape.transform(&shift);
+                self.add_polygon_with_holes(&shape, &styles.part);
+                self.add_label(&shape.calculate_mass_properties().centroid, &placement.part, &styles.label);
+            }
+
+            // sheet name above the sheet, top is at the larger y with the y-axis pointing up
+            let top = if self.flip_y {sheet_bbox.height() + styles.font_size} else {-styles.font_size};
+            self.add_label(&Point::new().set_values(cursor + sheet_bbox.width() / 2.0, top), &sheet.name, &styles.label);
+            self.elements.push("</g>".to_string());
+
+            cursor += sheet_bbox.width() + self.sheet_gap;
+        }
+    }
+
+    // public standalone svg document of everything added so far
+    pub fn render(&self) -> String {
+        let (min, width, height) = if self.bbox.is_empty() {
+            (Point::new(), 1.0, 1.0)
+        } else {
+            (self.bbox.min, self.bbox.width(), self.bbox.height())
+        };
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min.x - self.margin, min.y - self.margin, width + 2.0 * self.margin, height + 2.0 * self.margin);
+        for element in self.elements.iter() {
+            svg.push_str(element);
+            svg.push('\n');
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // public file export, see `render`
+    pub fn write_file<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.render())
+    }
+
+    // private mapping into document coordinates, growing the view box
+    fn map(&mut self, pt: &Point) -> Point {
+        let mapped = if self.flip_y {Point::new().set_values(pt.x, -pt.y)} else {*pt};
+        self.bbox.include_point(&mapped);
+        mapped
+    }
+}
@@ -0,0 +1,218 @@
+use super::super::geometry::{Point, Polygon, PolygonWithHoles};
+use std::fmt::Write;
+
+#[derive(Debug, PartialEq)]
+pub enum WktError {
+    Syntax(usize), // byte offset of the first unexpected token
+    UnsupportedType(String) // geometry type other than POLYGON and MULTIPOLYGON
+}
+
+pub struct Wkt {
+
+}
+
+impl Wkt {
+    /// Well-known text of a polygon with holes
+    /// Rings are closed by repeating their first point, the outer ring runs counter-clockwise and holes clockwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// use rust_nfp::io::Wkt;
+    ///
+    /// let square = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// assert_eq!(Wkt::write_polygon(&square), "POLYGON ((-1 -1, 1 -1, 1 1, -1 1, -1 -1))");
+    /// ```
+    pub fn write_polygon(poly: &PolygonWithHoles) -> String {
+        let mut text = String::from("POLYGON ");
+        write_rings(poly, &mut text);
+        text
+    }
+
+    // public well-known text of several polygons with holes, see `write_polygon`
+    pub fn write_multi_polygon(polys: &[PolygonWithHoles]) -> String {
+        if polys.is_empty() {
+            return "MULTIPOLYGON EMPTY".to_string();
+        }
+
+        let mut text = String::from("MULTIPOLYGON (");
+        for (n, poly) in polys.iter().enumerate() {
+            if n > 0 {
+                text.push_str(", ");
+            }
+            write_rings(poly, &mut text);
+        }
+        text.push(')');
+        text
+    }
+
+    /// Reads a POLYGON or MULTIPOLYGON in well-known text, keywords are case insensitive
+    /// The first ring of every polygon is its outer boundary, all further rings are holes.
+    /// Z and M coordinates are accepted and dropped, the repeated closing point of every ring is removed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::io::Wkt;
+    ///
+    /// let polys = Wkt::read("MULTIPOLYGON (((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 1 3, 3 3, 3 1, 1 1)), ((5 0, 6 0, 6 1, 5 0)))").unwrap();
+    /// assert_eq!(polys.len(), 2);
+    /// assert_eq!(polys[0].calculate_area(), 12.0);
+    /// assert_eq!(polys[1].outer.points.len(), 3);
+    /// ```
+    pub fn read(text: &str) -> Result<Vec<PolygonWithHoles>, WktError> {
+        let mut parser = Parser{bytes: text.as_bytes(), pos: 0};
+        let start = parser.pos;
+        let kind = parser.word().ok_or(start)?.to_ascii_uppercase();
+
+        // optional dimension tag
+        let dimension_pos = parser.pos;
+        match parser.word().map(|w| w.to_ascii_uppercase()) {
+            Some(ref w) if w == "Z" || w == "M" || w == "ZM" => (),
+            _ => parser.pos = dimension_pos
+        }
+
+        let empty_pos = parser.pos;
+        let is_empty = parser.word().is_some_and(|w| w.eq_ignore_ascii_case("EMPTY"));
+        if !is_empty {
+            parser.pos = empty_pos;
+        }
+
+        let result = match kind.as_str() {
+            "POLYGON" if is_empty => Vec::new(),
+            "POLYGON" => vec![parser.polygon()?],
+            "MULTIPOLYGON" if is_empty => Vec::new(),
+            "MULTIPOLYGON" => parser.list(|p| p.polygon())?,
+            _ => return Err(WktError::UnsupportedType(kind))
+        };
+
+        parser.skip_whitespace();
+        if parser.pos != parser.bytes.len() {
+            return Err(WktError::Syntax(parser.pos));
+        }
+        Ok(result)
+    }
+}
+
+// private rings of one polygon in parentheses
+fn write_rings(poly: &PolygonWithHoles, text: &mut String) {
+    if poly.outer.points.is_empty() {
+        text.push_str("EMPTY");
+        return;
+    }
+
+    let mut poly = poly.clone();
+    poly.normalize_orientation();
+
+    text.push('(');
+    for (n, ring) in poly.rings().enumerate() {
+        if n > 0 {
+            text.push_str(", ");
+        }
+        text.push('(');
+        for pt in ring.points.iter().chain(ring.points.first()) {
+            if !text.ends_with('(') {
+                text.push_str(", ");
+            }
+            let _ = write!(text, "{} {}", pt.x, pt.y);
+        }
+        text.push(')');
+    }
+    text.push(')');
+}
+
+impl From<usize> for WktError {
+    fn from(pos: usize) -> WktError {
+        WktError::Syntax(pos)
+    }
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn word(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_alphabetic() {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&self.bytes[start..self.pos]).into_owned())
+    }
+
+    fn symbol(&mut self, c: u8) -> Result<(), WktError> {
+        self.skip_whitespace();
+        if self.bytes.get(self.pos) != Some(&c) {
+            return Err(WktError::Syntax(self.pos));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.pos < self.bytes.len() && b"+-.eE0123456789".contains(&self.bytes[self.pos]) {
+            self.pos += 1;
+        }
+        let value = std::str::from_utf8(&self.bytes[start..self.pos]).ok().and_then(|s| s.parse::<f64>().ok());
+        if value.is_none() {
+            self.pos = start;
+        }
+        value
+    }
+
+    // parenthesized, comma separated list
+    fn list<T>(&mut self, item: impl Fn(&mut Parser<'a>) -> Result<T, WktError>) -> Result<Vec<T>, WktError> {
+        self.symbol(b'(')?;
+        let mut items = vec![item(self)?];
+        loop {
+            self.skip_whitespace();
+            match self.bytes.get(self.pos) {
+                Some(b',') => {
+                    self.pos += 1;
+                    items.push(item(self)?);
+                },
+                Some(b')') => {
+                    self.pos += 1;
+                    return Ok(items);
+                },
+                _ => return Err(WktError::Syntax(self.pos))
+            }
+        }
+    }
+
+    // coordinate with two to four ordinates, only x and y are kept
+    fn point(&mut self) -> Result<Point, WktError> {
+        let x = self.number().ok_or(self.pos)?;
+        let y = self.number().ok_or(self.pos)?;
+        while self.number().is_some() {}
+        Ok(Point::new().set_values(x, y))
+    }
+
+    fn ring(&mut self) -> Result<Polygon, WktError> {
+        let mut pts = self.list(|p| p.point())?;
+        if pts.len() > 1 && pts.first() == pts.last() {
+            pts.pop();
+        }
+        Ok(Polygon::from_points(&pts))
+    }
+
+    fn polygon(&mut self) -> Result<PolygonWithHoles, WktError> {
+        let mut rings = self.list(|p| p.ring())?;
+        let outer = rings.remove(0);
+        Ok(PolygonWithHoles::new(outer, rings))
+    }
+}
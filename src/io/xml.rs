@@ -0,0 +1,140 @@
+// minimal xml tokenizer, just enough for svg files
+// comments, processing instructions, doctypes and text content are skipped
+
+#[derive(Debug, PartialEq)]
+pub enum XmlEvent {
+    Start(XmlElement), // opening or self-closing tag
+    End(String) // closing tag, also emitted right after a self-closing start
+}
+
+#[derive(Debug, PartialEq)]
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>
+}
+
+impl XmlElement {
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+}
+
+// parse a complete document into a flat list of events, Err holds the byte offset of malformed markup
+pub fn parse(text: &str) -> Result<Vec<XmlEvent>, usize> {
+    let mut events = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = text[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &text[start..];
+
+        // markup that is skipped completely
+        let skipped = [("<!--", "-->"), ("<?", "?>"), ("<![CDATA[", "]]>"), ("<!", ">")].iter()
+            .find(|(open, _)| rest.starts_with(open));
+        if let Some((open, close)) = skipped {
+            let end = rest[open.len()..].find(close).ok_or(start)?;
+            pos = start + open.len() + end + close.len();
+            continue;
+        }
+
+        let end = start + find_tag_end(rest).ok_or(start)?;
+        let tag = &text[(start + 1)..end];
+        pos = end + 1;
+
+        if let Some(name) = tag.strip_prefix('/') {
+            events.push(XmlEvent::End(name.trim().to_string()));
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let element = parse_element(tag).ok_or(start)?;
+        let name = element.name.clone();
+        events.push(XmlEvent::Start(element));
+        if self_closing {
+            events.push(XmlEvent::End(name));
+        }
+    }
+
+    Ok(events)
+}
+
+// closing '>' of a tag, ignoring any inside quoted attribute values
+fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => ()
+        }
+    }
+    None
+}
+
+fn parse_element(tag: &str) -> Option<XmlElement> {
+    let name_end = tag.find(|c: char| c.is_whitespace()).unwrap_or(tag.len());
+    let name = tag[..name_end].to_string();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut attributes = Vec::new();
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=')?;
+        let key = rest[..eq].trim().to_string();
+        let after = rest[(eq + 1)..].trim_start();
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let close = after[1..].find(quote)?;
+        attributes.push((key, decode_entities(&after[1..(close + 1)])));
+        rest = after[(close + 2)..].trim_start();
+    }
+
+    Some(XmlElement{name, attributes})
+}
+
+// replace the predefined and numeric character references
+pub fn decode_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        result.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let entity = &rest[1..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(std::char::from_u32),
+                _ if entity.starts_with('#') => entity[1..].parse::<u32>().ok().and_then(std::char::from_u32),
+                _ => None
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                result.push(c);
+                rest = &rest[(semi + 1)..];
+            },
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+// escape text for attribute values and text content
+pub fn encode_entities(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
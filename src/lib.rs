@@ -1,20 +1,50 @@
+// private diagnostics of the algorithms, forwarded to the `log` facade with the `log` feature and compiled
+// out without it. Key-value pairs before the `;` become structured fields of the record
+macro_rules! log_debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+    }};
+}
+
+macro_rules! log_trace {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::trace!($($arg)*);
+    }};
+}
+
 pub mod geometry;
+pub mod io;
+pub mod nesting;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod trace;
 mod tests;
 
 
 #[no_mangle]
 pub extern fn test_link() {
-    println!("Hello world, I am running from a rust library")
+    log_debug!("Hello world, I am running from a rust library");
 }
 
 pub mod lib {
 
-    use super::geometry::{Point, Polygon, LineSegment, Line, Vector};
+    use super::geometry::{Point, Polygon, LineSegment, Line, Vector, Distance, DecompositionMode, BoundingBox};
+    use super::trace::{NfpTrace, NfpTraceStep};
     use std::f64::consts::{PI};
 
     /// Calculates the nfp of two convex polygon
-    /// WARNING: For performance we do NO error checking what so ever, so take care that the
-    /// input polygon are valid and both convex!
+    /// The reference point of other is its coordinate origin, so other translated by any point
+    /// on the nfp boundary touches first, and overlaps it for any point inside
+    ///
+    /// Both inputs may run in either orientation, the nfp is always counter-clockwise. It starts at the lowest,
+    /// leftmost corner of first minus the highest, rightmost corner of other, where other sits right below first.
+    /// Edges of zero length, e.g. from repeated corners, are skipped
+    ///
+    /// WARNING: For performance convexity is not checked, so take care that both input polygon are convex!
     /// 
     /// # Examples
     /// 
@@ -48,39 +78,348 @@ pub mod lib {
     /// assert!(nfp.is_convex());
     /// ```
     /// 
-    pub fn calculate_convex_nfp(first: &Polygon, mut other: Polygon) -> Polygon {
+    pub fn calculate_convex_nfp(first: &Polygon, other: Polygon) -> Polygon {
+        convex_nfp(first, other, None)
+    }
+
+    // public convex nfp recording every step into the trace, see `calculate_convex_nfp`
+    pub fn calculate_convex_nfp_traced(first: &Polygon, other: Polygon, trace: &mut NfpTrace) -> Polygon {
+        convex_nfp(first, other, Some(trace))
+    }
+
+    // private convex nfp, steps are only built when a trace is given
+    fn convex_nfp(first: &Polygon, mut other: Polygon, mut trace: Option<&mut NfpTrace>) -> Polygon {
 
         // helper line -> x-axis to compare angles to
         let x_axis = LineSegment::new_from_points(&Point::new(), &Point::new().set_values(1.0, 0.0));
 
+        // both polygons have to be counter-clockwise for the edge angles to be sorted correctly
+        let mut first = first.clone();
+        if first.calculate_signed_area() < 0.0 {
+            first.reverse_orientation();
+        }
+        if other.calculate_signed_area() < 0.0 {
+            other.reverse_orientation();
+        }
+
+        // the nfp starts at the lowest corner of first minus the highest corner of other,
+        // which puts the reference point of other at its coordinate origin
+        let (start, contact) = match (lowest_corner(&first.points), lowest_corner(&other.points.iter().map(|p| Point::new().set_values(-p.x, -p.y)).collect::<Vec<_>>())) {
+            (Some(f), Some(o)) => (Point::new().set_values(f.x + o.x, f.y + o.y), f),
+            _ => return Polygon::new()
+        };
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record(NfpTraceStep::ConvexStart{first: first.clone(), other: other.clone(), start});
+            // other lies below the lowest corner of first and left of it on equal height, so they only share that corner
+            trace.record(NfpTraceStep::TouchingPoints{reference: start, contacts: vec![contact]});
+        }
+
         // reverse orientation of other
         other.reverse_orientation();
 
-        // get all edges of both polygons
+        // get all edges of both polygons, degenerate edges have no angle
         let mut all_edges = first.calculate_edges();
         all_edges.extend(other.calculate_edges());
-
-        // create sorted list, first item is first edge popped
-        let mut edges_sorted: Vec<LineSegment> = vec![all_edges.remove(0)];
+        all_edges.retain(|e| e.calculate_length() > 0.0);
 
         // sort by angle to x_axis
-        all_edges.sort_by(|a, b| (x_axis.angle_to(&b)).partial_cmp(&(x_axis.angle_to(&a))).unwrap());
-
-        // iteratively sort by `flattest` angle between line segments and pop
-        while all_edges.len() > 0 {
-            let last_edge = edges_sorted.last().unwrap();
-            let all_angles_x_to_pt = all_edges.iter().map(|x| x_axis.angle_to(&x)).collect::<Vec<_>>();
-            let all_angles_pt_to_x = all_edges.iter().map(|x| x.angle_to(&x_axis)).collect::<Vec<_>>();
-            // all_edges.reverse();
-            println!("angles from x to pt: {:?}", all_angles_x_to_pt);
-            println!("angles from pt to x: {:?}", all_angles_pt_to_x);
-            let next_edge = all_edges.pop().unwrap();
-            println!("next_edge is: {:?}", next_edge);
-            let next_edge_moved = next_edge.copy_to_point(&last_edge.to);
+        all_edges.sort_by(|a, b| (x_axis.angle_to(a)).partial_cmp(&(x_axis.angle_to(b))).unwrap());
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record(NfpTraceStep::SortedEdges{edges: all_edges.clone()});
+        }
+
+        // chain the sorted edges, starting at the start corner
+        let mut edges_sorted: Vec<LineSegment> = Vec::with_capacity(all_edges.len());
+        let mut last_to = start;
+        log_debug!(first_corners = first.points.len(), other_corners = other.points.len(), edges = all_edges.len(); "convex nfp");
+        for next_edge in all_edges {
+            log_trace!(from:? = next_edge.from, to:? = next_edge.to; "convex nfp edge");
+            let translation = Vector::new_from_points(&next_edge.from, &last_to);
+            let next_edge_moved = next_edge.copy_to_point(&last_to);
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.record(NfpTraceStep::EdgeTranslated{edge: next_edge_moved.clone(), translation});
+            }
+            last_to = next_edge_moved.to;
             edges_sorted.push(next_edge_moved);
         }
 
-        Polygon::from_edges(&edges_sorted)
-        
+        let nfp = Polygon::from_edges(&edges_sorted);
+        if let Some(trace) = trace {
+            trace.record(NfpTraceStep::ConvexResult{nfp: nfp.clone()});
+        }
+        nfp
+    }
+
+    // possible results of a penetration query
+    #[derive(Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub enum PenetrationResult {
+        None, // polygons are separated or only touching
+        Overlap(f64, Vector) // penetration depth and the minimal translation vector moving other out of first
+    }
+
+    /// Penetration depth and minimal translation vector of two overlapping polygon
+    /// Convex pairs are resolved by a separating axis test, all others through the nfp of their convex pieces,
+    /// where the minimal translation vector points from the origin to the closest point on the nfp boundary
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::lib::{calculate_penetration, PenetrationResult};
+    /// use rust_nfp::geometry::{Polygon, Vector};
+    /// 
+    /// let tol = 0.0001; // geometric tolerance
+    /// 
+    /// let square = Polygon::square(2.0);
+    /// let moved = square.copy_along_vector(&Vector::new().set_values(1.5, 0.2));
+    /// 
+    /// match calculate_penetration(&square, &moved, tol) {
+    ///     PenetrationResult::None => panic!("Expected overlap!"),
+    ///     PenetrationResult::Overlap(depth, translation) => {
+    ///         assert!((depth - 0.5).abs() < tol);
+    ///         assert!(translation.epsilon_equals(&Vector::new().set_values(0.5, 0.0), tol));
+    ///     }
+    /// }
+    /// ```
+    pub fn calculate_penetration(first: &Polygon, other: &Polygon, tol: f64) -> PenetrationResult {
+        penetration(first, other, tol, None)
+    }
+
+    // public penetration recording the nfp of non-convex pairs and the result into the trace, see `calculate_penetration`
+    pub fn calculate_penetration_traced(first: &Polygon, other: &Polygon, tol: f64, trace: &mut NfpTrace) -> PenetrationResult {
+        penetration(first, other, tol, Some(trace))
+    }
+
+    // private penetration, steps are only built when a trace is given
+    fn penetration(first: &Polygon, other: &Polygon, tol: f64, mut trace: Option<&mut NfpTrace>) -> PenetrationResult {
+        let result = if first.is_convex() && other.is_convex() {
+            separating_axis_penetration(first, other, tol)
+        } else {
+            nfp_penetration(first, other, tol, trace.as_deref_mut())
+        };
+
+        if let Some(trace) = trace {
+            let (depth, translation) = match result {
+                PenetrationResult::None => (0.0, Vector::new()),
+                PenetrationResult::Overlap(depth, translation) => (depth, translation)
+            };
+            trace.record(NfpTraceStep::Penetration{depth, translation});
+        }
+        result
+    }
+
+    // private separating axis test, the axis of least overlap gives the minimal translation
+    fn separating_axis_penetration(first: &Polygon, other: &Polygon, tol: f64) -> PenetrationResult {
+        let project = |poly: &Polygon, axis: &Vector| poly.points.iter()
+            .map(|p| p.x * axis.x + p.y * axis.y)
+            .fold((f64::MAX, f64::MIN), |(min, max), d| (min.min(d), max.max(d)));
+
+        let mut best_depth = f64::MAX;
+        let mut best_axis = Vector::new();
+
+        for edge in first.calculate_edges().iter().chain(other.calculate_edges().iter()) {
+            if edge.calculate_length() == 0.0 {
+                continue;
+            }
+
+            let axis = Vector::new().set_values(-edge.direction.y, edge.direction.x).as_normalized();
+            let (first_min, first_max) = project(first, &axis);
+            let (other_min, other_max) = project(other, &axis);
+
+            // moving other along the axis, or against it, until the projections are disjoint
+            let push_along = first_max - other_min;
+            let push_against = other_max - first_min;
+            if push_along <= tol || push_against <= tol {
+                return PenetrationResult::None;
+            }
+
+            if push_along < best_depth {
+                best_depth = push_along;
+                best_axis = axis;
+            }
+            if push_against < best_depth {
+                best_depth = push_against;
+                best_axis = Vector::new().set_values(-axis.x, -axis.y);
+            }
+        }
+
+        if best_depth == f64::MAX {
+            return PenetrationResult::None;
+        }
+
+        PenetrationResult::Overlap(best_depth, Vector::new().set_values(best_axis.x * best_depth, best_axis.y * best_depth))
+    }
+
+    /// Calculates the nfp of two arbitrary simple polygon as the convex nfps of all pairs of their convex pieces
+    /// The union of the returned polygon is the nfp, so other translated by a point inside any of them overlaps first
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::lib::{calculate_nfp, is_inside_nfp};
+    /// use rust_nfp::geometry::{Polygon, Point};
+    /// 
+    /// let tol = 0.0001; // geometric tolerance
+    /// 
+    /// // L-shape and a small square
+    /// let l_shape = Polygon::from_points(&vec![
+    ///     Point::new(), Point::new().set_values(4.0, 0.0), Point::new().set_values(4.0, 1.0),
+    ///     Point::new().set_values(1.0, 1.0), Point::new().set_values(1.0, 4.0), Point::new().set_values(0.0, 4.0)]);
+    /// let square = Polygon::square(1.0);
+    /// 
+    /// let nfp = calculate_nfp(&l_shape, &square, tol);
+    /// assert_eq!(nfp.len(), 2);
+    /// assert!(is_inside_nfp(&Point::new().set_values(2.0, 0.5), &nfp, tol));
+    /// assert!(!is_inside_nfp(&Point::new().set_values(2.0, 2.0), &nfp, tol));
+    /// ```
+    pub fn calculate_nfp(first: &Polygon, other: &Polygon, tol: f64) -> Vec<Polygon> {
+        nfp_of_pieces(first, other, tol, None)
+    }
+
+    // public nfp recording the decomposition and every convex pair into the trace, see `calculate_nfp`
+    pub fn calculate_nfp_traced(first: &Polygon, other: &Polygon, tol: f64, trace: &mut NfpTrace) -> Vec<Polygon> {
+        nfp_of_pieces(first, other, tol, Some(trace))
+    }
+
+    // private nfp of all convex piece pairs, steps are only built when a trace is given
+    fn nfp_of_pieces(first: &Polygon, other: &Polygon, tol: f64, mut trace: Option<&mut NfpTrace>) -> Vec<Polygon> {
+        let other_pieces = convex_pieces(other, tol);
+        let first_pieces = convex_pieces(first, tol);
+        log_debug!(first_pieces = first_pieces.len(), other_pieces = other_pieces.len(); "nfp of convex pieces");
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.record(NfpTraceStep::ConvexPieces{first: first_pieces.clone(), other: other_pieces.clone()});
+        }
+
+        let mut nfps = Vec::with_capacity(first_pieces.len() * other_pieces.len());
+        for (first_index, f) in first_pieces.iter().enumerate() {
+            for (other_index, o) in other_pieces.iter().enumerate() {
+                if let Some(trace) = trace.as_deref_mut() {
+                    trace.record(NfpTraceStep::PiecePair{first_index, other_index});
+                }
+
+                let nfp = convex_nfp(f, o.clone(), trace.as_deref_mut());
+                if nfp.points.len() >= 3 {
+                    nfps.push(nfp);
+                } else if let Some(trace) = trace.as_deref_mut() {
+                    trace.record(NfpTraceStep::PieceDropped{first_index, other_index});
+                }
+            }
+        }
+
+        if let Some(trace) = trace {
+            trace.record(NfpTraceStep::NfpResult{nfp: nfps.clone()});
+        }
+        nfps
+    }
+
+    /// Calculates the inner fit polygon of other inside the bounding rectangle of container
+    /// Other translated by any point of the returned rectangle lies inside the bounding rectangle, which is exact
+    /// for axis aligned rectangular containers. None if other does not fit in any position
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_nfp::lib::calculate_rectangular_ifp;
+    /// use rust_nfp::geometry::Polygon;
+    /// 
+    /// let tol = 0.0001; // geometric tolerance
+    /// 
+    /// let ifp = calculate_rectangular_ifp(&Polygon::square(4.0), &Polygon::square(1.0), tol).unwrap();
+    /// assert_eq!(ifp.width(), 3.0);
+    /// assert_eq!(ifp.min.x, -1.5);
+    /// assert!(calculate_rectangular_ifp(&Polygon::square(1.0), &Polygon::square(4.0), tol).is_none());
+    /// ```
+    pub fn calculate_rectangular_ifp(container: &Polygon, other: &Polygon, tol: f64) -> Option<BoundingBox> {
+        let container_box = container.bounding_box();
+        let other_box = other.bounding_box();
+        if container_box.is_empty() || other_box.is_empty() {
+            return None;
+        }
+
+        let min = Point::new().set_values(container_box.min.x - other_box.min.x, container_box.min.y - other_box.min.y);
+        let max = Point::new().set_values(container_box.max.x - other_box.max.x, container_box.max.y - other_box.max.y);
+        if max.x - min.x < -tol || max.y - min.y < -tol {
+            return None;
+        }
+
+        // exact fits within the tolerance collapse to a line or a single point
+        Some(BoundingBox{min, max: Point::new().set_values(max.x.max(min.x), max.y.max(min.y))})
+    }
+
+    // public test for a point strictly inside the union of the nfp pieces, points on the boundary are outside
+    pub fn is_inside_nfp(pt: &Point, nfp: &[Polygon], tol: f64) -> bool {
+        is_on_union_interior(pt, nfp, tol)
+    }
+
+    // private nfp based penetration, the nfp is the union of the nfps of all convex piece pairs
+    fn nfp_penetration(first: &Polygon, other: &Polygon, tol: f64, trace: Option<&mut NfpTrace>) -> PenetrationResult {
+        let origin = Point::new();
+
+        let nfps = nfp_of_pieces(first, other, tol, trace);
+
+        // origin has to be strictly inside the union
+        if !is_on_union_interior(&origin, &nfps, tol) {
+            return PenetrationResult::None;
+        }
+
+        // candidates on the union boundary: closest points of all edges, corners and edge crossings
+        let edges = nfps.iter().map(|nfp| nfp.calculate_edges()).collect::<Vec<_>>();
+        let mut candidates: Vec<Point> = Vec::new();
+        for (i, nfp_edges) in edges.iter().enumerate() {
+            for edge in nfp_edges.iter() {
+                candidates.push(edge.closest_point(&origin));
+                candidates.push(edge.from);
+                for other_edges in edges.iter().skip(i + 1) {
+                    for other_edge in other_edges.iter() {
+                        let closest = Distance::line_segment_line_segment(edge, other_edge, tol);
+                        if closest.distance == 0.0 {
+                            candidates.push(closest.first);
+                        }
+                    }
+                }
+            }
+        }
+
+        candidates.into_iter()
+            .filter(|pt| !is_on_union_interior(pt, &nfps, tol))
+            .min_by(|a, b| a.distance_to_squared(&origin).partial_cmp(&b.distance_to_squared(&origin)).unwrap())
+            .map_or(PenetrationResult::None, |pt| PenetrationResult::Overlap(pt.distance_to(&origin), Vector::new_from_points(&origin, &pt)))
+    }
+
+    // private helper testing if a point lies inside a union of polygons, probing its neighbourhood
+    fn is_on_union_interior(pt: &Point, polys: &[Polygon], tol: f64) -> bool {
+        let probe_count = 16;
+        let probe_distance = 10.0 * tol;
+        (0..probe_count).all(|n| {
+            let probe = pt.copy_along_vector(&Vector::new().set_values(
+                probe_distance * (n as f64 * 2.0 * PI / probe_count as f64).cos(),
+                probe_distance * (n as f64 * 2.0 * PI / probe_count as f64).sin()));
+            polys.iter().any(|poly| poly.contains_point(&probe, 0.0))
+        })
+    }
+
+    // private helper splitting a polygon into counter-clockwise convex pieces
+    fn convex_pieces(poly: &Polygon, tol: f64) -> Vec<Polygon> {
+        let mut poly = poly.clone();
+        if poly.calculate_signed_area() < 0.0 {
+            poly.reverse_orientation();
+        }
+
+        if poly.is_convex() {
+            return vec![poly];
+        }
+
+        // a polygon that cannot be decomposed is self-intersecting, keep it as it is
+        match poly.decompose_convex(DecompositionMode::HertelMehlhorn, tol) {
+            Ok(pieces) => pieces,
+            Err(_) => vec![poly]
+        }
+    }
+
+    // private helper finding the lowest corner, the leftmost one of those on equal height
+    fn lowest_corner(pts: &[Point]) -> Option<Point> {
+        pts.iter()
+            .min_by(|a, b| a.y.partial_cmp(&b.y).unwrap().then(a.x.partial_cmp(&b.x).unwrap()))
+            .cloned()
     }
 }
@@ -0,0 +1,377 @@
+// command line front end of the library, reads and writes json, geojson, wkt, svg and dxf files
+
+use rust_nfp::geometry::PolygonWithHoles;
+use rust_nfp::io::{SvgReader, SvgWriter, SvgStyles, DxfReader, DxfWriter, Wkt, GeoJson};
+use rust_nfp::lib::{calculate_nfp, calculate_nfp_traced};
+use rust_nfp::nesting::{Layout, NestingJob, Nester};
+use rust_nfp::trace::NfpTrace;
+use std::path::Path;
+use std::process::exit;
+
+const USAGE: &str = "usage: rust_nfp <command> [options]
+
+commands:
+    nfp <first> <other> -o <output>    nfp of the first polygon of each file, the reference point of other is its origin
+    nest <job.json> -o <output>        nest a job, writes the layout as json, svg or dxf
+    validate <input>...                report problems of all polygons in the input files
+    render <input> -o <output>         draw polygons, a job or a layout as svg or dxf
+
+options:
+    -o, --output <file>    output file, the format follows the extension
+    -t, --tol <value>      geometric tolerance, default 0.0001
+    --trace <file>         nfp only, record how the nfp is built as json or as an animated svg
+    --strip <width>        nest only, pack the parts on a strip of this width with minimal length
+    -h, --help             print this help
+
+formats: .json, .geojson, .wkt, .svg, .dxf
+polygon json is a polygon with holes or a list of them, see `NestingJob` for the job schema";
+
+struct Arguments {
+    command: String,
+    inputs: Vec<String>,
+    output: Option<String>,
+    trace: Option<String>,
+    strip: Option<f64>,
+    tol: f64
+}
+
+fn main() {
+    let args = match parse_arguments(std::env::args().skip(1).collect()) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            exit(2);
+        }
+    };
+
+    let result = match args.command.as_str() {
+        "nfp" => run_nfp(&args),
+        "nest" => run_nest(&args),
+        "validate" => run_validate(&args),
+        "render" => run_render(&args),
+        _ => Err(format!("unknown command '{}'", args.command))
+    };
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => exit(1),
+        Err(message) => {
+            eprintln!("error: {}", message);
+            exit(1);
+        }
+    }
+}
+
+fn parse_arguments(raw: Vec<String>) -> Result<Arguments, String> {
+    let mut args = Arguments{command: String::new(), inputs: Vec::new(), output: None, trace: None, strip: None, tol: 0.0001};
+
+    let mut iter = raw.into_iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0);
+            },
+            "-o" | "--output" => args.output = Some(iter.next().ok_or("missing output file")?),
+            "--trace" => args.trace = Some(iter.next().ok_or("missing trace file")?),
+            "--strip" => {
+                args.strip = Some(iter.next()
+                    .and_then(|w| w.parse::<f64>().ok())
+                    .filter(|w| *w > 0.0)
+                    .ok_or("strip width has to be a positive number")?);
+            },
+            "-t" | "--tol" => {
+                args.tol = iter.next()
+                    .and_then(|t| t.parse::<f64>().ok())
+                    .filter(|t| *t > 0.0)
+                    .ok_or("tolerance has to be a positive number")?;
+            },
+            _ if arg.starts_with('-') && arg.len() > 1 => return Err(format!("unknown option '{}'", arg)),
+            _ if args.command.is_empty() => args.command = arg,
+            _ => args.inputs.push(arg)
+        }
+    }
+
+    if args.command.is_empty() {
+        return Err("missing command".to_string());
+    }
+    Ok(args)
+}
+
+fn extension(path: &str) -> String {
+    Path::new(path).extension().map(|e| e.to_string_lossy().to_ascii_lowercase()).unwrap_or_default()
+}
+
+fn read_text(path: &str) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn write_text(path: &str, text: &str) -> Result<(), String> {
+    std::fs::write(path, text).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn output_path(args: &Arguments) -> Result<&str, String> {
+    args.output.as_deref().ok_or_else(|| format!("'{}' needs an output file", args.command))
+}
+
+fn input_paths(args: &Arguments, count: usize) -> Result<&[String], String> {
+    if args.inputs.len() != count {
+        return Err(format!("'{}' expects {} input file(s), got {}", args.command, count, args.inputs.len()));
+    }
+    Ok(&args.inputs)
+}
+
+// all polygons of a file in any supported format
+fn read_polygons(path: &str, tol: f64) -> Result<Vec<PolygonWithHoles>, String> {
+    let polys = match extension(path).as_str() {
+        "json" => {
+            let text = read_text(path)?;
+            serde_json::from_str::<Vec<PolygonWithHoles>>(&text)
+                .or_else(|_| serde_json::from_str::<PolygonWithHoles>(&text).map(|p| vec![p]))
+                .map_err(|e| format!("{}: {}", path, e))?
+        },
+        "geojson" => GeoJson::read_geometry(&read_text(path)?).map_err(|e| format!("{}: {:?}", path, e))?,
+        "wkt" => Wkt::read(&read_text(path)?).map_err(|e| format!("{}: {:?}", path, e))?,
+        "svg" => SvgReader::read_file(path, tol).map_err(|e| format!("{}: {:?}", path, e))?,
+        "dxf" => DxfReader::read_file(path, tol).map_err(|e| format!("{}: {:?}", path, e))?,
+        ext => return Err(format!("{}: unsupported input format '{}'", path, ext))
+    };
+
+    if polys.is_empty() {
+        return Err(format!("{}: no polygons found", path));
+    }
+    Ok(polys)
+}
+
+fn write_polygons(path: &str, polys: &[PolygonWithHoles]) -> Result<(), String> {
+    match extension(path).as_str() {
+        "json" => write_text(path, &serde_json::to_string_pretty(polys).map_err(|e| e.to_string())?),
+        "geojson" => write_text(path, &GeoJson::write_geometry(polys)),
+        "wkt" => write_text(path, &Wkt::write_multi_polygon(polys)),
+        "svg" => {
+            let mut writer = SvgWriter::new(SvgStyles::default());
+            writer.flip_y = true;
+            let style = writer.styles.part.clone();
+            for poly in polys.iter() {
+                writer.add_polygon_with_holes(poly, &style);
+            }
+            write_text(path, &writer.render())
+        },
+        "dxf" => {
+            let mut writer = DxfWriter::new();
+            for poly in polys.iter() {
+                writer.add_polygon_with_holes(poly, "0");
+            }
+            write_text(path, &writer.render())
+        },
+        ext => Err(format!("{}: unsupported output format '{}'", path, ext))
+    }
+}
+
+fn write_layout(path: &str, layout: &Layout) -> Result<(), String> {
+    match extension(path).as_str() {
+        "json" => write_text(path, &serde_json::to_string_pretty(layout).map_err(|e| e.to_string())?),
+        "svg" => {
+            let mut writer = SvgWriter::new(SvgStyles::default());
+            writer.flip_y = true;
+            writer.add_layout(layout);
+            write_text(path, &writer.render())
+        },
+        "dxf" => {
+            let mut writer = DxfWriter::new();
+            writer.add_layout(layout);
+            write_text(path, &writer.render())
+        },
+        ext => Err(format!("{}: unsupported layout format '{}'", path, ext))
+    }
+}
+
+fn write_trace(path: &str, trace: &NfpTrace) -> Result<(), String> {
+    match extension(path).as_str() {
+        "json" => write_text(path, &trace.to_json()),
+        "svg" => {
+            let mut writer = SvgWriter::new(SvgStyles::default());
+            writer.flip_y = true;
+            writer.add_nfp_trace_animation(trace, 0.5);
+            write_text(path, &writer.render())
+        },
+        ext => Err(format!("{}: unsupported trace format '{}'", path, ext))
+    }
+}
+
+fn run_nfp(args: &Arguments) -> Result<bool, String> {
+    let inputs = input_paths(args, 2)?;
+    let output = output_path(args)?;
+
+    // only the outer boundaries are used, positions inside holes of first are not part of this nfp
+    let first = read_polygons(&inputs[0], args.tol)?.swap_remove(0).outer;
+    let other = read_polygons(&inputs[1], args.tol)?.swap_remove(0).outer;
+    let nfp = match args.trace.as_deref() {
+        Some(path) => {
+            let mut trace = NfpTrace::new();
+            let nfp = calculate_nfp_traced(&first, &other, args.tol, &mut trace);
+            write_trace(path, &trace)?;
+            nfp
+        },
+        None => calculate_nfp(&first, &other, args.tol)
+    };
+
+    if extension(output) == "svg" {
+        let mut writer = SvgWriter::new(SvgStyles::default());
+        writer.flip_y = true;
+        let styles = writer.styles.clone();
+        writer.add_polygon(&first, &styles.part);
+        for piece in nfp.iter() {
+            writer.add_polygon(piece, &styles.nfp);
+        }
+        write_text(output, &writer.render())?;
+    } else {
+        write_polygons(output, &nfp.into_iter().map(PolygonWithHoles::from_outer).collect::<Vec<_>>())?;
+    }
+
+    Ok(true)
+}
+
+fn run_nest(args: &Arguments) -> Result<bool, String> {
+    let inputs = input_paths(args, 1)?;
+    let output = output_path(args)?;
+
+    let job: NestingJob = serde_json::from_str(&read_text(&inputs[0])?).map_err(|e| format!("{}: {}", inputs[0], e))?;
+    if let Some(width) = args.strip {
+        return run_nest_strip(&job, width, output, args.tol);
+    }
+
+    let layout = Nester::nest(&job, args.tol);
+    write_layout(output, &layout)?;
+
+    println!("placed {} of {} parts", layout.placements.len(), job.part_count());
+    for (index, sheet) in layout.sheets.iter().enumerate() {
+        println!("  {}: {} parts, {:.1}% utilization",
+            sheet.name, layout.placements_on_sheet(index).count(), 100.0 * layout.calculate_utilization(index));
+    }
+    for name in layout.unplaced.iter() {
+        println!("  not placed: {}", name);
+    }
+    if !job.bins.is_empty() {
+        println!("material cost: {}", layout.calculate_cost());
+    }
+
+    Ok(layout.unplaced.is_empty())
+}
+
+fn run_nest_strip(job: &NestingJob, width: f64, output: &str, tol: f64) -> Result<bool, String> {
+    let strip = Nester::nest_strip(job, width, tol);
+    write_layout(output, &strip.layout)?;
+
+    println!("placed {} of {} parts", strip.layout.placements.len(), job.part_count());
+    println!("  strip {} x {}, {:.1}% utilization", strip.width, strip.length, 100.0 * strip.utilization);
+    for name in strip.layout.unplaced.iter() {
+        println!("  not placed: {}", name);
+    }
+
+    Ok(strip.layout.unplaced.is_empty())
+}
+
+fn run_validate(args: &Arguments) -> Result<bool, String> {
+    if args.inputs.is_empty() {
+        return Err("'validate' expects at least one input file".to_string());
+    }
+
+    let mut valid = true;
+    for path in args.inputs.iter() {
+        for (index, poly) in read_polygons(path, args.tol)?.iter().enumerate() {
+            for issue in poly.validate(args.tol) {
+                println!("{}: polygon {}: {:?}", path, index, issue);
+                valid = false;
+            }
+        }
+    }
+
+    if valid {
+        println!("no problems found");
+    }
+    Ok(valid)
+}
+
+fn run_render(args: &Arguments) -> Result<bool, String> {
+    let inputs = input_paths(args, 1)?;
+    let output = output_path(args)?;
+
+    // json inputs may hold a layout or a job, which is drawn as its sheets and parts
+    if extension(&inputs[0]) == "json" {
+        let text = read_text(&inputs[0])?;
+        if let Ok(layout) = serde_json::from_str::<Layout>(&text) {
+            return write_layout(output, &layout).map(|_| true);
+        }
+        if let Ok(job) = serde_json::from_str::<NestingJob>(&text) {
+            let polys = job.sheets.iter().map(|s| s.shape.clone())
+                .chain(job.parts.iter().map(|p| p.shape.clone()))
+                .collect::<Vec<_>>();
+            return write_polygons(output, &polys).map(|_| true);
+        }
+    }
+
+    write_polygons(output, &read_polygons(&inputs[0], args.tol)?)?;
+    Ok(true)
+}
+
+
+#[cfg(test)]
+mod cli_tests {
+    use super::{parse_arguments, read_polygons, write_polygons};
+    use rust_nfp::geometry::{Polygon, PolygonWithHoles, Transform};
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_arguments() {
+        // Act
+        let args = parse_arguments(strings(&["nest", "job.json", "-o", "out.svg", "--strip", "10", "-t", "0.01"])).unwrap();
+
+        // Assert
+        assert_eq!(args.command, "nest");
+        assert_eq!(args.inputs, vec!["job.json".to_string()]);
+        assert_eq!(args.output, Some("out.svg".to_string()));
+        assert_eq!(args.strip, Some(10.0));
+        assert_eq!(args.trace, None);
+        assert!((args.tol - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_arguments_errors() {
+        // Assert
+        assert!(parse_arguments(Vec::new()).is_err());
+        assert!(parse_arguments(strings(&["nfp", "-o"])).is_err());
+        assert!(parse_arguments(strings(&["nfp", "--unknown"])).is_err());
+        assert!(parse_arguments(strings(&["nest", "--strip", "-1"])).is_err());
+        assert!(parse_arguments(strings(&["nfp", "-t", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_polygon_file_round_trip() {
+        // Arrange
+        let mut hole = Polygon::square(1.0);
+        hole.reverse_orientation();
+        let polys = vec![PolygonWithHoles::new(Polygon::square(4.0), vec![hole]), PolygonWithHoles::from_outer(Polygon::square(2.0).transform(&Transform::translation(6.0, 0.0)))];
+        let dir = std::env::temp_dir().join(format!("rust_nfp_cli_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        for ext in ["json", "geojson", "wkt", "svg", "dxf"].iter() {
+            let path = dir.join(format!("polygons.{}", ext)).to_string_lossy().to_string();
+
+            // Act
+            write_polygons(&path, &polys).unwrap();
+            let read = read_polygons(&path, 0.0001).unwrap();
+
+            // Assert
+            assert_eq!(read.len(), 2, "{}", ext);
+            let area = read.iter().map(|p| p.calculate_area()).sum::<f64>();
+            assert!((area - 19.0).abs() < 0.0001, "{} area {}", ext, area);
+        }
+
+        assert!(write_polygons(&dir.join("polygons.txt").to_string_lossy(), &polys).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
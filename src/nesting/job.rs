@@ -0,0 +1,144 @@
+use super::super::geometry::PolygonWithHoles;
+use super::Sheet;
+
+/// Part to be nested with the number of copies and the rotations each copy may be placed in
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Part {
+    pub name: String,
+    pub shape: PolygonWithHoles,
+    #[cfg_attr(feature = "serde", serde(default = "default_quantity"))]
+    pub quantity: usize,
+    #[cfg_attr(feature = "serde", serde(default = "default_rotations"))]
+    pub rotations: Vec<f64>, // allowed counter-clockwise rotations, in radians
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grade: u32 // lowest material quality the part accepts, 0 allows every graded defect zone
+}
+
+#[cfg(feature = "serde")]
+fn default_quantity() -> usize {
+    1
+}
+
+#[cfg(feature = "serde")]
+fn default_rotations() -> Vec<f64> {
+    vec![0.0]
+}
+
+impl Part {
+    // public constructor, copies may only be placed unrotated
+    pub fn new(name: &str, shape: PolygonWithHoles, quantity: usize) -> Part {
+        Part{name: name.to_string(), shape, quantity, rotations: vec![0.0], grade: 0}
+    }
+
+    /// Allows the given number of evenly spaced rotations, starting at 0.0
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// use rust_nfp::nesting::Part;
+    ///
+    /// let part = Part::new("square", PolygonWithHoles::from_outer(Polygon::square(2.0)), 3).set_rotation_steps(4);
+    /// assert_eq!(part.rotations.len(), 4);
+    /// assert_eq!(part.rotations[2], std::f64::consts::PI);
+    /// ```
+    pub fn set_rotation_steps(mut self, steps: usize) -> Part {
+        let steps = steps.max(1);
+        self.rotations = (0..steps).map(|i| i as f64 * 2.0 * std::f64::consts::PI / steps as f64).collect();
+        self
+    }
+
+    pub fn set_grade(mut self, grade: u32) -> Part {
+        self.grade = grade;
+        self
+    }
+}
+
+/// Stock of sheets that are opened on demand when the parts do not fit on the sheets already in use
+/// Remnants are bins with a quantity of 1, usually at a lower cost
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bin {
+    pub name: String,
+    pub shape: PolygonWithHoles,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cost: f64, // cost of every opened sheet
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quantity: Option<usize> // sheets in stock, None for an unlimited supply
+}
+
+impl Bin {
+    // public constructor for an unlimited supply
+    pub fn new(name: &str, shape: PolygonWithHoles, cost: f64) -> Bin {
+        Bin{name: name.to_string(), shape, cost, quantity: None}
+    }
+
+    pub fn set_quantity(mut self, quantity: usize) -> Bin {
+        self.quantity = Some(quantity);
+        self
+    }
+
+    // public n-th sheet opened from this stock, counting from 1, named `<bin> #<number>`
+    pub fn open_sheet(&self, number: usize) -> Sheet {
+        Sheet::new(&format!("{} #{}", self.name, number), self.shape.clone()).set_cost(self.cost)
+    }
+}
+
+/// Input of a nesting run, the available sheets and the parts to place on them
+/// Parts go on the given sheets first, further sheets are opened from the bins as needed
+///
+/// With the `serde` feature a job is read from and written to json in the following schema.
+/// Points are objects with `x` and `y` members, polygons list their points counter-clockwise
+/// without repeating the first one, angles are in radians and all lengths share one unit
+///
+/// ```json
+/// {
+///     "sheets": [
+///         {"name": "plate", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 100, "y": 0}, {"x": 100, "y": 50}, {"x": 0, "y": 50}]},
+///                                     "holes": []}},
+///         {"name": "hide", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 80, "y": 10}, {"x": 90, "y": 60}, {"x": 10, "y": 70}]}},
+///          "defects": [{"shape": {"points": [{"x": 40, "y": 30}, {"x": 45, "y": 30}, {"x": 45, "y": 34}]}},
+///                      {"shape": {"points": [{"x": 0, "y": 0}, {"x": 20, "y": 2}, {"x": 5, "y": 20}]}, "grade": 1}]}
+///     ],
+///     "bins": [
+///         {"name": "stock 2x1", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 200, "y": 0}, {"x": 200, "y": 100}, {"x": 0, "y": 100}]}},
+///          "cost": 40.0, "quantity": 10}
+///     ],
+///     "parts": [
+///         {"name": "bracket", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 10, "y": 0}, {"x": 0, "y": 10}]}},
+///          "quantity": 4, "rotations": [0.0, 3.141592653589793], "grade": 2}
+///     ],
+///     "spacing": 1.5,
+///     "part_in_part": true
+/// }
+/// ```
+///
+/// `holes` defaults to no holes, `bins` to none, sheet and bin `cost` to 0.0, sheet `defects` to none, defect
+/// `grade` to a zone forbidden for all parts, bin `quantity` to an unlimited supply, part `quantity` to 1,
+/// `rotations` to `[0.0]`, part `grade` to 0, `spacing` to 0.0 and `part_in_part` to false.
+/// Layouts, placements and all geometry types serialize with the same field names as their rust definitions
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NestingJob {
+    pub sheets: Vec<Sheet>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bins: Vec<Bin>,
+    pub parts: Vec<Part>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spacing: f64, // minimal distance between placed parts
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub part_in_part: bool // place parts into the holes of already placed parts first
+}
+
+impl NestingJob {
+    // public constructor without spacing between parts
+    pub fn new(sheets: Vec<Sheet>, parts: Vec<Part>) -> NestingJob {
+        NestingJob{sheets, bins: Vec::new(), parts, spacing: 0.0, part_in_part: false}
+    }
+
+    // public number of part copies to place
+    pub fn part_count(&self) -> usize {
+        self.parts.iter().map(|p| p.quantity).sum()
+    }
+}
@@ -0,0 +1,156 @@
+use super::super::geometry::{Polygon, PolygonWithHoles, Vector, Transform};
+
+/// Zone of a sheet with defects or reduced quality, like a scar on a hide or a scratch on a remnant plate
+/// Zones without a grade are kept free of all parts, graded zones only of parts that need a better grade
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DefectZone {
+    pub shape: Polygon,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub grade: Option<u32> // quality of the material in the zone, compared to `Part::grade`
+}
+
+impl DefectZone {
+    // public constructor for a zone that is forbidden for every part
+    pub fn new(shape: Polygon) -> DefectZone {
+        DefectZone{shape, grade: None}
+    }
+
+    pub fn set_grade(mut self, grade: u32) -> DefectZone {
+        self.grade = Some(grade);
+        self
+    }
+
+    // public check if a part needing the given grade may be placed on the zone
+    pub fn accepts(&self, grade: u32) -> bool {
+        self.grade.is_some_and(|g| g >= grade)
+    }
+}
+
+/// Stock sheet that parts are nested on
+/// The outline may be any polygon, parts keep off its holes and defect zones
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sheet {
+    pub name: String,
+    pub shape: PolygonWithHoles,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cost: f64, // material cost, counted by `Layout::calculate_cost` once the sheet is used
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub defects: Vec<DefectZone>
+}
+
+impl Sheet {
+    // public constructor from any shape, without cost and defects
+    pub fn new(name: &str, shape: PolygonWithHoles) -> Sheet {
+        Sheet{name: name.to_string(), shape, cost: 0.0, defects: Vec::new()}
+    }
+
+    pub fn set_cost(mut self, cost: f64) -> Sheet {
+        self.cost = cost;
+        self
+    }
+
+    // public rectangular sheet with its lower left corner at the origin
+    pub fn rectangle(name: &str, width: f64, height: f64) -> Sheet {
+        let outline = Polygon::square(1.0)
+            .transform(&Transform::translation(0.5, 0.5).then(&Transform::scale(width, height)));
+        Sheet::new(name, PolygonWithHoles::from_outer(outline))
+    }
+}
+
+/// Single part placed on a sheet
+/// The part outline is rotated around its coordinate origin first and moved by `translation` second
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Placement {
+    pub part: String, // name of the placed part
+    pub sheet: usize, // index into the sheets of the layout
+    pub rotation: f64, // counter-clockwise, in radians
+    pub translation: Vector,
+    pub shape: PolygonWithHoles, // outline in sheet coordinates
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub part_index: Option<usize> // index into the parts of the nested job, None for placements made by hand
+}
+
+impl Placement {
+    // public constructor, places the given part outline
+    pub fn new(part: &str, sheet: usize, outline: &PolygonWithHoles, rotation: f64, translation: &Vector) -> Placement {
+        let transform = Placement::calculate_transform(rotation, translation);
+        Placement{
+            part: part.to_string(),
+            sheet,
+            rotation,
+            translation: *translation,
+            shape: outline.transform(&transform),
+            part_index: None
+        }
+    }
+
+    pub fn set_part_index(mut self, part_index: usize) -> Placement {
+        self.part_index = Some(part_index);
+        self
+    }
+
+    // public transformation from part to sheet coordinates
+    pub fn transform(&self) -> Transform {
+        Placement::calculate_transform(self.rotation, &self.translation)
+    }
+
+    fn calculate_transform(rotation: f64, translation: &Vector) -> Transform {
+        Transform::rotation(rotation).then(&Transform::translation(translation.x, translation.y))
+    }
+}
+
+/// Result of a nesting run, sheets with the parts placed on them
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layout {
+    pub sheets: Vec<Sheet>,
+    pub placements: Vec<Placement>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unplaced: Vec<String> // names of part copies that fit on no sheet
+}
+
+impl Layout {
+    // public constructor from sheets, without any placements
+    pub fn new(sheets: Vec<Sheet>) -> Layout {
+        Layout{sheets, placements: Vec::new(), unplaced: Vec::new()}
+    }
+
+    // public iterator over all placements on the given sheet
+    pub fn placements_on_sheet(&self, sheet: usize) -> impl Iterator<Item = &Placement> {
+        self.placements.iter().filter(move |p| p.sheet == sheet)
+    }
+
+    // public total cost of all sheets with at least one placement
+    pub fn calculate_cost(&self) -> f64 {
+        self.sheets.iter().enumerate()
+            .filter(|(index, _)| self.placements_on_sheet(*index).next().is_some())
+            .map(|(_, sheet)| sheet.cost)
+            .sum()
+    }
+
+    /// Ratio of the placed part area to the sheet area, between 0.0 and 1.0 for valid layouts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles, Vector};
+    /// use rust_nfp::nesting::{Layout, Sheet, Placement};
+    ///
+    /// let mut layout = Layout::new(vec![Sheet::rectangle("sheet", 4.0, 2.0)]);
+    /// let part = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// layout.placements.push(Placement::new("square", 0, &part, 0.0, &Vector::new().set_values(1.0, 1.0)));
+    ///
+    /// assert_eq!(layout.calculate_utilization(0), 0.5);
+    /// ```
+    pub fn calculate_utilization(&self, sheet: usize) -> f64 {
+        let sheet_area = self.sheets[sheet].shape.calculate_area();
+        if sheet_area == 0.0 {
+            return 0.0;
+        }
+
+        self.placements_on_sheet(sheet).map(|p| p.shape.calculate_area()).sum::<f64>() / sheet_area
+    }
+}
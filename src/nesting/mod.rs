@@ -0,0 +1,11 @@
+pub use self::layout::{DefectZone, Sheet, Placement, Layout};
+mod layout;
+
+pub use self::job::{Part, Bin, NestingJob};
+mod job;
+
+pub use self::nester::Nester;
+mod nester;
+
+pub use self::strip::StripLayout;
+mod strip;
@@ -0,0 +1,377 @@
+use super::super::geometry::{Point, Polygon, PolygonWithHoles, Vector, Transform, Path, Distance, BooleanOperation, BoundingBox};
+use super::super::lib::{calculate_nfp, calculate_rectangular_ifp, is_inside_nfp};
+use super::{NestingJob, Layout, Placement, Sheet, Part};
+use std::cmp::Ordering;
+
+pub struct Nester {
+
+}
+
+impl Nester {
+    /// Nests all part copies of the job with a bottom-left fill on the nfps of the already placed parts
+    /// Copies are placed largest first, each on the first sheet with room for it, in the allowed rotation
+    /// that puts it lowest and then leftmost. Copies that fit on no sheet are listed in `Layout::unplaced`
+    /// Parts keep the spacing of the job to each other, to sheet holes and to defect zones, arcs of the spacing
+    /// are tessellated with a chord error of 1% of the spacing
+    ///
+    /// Sheets may have any outline. The feasible positions of a copy are its inner fit rectangle in the sheet minus
+    /// the nfps of everything it must not overlap: the parts of the sheet bounding box outside a concave outline,
+    /// holes, defect zones without a grade, graded zones below the grade of the part and the placed parts
+    ///
+    /// With `NestingJob::part_in_part` the holes of placed parts, shrunk by the spacing, are searched for room
+    /// before any sheet. A copy goes into the first hole it fits, so small parts fill the cutouts of large ones
+    ///
+    /// When a copy fits on no sheet in use, a new sheet is opened from the bins of the job. Every bin with stock
+    /// left is filled on trial with the remaining copies and the one with the lowest cost per placed part area
+    /// is opened. Finally the parts of every opened sheet are moved to the cheapest bin that still holds them all
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// use rust_nfp::nesting::{Nester, NestingJob, Sheet, Part};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let square = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// let job = NestingJob::new(vec![Sheet::rectangle("sheet", 5.0, 2.0)], vec![Part::new("square", square, 3)]);
+    ///
+    /// let layout = Nester::nest(&job, tol);
+    /// assert_eq!(layout.placements.len(), 2);
+    /// assert_eq!(layout.unplaced, vec!["square".to_string()]);
+    /// assert!((layout.placements[1].translation.x - 3.0).abs() < tol);
+    /// ```
+    ///
+    /// Bins of different sizes and costs
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// use rust_nfp::nesting::{Nester, NestingJob, Sheet, Bin, Part};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let square = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// let mut job = NestingJob::new(Vec::new(), vec![Part::new("square", square, 5)]);
+    /// job.bins.push(Bin::new("large", Sheet::rectangle("", 4.0, 4.0).shape, 10.0));
+    /// job.bins.push(Bin::new("small", Sheet::rectangle("", 2.0, 2.0).shape, 3.0));
+    ///
+    /// // one large sheet for four squares, the fifth on a small one
+    /// let layout = Nester::nest(&job, tol);
+    /// assert!(layout.unplaced.is_empty());
+    /// assert_eq!(layout.sheets.len(), 2);
+    /// assert_eq!(layout.calculate_cost(), 13.0);
+    /// ```
+    pub fn nest(job: &NestingJob, tol: f64) -> Layout {
+        let mut layout = Layout::new(job.sheets.clone());
+
+        // grown outlines of placed parts, sheet holes and defects, nothing may be placed on them
+        let mut obstacles: Vec<Vec<Polygon>> = job.sheets.iter()
+            .map(|s| sheet_obstacles(s, job.spacing, tol))
+            .collect();
+        // per sheet, the index of the grown outline in the obstacles and the index in the layout of every placement
+        let mut placed: Vec<Vec<(usize, usize)>> = vec![Vec::new(); job.sheets.len()];
+
+        // bins left in stock and the bin every sheet of the layout was opened from
+        let mut stock: Vec<Option<usize>> = job.bins.iter().map(|b| b.quantity).collect();
+        let mut opened_from: Vec<Option<usize>> = vec![None; job.sheets.len()];
+
+        // part copies as indices into the parts of the job, largest outline first
+        let mut copies = (0..job.parts.len())
+            .flat_map(|index| (0..job.parts[index].quantity).map(move |_| index))
+            .collect::<Vec<_>>();
+        sort_by_outer_area(job, &mut copies);
+
+        for (n, &copy) in copies.iter().enumerate() {
+            let part = &job.parts[copy];
+            let mut found = None;
+            if job.part_in_part {
+                found = (0..layout.sheets.len()).find_map(|index| {
+                    let parents = placed[index].iter().map(|&(obstacle, n)| (obstacle, &layout.placements[n])).collect::<Vec<_>>();
+                    find_placement_in_holes(&layout.sheets[index], &obstacles[index], &parents, part, job.spacing, tol).map(|p| (index, p))
+                });
+            }
+            if found.is_none() {
+                found = (0..layout.sheets.len())
+                    .find_map(|index| find_placement(&layout.sheets[index], &obstacles[index], part, job.spacing, bottom_left, tol).map(|p| (index, p)));
+            }
+
+            if found.is_none() {
+                if let Some(bin) = choose_bin(job, &stock, &copies[n..], tol) {
+                    take(&mut stock, bin);
+                    let sheet = job.bins[bin].open_sheet(opened_count(&opened_from, bin) + 1);
+                    obstacles.push(sheet_obstacles(&sheet, job.spacing, tol));
+                    placed.push(Vec::new());
+                    layout.sheets.push(sheet);
+                    opened_from.push(Some(bin));
+
+                    let index = layout.sheets.len() - 1;
+                    found = find_placement(&layout.sheets[index], &obstacles[index], part, job.spacing, bottom_left, tol).map(|p| (index, p));
+                }
+            }
+
+            match found {
+                Some((index, (rotation, pt))) => {
+                    let placement = Placement::new(&part.name, index, &part.shape, rotation, &Vector::new().set_values(pt.x, pt.y))
+                        .set_part_index(copy);
+                    placed[index].push((obstacles[index].len(), layout.placements.len()));
+                    obstacles[index].push(grow(&placement.shape.outer, job.spacing, tol));
+                    layout.placements.push(placement);
+                },
+                None => layout.unplaced.push(part.name.clone())
+            }
+        }
+
+        downsize(job, &mut layout, &mut opened_from, &mut stock, tol);
+        layout
+    }
+}
+
+fn available(stock: &[Option<usize>], bin: usize) -> bool {
+    stock[bin] != Some(0)
+}
+
+fn take(stock: &mut [Option<usize>], bin: usize) {
+    if let Some(quantity) = stock[bin].as_mut() {
+        *quantity -= 1;
+    }
+}
+
+fn give_back(stock: &mut [Option<usize>], bin: usize) {
+    if let Some(quantity) = stock[bin].as_mut() {
+        *quantity += 1;
+    }
+}
+
+// private number of layout sheets opened from the bin
+fn opened_count(opened_from: &[Option<usize>], bin: usize) -> usize {
+    opened_from.iter().filter(|b| **b == Some(bin)).count()
+}
+
+// private stable sort of part copies by decreasing area of their outer boundary
+pub(super) fn sort_by_outer_area(job: &NestingJob, copies: &mut [usize]) {
+    copies.sort_by(|a, b| job.parts[*b].shape.outer.calculate_area().total_cmp(&job.parts[*a].shape.outer.calculate_area()));
+}
+
+// private area outside of a concave sheet outline, holes and zones forbidden for all parts, the last two grown by the spacing
+fn sheet_obstacles(sheet: &Sheet, spacing: f64, tol: f64) -> Vec<Polygon> {
+    let zones = sheet.shape.holes.iter()
+        .chain(sheet.defects.iter().filter(|d| d.grade.is_none()).map(|d| &d.shape))
+        .map(|h| grow(h, spacing, tol));
+    exterior(&sheet.shape.outer, tol).into_iter().chain(zones).collect()
+}
+
+// private pieces of the bounding box outside of the outline, empty for convex outlines
+fn exterior(outline: &Polygon, tol: f64) -> Vec<Polygon> {
+    if outline.is_convex() {
+        return Vec::new();
+    }
+
+    PolygonWithHoles::from_outer(outline.bounding_box().as_polygon())
+        .boolean(&PolygonWithHoles::from_outer(outline.clone()), BooleanOperation::Difference, tol)
+        .into_iter()
+        .filter(|piece| piece.calculate_area() > tol)
+        .map(|piece| piece.outer)
+        .collect()
+}
+
+// private first position of a part copy in the given point order over all allowed rotations, as (rotation, reference point)
+pub(super) fn find_placement(sheet: &Sheet, obstacles: &[Polygon], part: &Part, spacing: f64, order: PointOrder, tol: f64) -> Option<(f64, Point)> {
+    // graded zones below the grade of the part are obstacles for this part only
+    let graded = sheet.defects.iter()
+        .filter(|d| d.grade.is_some() && !d.accepts(part.grade))
+        .map(|d| grow(&d.shape, spacing, tol))
+        .collect::<Vec<_>>();
+    let obstacles = obstacles.iter().chain(graded.iter()).collect::<Vec<_>>();
+
+    part.rotations.iter()
+        .filter_map(|&rotation| {
+            let outline = part.shape.outer.transform(&Transform::rotation(rotation));
+            find_position(&sheet.shape.outer, &outline, &obstacles, order, tol).map(|pt| (rotation, pt))
+        })
+        .min_by(|a, b| order(&a.1, &b.1, tol))
+}
+
+// private first position of a part copy inside a hole of the placed parents, as (rotation, reference point)
+// every parent comes with the index of its grown outline in the obstacles
+fn find_placement_in_holes(sheet: &Sheet, obstacles: &[Polygon], parents: &[(usize, &Placement)], part: &Part, spacing: f64, tol: f64) -> Option<(f64, Point)> {
+    // the whole outline has to fit, holes of the part do not make room for it
+    let area = part.shape.outer.calculate_area();
+
+    for &(outline, parent) in parents.iter() {
+        for hole in parent.shape.holes.iter().filter(|h| h.calculate_area() > area) {
+            // the hole is a sheet of its own, without the outline of its parent
+            let container = if spacing <= tol {
+                hole.clone()
+            } else {
+                Path::from_polygon(hole).offset(-spacing, tol).tessellate(spacing * 0.01)
+            };
+            if container.points.len() < 3 || container.calculate_area() < area {
+                continue;
+            }
+
+            let bbox = container.bounding_box();
+            let mut hole_sheet = Sheet::new(&sheet.name, PolygonWithHoles::from_outer(container));
+            hole_sheet.defects = sheet.defects.clone();
+
+            let mut hole_obstacles = exterior(&hole_sheet.shape.outer, tol);
+            hole_obstacles.extend(obstacles.iter().enumerate()
+                .filter(|(index, o)| *index != outline && overlaps(&bbox, &o.bounding_box(), tol))
+                .map(|(_, o)| o.clone()));
+
+            let found = find_placement(&hole_sheet, &hole_obstacles, part, spacing, bottom_left, tol);
+            if found.is_some() {
+                return found;
+            }
+        }
+    }
+
+    None
+}
+
+// private check if two bounding boxes share more than their boundary
+fn overlaps(a: &BoundingBox, b: &BoundingBox, tol: f64) -> bool {
+    a.min.x < b.max.x - tol && b.min.x < a.max.x - tol && a.min.y < b.max.y - tol && b.min.y < a.max.y - tol
+}
+
+// private trial fill of a single empty sheet with the copies in order, with the spacing and part in part option of the job
+// copies are indices into the parts of the job, placements refer to the given sheet index
+pub(super) fn fill(sheet: &Sheet, index: usize, copies: &[usize], job: &NestingJob, order: PointOrder, tol: f64) -> Vec<Placement> {
+    let mut obstacles = sheet_obstacles(sheet, job.spacing, tol);
+    let mut placements: Vec<Placement> = Vec::new();
+    let mut outlines: Vec<usize> = Vec::new(); // index of the grown outline of every placement in the obstacles
+
+    for &copy in copies.iter() {
+        let part = &job.parts[copy];
+        let mut found = None;
+        if job.part_in_part {
+            let parents = outlines.iter().copied().zip(placements.iter()).collect::<Vec<_>>();
+            found = find_placement_in_holes(sheet, &obstacles, &parents, part, job.spacing, tol);
+        }
+        if found.is_none() {
+            found = find_placement(sheet, &obstacles, part, job.spacing, order, tol);
+        }
+
+        if let Some((rotation, pt)) = found {
+            let placement = Placement::new(&part.name, index, &part.shape, rotation, &Vector::new().set_values(pt.x, pt.y))
+                .set_part_index(copy);
+            outlines.push(obstacles.len());
+            obstacles.push(grow(&placement.shape.outer, job.spacing, tol));
+            placements.push(placement);
+        }
+    }
+
+    placements
+}
+
+// private bin to open for the first of the remaining copies, the lowest cost per placed area in a trial fill wins
+fn choose_bin(job: &NestingJob, stock: &[Option<usize>], remaining: &[usize], tol: f64) -> Option<usize> {
+    let first = &job.parts[*remaining.first()?];
+
+    job.bins.iter().enumerate()
+        .filter(|(index, _)| available(stock, *index))
+        .filter_map(|(index, bin)| {
+            let sheet = bin.open_sheet(1);
+            find_placement(&sheet, &sheet_obstacles(&sheet, job.spacing, tol), first, job.spacing, bottom_left, tol)?;
+
+            // copies without area give no cost per area, such bins only win on their sheet area
+            let placed_area = fill(&sheet, 0, remaining, job, bottom_left, tol).iter().map(|p| p.shape.calculate_area()).sum::<f64>();
+            let cost_per_area = if placed_area > tol {bin.cost / placed_area} else {f64::INFINITY};
+            Some((index, cost_per_area, sheet.shape.calculate_area()))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)))
+        .map(|(index, _, _)| index)
+}
+
+// private move of the parts of every opened sheet to the cheapest bin holding them all
+fn downsize(job: &NestingJob, layout: &mut Layout, opened_from: &mut [Option<usize>], stock: &mut [Option<usize>], tol: f64) {
+    for index in 0..opened_from.len() {
+        let bin = match opened_from[index] {
+            Some(bin) => bin,
+            None => continue
+        };
+
+        let copies = layout.placements_on_sheet(index)
+            .filter_map(|p| p.part_index)
+            .collect::<Vec<_>>();
+
+        let mut cheaper = (0..job.bins.len())
+            .filter(|&other| job.bins[other].cost < job.bins[bin].cost && available(stock, other))
+            .collect::<Vec<_>>();
+        cheaper.sort_by(|a, b| job.bins[*a].cost.total_cmp(&job.bins[*b].cost));
+
+        for other in cheaper {
+            let sheet = job.bins[other].open_sheet(opened_count(opened_from, other) + 1);
+            let placements = fill(&sheet, index, &copies, job, bottom_left, tol);
+            if placements.len() == copies.len() {
+                give_back(stock, bin);
+                take(stock, other);
+                opened_from[index] = Some(other);
+                layout.sheets[index] = sheet;
+                layout.placements.retain(|p| p.sheet != index);
+                layout.placements.extend(placements);
+                break;
+            }
+        }
+    }
+}
+
+// preference of reference points, the smallest one is taken
+pub(super) type PointOrder = fn(&Point, &Point, f64) -> Ordering;
+
+// private lowest, then leftmost point order
+pub(super) fn bottom_left(a: &Point, b: &Point, tol: f64) -> Ordering {
+    if (a.y - b.y).abs() > tol {
+        return a.y.partial_cmp(&b.y).unwrap();
+    }
+    a.x.partial_cmp(&b.x).unwrap()
+}
+
+// private leftmost, then lowest point order, fills a sheet column by column
+pub(super) fn left_bottom(a: &Point, b: &Point, tol: f64) -> Ordering {
+    if (a.x - b.x).abs() > tol {
+        return a.x.partial_cmp(&b.x).unwrap();
+    }
+    a.y.partial_cmp(&b.y).unwrap()
+}
+
+// private outline grown by the spacing, rounded corners are tessellated
+pub(super) fn grow(poly: &Polygon, spacing: f64, tol: f64) -> Polygon {
+    if spacing <= tol {
+        return poly.clone();
+    }
+
+    Path::from_polygon(poly).offset(spacing, tol).tessellate(spacing * 0.01)
+}
+
+// private first reference point in the given order for the outline inside the sheet, touching but not overlapping the obstacles
+fn find_position(sheet: &Polygon, outline: &Polygon, obstacles: &[&Polygon], order: PointOrder, tol: f64) -> Option<Point> {
+    // every reference point inside the inner fit rectangle keeps the outline inside the sheet box
+    let ifp = calculate_rectangular_ifp(sheet, outline, tol)?;
+
+    // one nfp per obstacle, a point between two touching obstacles is a tight fit and not inside their union
+    let nfps = obstacles.iter()
+        .map(|o| calculate_nfp(o, outline, tol))
+        .collect::<Vec<_>>();
+
+    // candidates are the corners of the inner fit rectangle and of all nfp pieces and their edge crossings
+    let ifp_edges = ifp.as_polygon().calculate_edges();
+    let mut candidates = ifp.as_polygon().points;
+    let edges = nfps.iter().flatten().map(|piece| piece.calculate_edges()).collect::<Vec<_>>();
+    for (i, piece_edges) in edges.iter().enumerate() {
+        for edge in piece_edges.iter() {
+            candidates.push(edge.from);
+            for other in edges.iter().skip(i + 1).flatten().chain(ifp_edges.iter()) {
+                let closest = Distance::line_segment_line_segment(edge, other, tol);
+                if closest.distance <= tol {
+                    candidates.push(closest.first);
+                }
+            }
+        }
+    }
+
+    candidates.into_iter()
+        .filter(|pt| ifp.contains_point(pt, tol))
+        .filter(|pt| !nfps.iter().any(|nfp| is_inside_nfp(pt, nfp, tol)))
+        .filter(|pt| outline.points.iter().all(|c| sheet.contains_point(&Point::new().set_values(c.x + pt.x, c.y + pt.y), tol)))
+        .min_by(|a, b| order(a, b, tol))
+}
@@ -0,0 +1,123 @@
+use super::super::geometry::{BoundingBox, Transform};
+use super::nester::{fill, sort_by_outer_area, bottom_left, left_bottom};
+use super::{NestingJob, Nester, Layout, Placement, Sheet, Part};
+
+// maximum number of compression steps and the relative gap to the lower bound at which compression stops
+const MAX_COMPRESSIONS: usize = 32;
+const COMPRESSION_PRECISION: f64 = 0.001;
+
+/// Result of strip packing, all placements are on a single strip sheet cut to the used length
+#[derive(Debug, Clone)]
+pub struct StripLayout {
+    pub layout: Layout, // the only sheet is the strip from (0, 0) to (width, length)
+    pub width: f64,
+    pub length: f64, // used length along the y axis
+    pub utilization: f64, // placed part area per used strip area
+    pub compressions: usize // number of shorter strips tried after the first fill
+}
+
+impl Nester {
+    /// Nests all part copies of the job on a strip of fixed width along the x axis, minimizing the used length
+    /// along the y axis. Sheets and bins of the job are ignored, copies wider than the strip in every allowed
+    /// rotation are listed in `Layout::unplaced`
+    ///
+    /// A bottom-left fill on a strip long enough for every copy gives the first length. It is compressed by
+    /// filling rectangles of shrinking length column by column, the length is halved between the longest
+    /// rectangle that failed to hold all copies and the shortest used length so far
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_nfp::geometry::{Polygon, PolygonWithHoles};
+    /// use rust_nfp::nesting::{Nester, NestingJob, Part};
+    ///
+    /// let tol = 0.0001; // geometric tolerance
+    ///
+    /// let square = PolygonWithHoles::from_outer(Polygon::square(2.0));
+    /// let job = NestingJob::new(Vec::new(), vec![Part::new("square", square, 5)]);
+    ///
+    /// // two squares per row on a strip of width 4
+    /// let strip = Nester::nest_strip(&job, 4.0, tol);
+    /// assert!(strip.layout.unplaced.is_empty());
+    /// assert!((strip.length - 6.0).abs() < tol);
+    /// assert!((strip.utilization - 20.0 / 24.0).abs() < tol);
+    /// ```
+    pub fn nest_strip(job: &NestingJob, width: f64, tol: f64) -> StripLayout {
+        // part copies as indices into the parts of the job
+        let mut unplaced = Vec::new();
+        let mut copies = Vec::new();
+        for (index, part) in job.parts.iter().enumerate() {
+            for _ in 0..part.quantity {
+                match row_height(part, width, tol) {
+                    Some(_) => copies.push(index),
+                    None => unplaced.push(part.name.clone())
+                }
+            }
+        }
+        sort_by_outer_area(job, &mut copies);
+
+        // every copy in a row of its own always fits
+        let rows = copies.iter()
+            .filter_map(|&copy| row_height(&job.parts[copy], width, tol))
+            .map(|h| h + job.spacing + tol)
+            .sum::<f64>();
+        let mut best = fill(&Sheet::rectangle("strip", width, rows), 0, &copies, job, bottom_left, tol);
+        unplaced.extend(missing(job, &copies, &best));
+
+        let mut upper = used_length(&best);
+        let mut lower = copies.iter().map(|&copy| job.parts[copy].shape.calculate_area()).sum::<f64>() / width;
+        lower = copies.iter().filter_map(|&copy| row_height(&job.parts[copy], width, tol)).fold(lower, f64::max);
+
+        let mut compressions = 0;
+        while compressions < MAX_COMPRESSIONS && upper - lower > (upper * COMPRESSION_PRECISION).max(tol) {
+            let length = 0.5 * (lower + upper);
+            let trial = fill(&Sheet::rectangle("strip", width, length), 0, &copies, job, left_bottom, tol);
+            if trial.len() == best.len() {
+                upper = used_length(&trial);
+                best = trial;
+            } else {
+                lower = length;
+            }
+            compressions += 1;
+        }
+
+        let mut layout = Layout::new(vec![Sheet::rectangle("strip", width, upper)]);
+        layout.placements = best;
+        layout.unplaced = unplaced;
+        let utilization = layout.calculate_utilization(0);
+
+        StripLayout{layout, width, length: upper, utilization, compressions}
+    }
+}
+
+// private lowest height of a part copy over the allowed rotations that fit the strip width
+fn row_height(part: &Part, width: f64, tol: f64) -> Option<f64> {
+    part.rotations.iter()
+        .map(|&rotation| part.shape.outer.transform(&Transform::rotation(rotation)).bounding_box())
+        .filter(|bbox| bbox.width() <= width + tol)
+        .map(|bbox| bbox.height())
+        .min_by(|a, b| a.partial_cmp(b).unwrap())
+}
+
+// private top of the highest placement, the strip starts at zero
+fn used_length(placements: &[Placement]) -> f64 {
+    placements.iter()
+        .fold(BoundingBox::empty(), |bbox, p| bbox.union(&p.shape.bounding_box()))
+        .max.y
+        .max(0.0)
+}
+
+// private names of the copies without a placement
+fn missing(job: &NestingJob, copies: &[usize], placements: &[Placement]) -> Vec<String> {
+    let mut placed = placements.iter().filter_map(|p| p.part_index).collect::<Vec<_>>();
+    copies.iter()
+        .filter(|&&copy| match placed.iter().position(|&index| index == copy) {
+            Some(index) => {
+                placed.swap_remove(index);
+                false
+            },
+            None => true
+        })
+        .map(|&copy| job.parts[copy].name.clone())
+        .collect()
+}
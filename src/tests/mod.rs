@@ -85,6 +85,19 @@ pub mod polygon_tests {
         assert!(poly.is_convex());
     }
 
+    #[test]
+    fn test_obtuse_convex_polygon_is_not_concave() {
+        // Arrange
+        let hexagon = Polygon::circle(1.0, 6);
+        let mut clockwise = hexagon.clone();
+        clockwise.reverse_orientation();
+
+        // Assert
+        assert!(!hexagon.is_concave());
+        assert!(hexagon.is_convex());
+        assert!(!clockwise.is_concave());
+    }
+
     #[test]
     fn test_concave() {
         // Arrange
@@ -110,7 +123,7 @@ pub mod polygon_tests {
         let poly = Polygon::from_points(&vec![pt0, pt1, pt2, pt3, pt4]);
 
         // Act
-        let triangulated = poly.triangulate(ZERO_TOLERANCE);
+        let triangulated = poly.triangulate(ZERO_TOLERANCE).unwrap();
 
         // Assert
         println!("Triangulated: {:?}", triangulated);
@@ -121,7 +134,7 @@ pub mod polygon_tests {
     fn test_merge_square() {
         // Arrange
         let poly = Polygon::square(2.0);
-        let tris = poly.triangulate(ZERO_TOLERANCE);
+        let tris = poly.triangulate(ZERO_TOLERANCE).unwrap();
 
         match Polygon::merge_convex_polygon(&tris[0], &tris[1], ZERO_TOLERANCE) {
             PolygonMergeResult::None => panic!("Not merged!"),
@@ -136,7 +149,7 @@ pub mod polygon_tests {
         let circle = Polygon::circle(10.0, pt_count);
 
         // Act
-        let subdivided = circle.subdivide_concave_polygon_in_convex_pieces(ZERO_TOLERANCE);
+        let subdivided = circle.subdivide_concave_polygon_in_convex_pieces(ZERO_TOLERANCE).unwrap();
 
         // Assert
         assert!(subdivided.len() != 0);
@@ -150,7 +163,7 @@ pub mod polygon_tests {
         let poly = Polygon::square(2.0);
 
         // Act
-        let subdivided = poly.subdivide_concave_polygon_in_convex_pieces(ZERO_TOLERANCE);
+        let subdivided = poly.subdivide_concave_polygon_in_convex_pieces(ZERO_TOLERANCE).unwrap();
         
         // Assert
         assert!(subdivided.len() != 0);
@@ -565,4 +578,2276 @@ pub mod intersection_tests {
         }
         
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+pub mod enclosing_rectangle_tests {
+    use super::super::geometry::{Polygon, Point, constants::ZERO_TOLERANCE};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_convex_hull_of_convex_is_same() {
+        // Arrange
+        let circle = Polygon::circle(5.0, 12);
+
+        // Act
+        let hull = circle.convex_hull(ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(hull.points.len(), 12);
+        assert!((hull.calculate_area() - circle.calculate_area()).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_minimum_area_rectangle_rotated() {
+        // Arrange
+        let angle = PI / 6.0;
+        let (sin, cos) = angle.sin_cos();
+        let rotate = |x: f64, y: f64| Point::new().set_values(x * cos - y * sin, x * sin + y * cos);
+        let rect = Polygon::from_points(&vec![rotate(0.0, 0.0), rotate(4.0, 0.0), rotate(4.0, 1.0), rotate(0.0, 1.0)]);
+
+        // Act
+        let enclosing = rect.minimum_area_rectangle(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert!((enclosing.calculate_area() - 4.0).abs() < ZERO_TOLERANCE);
+        assert!((enclosing.as_polygon().calculate_area() - 4.0).abs() < ZERO_TOLERANCE);
+        assert!((enclosing.angle % (PI / 2.0) - angle).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_minimum_width_rectangle_is_narrowest() {
+        // Arrange
+        let rect = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(4.0, 0.0),
+            Point::new().set_values(4.0, 1.0),
+            Point::new().set_values(0.0, 1.0)]);
+
+        // Act
+        let strip = rect.minimum_width_rectangle(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert!((strip.height - 1.0).abs() < ZERO_TOLERANCE);
+        assert!((strip.width - 4.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_minimum_area_rectangle_empty() {
+        // Arrange
+        let poly = Polygon::new();
+
+        // Assert
+        assert!(poly.minimum_area_rectangle(ZERO_TOLERANCE).is_none());
+    }
+}
+
+#[cfg(test)]
+pub mod simplify_tests {
+    use super::super::geometry::{Polygon, Point, Line, LinePointRelation, SimplifyMode, constants::ZERO_TOLERANCE};
+
+    // circle with a lot of corners, every second one pushed inwards a little
+    fn noisy_circle(radius: f64, corner_count: usize) -> Polygon {
+        let mut circle = Polygon::circle(radius, corner_count);
+        for (i, pt) in circle.points.iter_mut().enumerate() {
+            if i % 2 == 1 {
+                *pt = Point::new().set_values(pt.x * 0.999, pt.y * 0.999);
+            }
+        }
+        circle
+    }
+
+    #[test]
+    fn test_simplify_douglas_peucker_reduces() {
+        // Arrange
+        let circle = noisy_circle(10.0, 400);
+
+        // Act
+        let simplified = circle.simplify(0.1, SimplifyMode::DouglasPeucker);
+
+        // Assert
+        assert!(simplified.points.len() < 100);
+        assert!(simplified.points.len() >= 3);
+        assert!((simplified.calculate_area() - circle.calculate_area()).abs() < 0.1 * 2.0 * std::f64::consts::PI * 10.0);
+    }
+
+    #[test]
+    fn test_simplify_visvalingam_respects_tolerance() {
+        // Arrange
+        let circle = noisy_circle(10.0, 400);
+        let tolerance = 0.1;
+
+        // Act
+        let simplified = circle.simplify(tolerance, SimplifyMode::Visvalingam);
+
+        // Assert
+        assert!(simplified.points.len() < 100);
+        for pt in circle.points.iter() {
+            let distance = simplified.calculate_edges().iter()
+                .map(|e| {
+                    let t = ((pt.x - e.from.x) * e.direction.x + (pt.y - e.from.y) * e.direction.y) / (e.calculate_length() * e.calculate_length());
+                    pt.distance_to(&e.point_at_normalized_parameter(t.clamp(0.0, 1.0)))
+                })
+                .fold(f64::MAX, f64::min);
+            assert!(distance <= tolerance + ZERO_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_simplify_outer_contains_original() {
+        // Arrange
+        let circle = noisy_circle(10.0, 400);
+
+        // Act
+        let simplified = circle.simplify(0.1, SimplifyMode::Outer);
+
+        // Assert
+        assert!(simplified.points.len() < 100);
+        for edge in simplified.calculate_edges() {
+            for pt in circle.points.iter() {
+                if let LinePointRelation::Right = Line::line_point_relation_fast(&edge.from, &edge.to, pt, ZERO_TOLERANCE) {
+                    panic!("Original corner {:?} outside of simplified polygon", pt);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_simplify_keeps_triangle() {
+        // Arrange
+        let tri = Polygon::from_points(&vec![Point::new(), Point::new().set_values(1.0, 0.0), Point::new().set_values(0.0, 1.0)]);
+
+        // Act
+        let simplified = tri.simplify(10.0, SimplifyMode::Visvalingam);
+
+        // Assert
+        assert_eq!(simplified.points.len(), 3);
+    }
+}
+
+#[cfg(test)]
+pub mod mass_properties_tests {
+    use super::super::geometry::{Polygon, PolygonWithHoles, Point, constants::ZERO_TOLERANCE};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_mass_properties_orientation_independent() {
+        // Arrange
+        let ccw = Polygon::square(2.0);
+        let mut cw = Polygon::square(2.0);
+        cw.reverse_orientation();
+
+        // Act
+        let ccw_props = ccw.calculate_mass_properties();
+        let cw_props = cw.calculate_mass_properties();
+
+        // Assert
+        assert_eq!(ccw_props, cw_props);
+        assert_eq!(ccw_props.area, 4.0);
+    }
+
+    #[test]
+    fn test_mass_properties_principal_axes_rotated() {
+        // Arrange
+        let angle = PI / 3.0;
+        let (sin, cos) = angle.sin_cos();
+        let rotate = |x: f64, y: f64| Point::new().set_values(x * cos - y * sin + 5.0, x * sin + y * cos - 2.0);
+        let rect = Polygon::from_points(&vec![rotate(-2.0, -0.5), rotate(2.0, -0.5), rotate(2.0, 0.5), rotate(-2.0, 0.5)]);
+
+        // Act
+        let props = rect.calculate_mass_properties();
+
+        // Assert
+        assert!(props.centroid.epsilon_equals(&Point::new().set_values(5.0, -2.0), ZERO_TOLERANCE));
+        assert!((props.principal_angle - angle).abs() < ZERO_TOLERANCE);
+        assert!((props.principal_moments.0 - 4.0 * 1.0 / 12.0).abs() < ZERO_TOLERANCE);
+        assert!((props.principal_moments.1 - 1.0 * 64.0 / 12.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_mass_properties_hole_shifts_centroid() {
+        // Arrange
+        let outer = Polygon::square(4.0);
+        let mut hole = Polygon::from_points(&vec![
+            Point::new().set_values(0.0, -1.0),
+            Point::new().set_values(2.0, -1.0),
+            Point::new().set_values(2.0, 1.0),
+            Point::new().set_values(0.0, 1.0)]);
+        hole.reverse_orientation(); // hole orientation must not matter
+        let poly = PolygonWithHoles::new(outer, vec![hole]);
+
+        // Act
+        let props = poly.calculate_mass_properties();
+
+        // Assert
+        assert_eq!(props.area, 12.0);
+        assert!(props.centroid.epsilon_equals(&Point::new().set_values(-1.0 / 3.0, 0.0), ZERO_TOLERANCE));
+    }
+}
+
+#[cfg(test)]
+pub mod distance_tests {
+    use super::super::geometry::{Distance, Polygon, Point, LineSegment, constants::ZERO_TOLERANCE};
+
+    #[test]
+    fn test_point_line_segment_distance_end_point() {
+        // Arrange
+        let line = LineSegment::new_from_points(&Point::new(), &Point::new().set_values(2.0, 2.0));
+        let pt = Point::new().set_values(3.0, 3.0);
+
+        // Act
+        let closest = Distance::point_line_segment(&pt, &line);
+
+        // Assert
+        assert!((closest.distance - 2.0_f64.sqrt()).abs() < ZERO_TOLERANCE);
+        assert_eq!(closest.other, line.to);
+    }
+
+    #[test]
+    fn test_line_segment_line_segment_distance_crossing() {
+        // Arrange
+        let line0 = LineSegment::new_from_points(&Point::new().set_values(-1.0, -1.0), &Point::new().set_values(1.0, 1.0));
+        let line1 = LineSegment::new_from_points(&Point::new().set_values(-1.0, 1.0), &Point::new().set_values(1.0, -1.0));
+
+        // Act
+        let closest = Distance::line_segment_line_segment(&line0, &line1, ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(closest.distance, 0.0);
+        assert!(closest.first.epsilon_equals(&Point::new(), ZERO_TOLERANCE));
+    }
+
+    #[test]
+    fn test_line_segment_line_segment_distance_parallel() {
+        // Arrange
+        let line0 = LineSegment::new_from_points(&Point::new(), &Point::new().set_values(4.0, 0.0));
+        let line1 = LineSegment::new_from_points(&Point::new().set_values(3.0, 2.0), &Point::new().set_values(8.0, 2.0));
+
+        // Act
+        let closest = Distance::line_segment_line_segment(&line0, &line1, ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(closest.distance, 2.0);
+    }
+
+    #[test]
+    fn test_polygon_polygon_distance_contained() {
+        // Arrange
+        let outer = Polygon::square(10.0);
+        let inner = Polygon::square(1.0);
+
+        // Act
+        let closest = Distance::polygon_polygon(&outer, &inner, ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(closest.distance, 0.0);
+    }
+
+    #[test]
+    fn test_point_polygon_boundary_inside() {
+        // Arrange
+        let square = Polygon::square(4.0);
+        let pt = Point::new().set_values(1.5, 0.0);
+
+        // Act
+        let closest = Distance::point_polygon_boundary(&pt, &square);
+
+        // Assert
+        assert!((closest.distance - 0.5).abs() < ZERO_TOLERANCE);
+        assert!(closest.other.epsilon_equals(&Point::new().set_values(2.0, 0.0), ZERO_TOLERANCE));
+    }
+}
+
+#[cfg(test)]
+pub mod convex_nfp_tests {
+    use super::super::geometry::{Polygon, Point, Vector, constants::ZERO_TOLERANCE};
+    use super::super::lib::calculate_convex_nfp;
+
+    #[test]
+    fn test_convex_nfp_reference_point() {
+        // Arrange
+        let square = Polygon::square(2.0);
+        let other = Polygon::square(2.0).copy_along_vector(&Vector::new().set_values(5.0, 5.0));
+
+        // Act
+        let nfp = calculate_convex_nfp(&square, other);
+
+        // Assert: other has to move by (-5, -5) to be centered on square
+        assert!(nfp.contains_point(&Point::new().set_values(-5.0, -5.0), ZERO_TOLERANCE));
+        assert!(nfp.is_point_on(&Point::new().set_values(-3.0, -5.0), ZERO_TOLERANCE));
+        assert!((nfp.calculate_area() - 16.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_convex_nfp_starts_below_first() {
+        // Arrange
+        let square = Polygon::square(2.0);
+        let small = Polygon::square(1.0);
+
+        // Act
+        let nfp = calculate_convex_nfp(&square, small);
+
+        // Assert
+        assert!(nfp.points[0].epsilon_equals(&Point::new().set_values(-1.5, -1.5), ZERO_TOLERANCE));
+        assert!(nfp.calculate_signed_area() > 0.0);
+        assert!((nfp.calculate_area() - 9.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_convex_nfp_ignores_orientation() {
+        // Arrange
+        let tri = Polygon::from_points(&vec![Point::new(), Point::new().set_values(2.0, 0.0), Point::new().set_values(0.0, 2.0)]);
+        let square = Polygon::square(1.0);
+        let mut tri_clockwise = tri.clone();
+        tri_clockwise.reverse_orientation();
+        let mut square_clockwise = square.clone();
+        square_clockwise.reverse_orientation();
+
+        // Act
+        let nfp = calculate_convex_nfp(&tri, square);
+        let nfp_clockwise = calculate_convex_nfp(&tri_clockwise, square_clockwise);
+
+        // Assert
+        assert_eq!(nfp.points.len(), nfp_clockwise.points.len());
+        assert!(nfp.points.iter().zip(nfp_clockwise.points.iter()).all(|(a, b)| a.epsilon_equals(b, ZERO_TOLERANCE)));
+    }
+
+    #[test]
+    fn test_convex_nfp_skips_repeated_corners() {
+        // Arrange
+        let square = Polygon::square(2.0);
+        let mut repeated = square.points.clone();
+        repeated.insert(1, repeated[1]);
+        let repeated = Polygon::from_points(&repeated);
+
+        // Act
+        let nfp = calculate_convex_nfp(&square, Polygon::square(1.0));
+        let nfp_repeated = calculate_convex_nfp(&repeated, Polygon::square(1.0));
+
+        // Assert
+        assert_eq!(nfp.points.len(), nfp_repeated.points.len());
+        assert!((nfp_repeated.calculate_area() - 9.0).abs() < ZERO_TOLERANCE);
+    }
+}
+
+#[cfg(test)]
+pub mod penetration_tests {
+    use super::super::geometry::{Polygon, Point, Vector, constants::ZERO_TOLERANCE};
+    use super::super::lib::{calculate_penetration, PenetrationResult};
+
+    fn l_shape() -> Polygon {
+        Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(4.0, 0.0),
+            Point::new().set_values(4.0, 1.0),
+            Point::new().set_values(1.0, 1.0),
+            Point::new().set_values(1.0, 4.0),
+            Point::new().set_values(0.0, 4.0)])
+    }
+
+    #[test]
+    fn test_penetration_separated() {
+        // Arrange
+        let square = Polygon::square(2.0);
+        let other = Polygon::square(2.0).copy_along_vector(&Vector::new().set_values(3.0, 0.0));
+
+        // Act
+        let result = calculate_penetration(&square, &other, ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(result, PenetrationResult::None);
+    }
+
+    #[test]
+    fn test_penetration_concave() {
+        // Arrange
+        let l = l_shape();
+        let square = Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(2.0, 0.6));
+
+        // Act
+        let result = calculate_penetration(&l, &square, ZERO_TOLERANCE);
+
+        // Assert
+        match result {
+            PenetrationResult::None => panic!("Expected overlap!"),
+            PenetrationResult::Overlap(depth, translation) => {
+                assert!((depth - 0.9).abs() < 0.001);
+                assert!(translation.epsilon_equals(&Vector::new().set_values(0.0, 0.9), 0.001));
+            }
+        }
+    }
+
+    #[test]
+    fn test_penetration_concave_inside_notch() {
+        // Arrange
+        let l = l_shape();
+        let square = Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(2.0, 2.0));
+
+        // Act
+        let result = calculate_penetration(&l, &square, ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(result, PenetrationResult::None);
+    }
+}
+
+#[cfg(test)]
+pub mod triangulation_tests {
+    use super::super::geometry::{Polygon, PolygonWithHoles, Point, Vector, TriangulationError, constants::ZERO_TOLERANCE};
+
+    fn summed_area(tris: &[Polygon]) -> f64 {
+        tris.iter().map(|t| t.calculate_signed_area()).sum()
+    }
+
+    #[test]
+    fn test_triangulate_circle_fan_count() {
+        // Arrange
+        let circle = Polygon::circle(5.0, 32);
+
+        // Act
+        let tris = circle.triangulate(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert_eq!(tris.len(), 30);
+        assert!(tris.iter().all(|t| t.calculate_signed_area() > 0.0));
+        assert!((summed_area(&tris) - circle.calculate_area()).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_triangulate_skips_colinear_corners() {
+        // Arrange
+        let poly = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(1.0, 0.0),
+            Point::new().set_values(2.0, 0.0),
+            Point::new().set_values(2.0, 2.0),
+            Point::new().set_values(2.0, 2.0), // duplicate
+            Point::new().set_values(0.0, 2.0)]);
+
+        // Act
+        let tris = poly.triangulate_indices(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert_eq!(tris.len(), 2);
+    }
+
+    #[test]
+    fn test_triangulate_with_holes() {
+        // Arrange
+        let hole0 = Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(-2.0, 0.0));
+        let mut hole1 = Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(2.0, 1.0));
+        hole1.reverse_orientation();
+        let poly = PolygonWithHoles::new(Polygon::square(8.0), vec![hole0, hole1]);
+
+        // Act
+        let tris = poly.triangulate(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert_eq!(tris.len(), 4 + 4 + 4 + 2 * 2 - 2);
+        assert!(tris.iter().all(|t| t.calculate_signed_area() > 0.0));
+        assert!((summed_area(&tris) - poly.calculate_area()).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_triangulate_errors() {
+        // Arrange
+        let line = Polygon::from_points(&vec![Point::new(), Point::new().set_values(1.0, 1.0), Point::new().set_values(2.0, 2.0)]);
+        let outside = PolygonWithHoles::new(
+            Polygon::square(2.0),
+            vec![Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(10.0, 0.0))]);
+
+        // Assert
+        assert_eq!(line.triangulate_indices(ZERO_TOLERANCE), Err(TriangulationError::TooFewPoints));
+        assert_eq!(outside.triangulate_indices(ZERO_TOLERANCE), Err(TriangulationError::HoleOutside(0)));
+    }
+
+    #[test]
+    fn test_triangulate_hole_left_of_outer() {
+        // Arrange
+        let outside = PolygonWithHoles::new(
+            Polygon::square(2.0),
+            vec![Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(-8.0, 0.0))]);
+
+        // Assert
+        assert_eq!(outside.triangulate_indices(ZERO_TOLERANCE), Err(TriangulationError::HoleOutside(0)));
+    }
+
+    #[test]
+    fn test_triangulate_self_intersecting() {
+        // Arrange
+        let bow_tie = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(3.0, 2.0),
+            Point::new().set_values(3.0, 0.0),
+            Point::new().set_values(0.0, 3.0)]);
+
+        // Assert
+        assert!(matches!(bow_tie.triangulate_indices(ZERO_TOLERANCE), Err(TriangulationError::NoEarFound(_))));
+    }
+}
+
+#[cfg(test)]
+pub mod convex_decomposition_tests {
+    use super::super::geometry::{Polygon, Point, DecompositionMode, constants::ZERO_TOLERANCE};
+    use std::f64::consts::PI;
+
+    fn star(corner_count: usize) -> Polygon {
+        let pts = (0..(2 * corner_count))
+            .map(|n| Point::new_from_polar(if n % 2 == 0 {5.0} else {2.0}, n as f64 * PI / corner_count as f64))
+            .collect::<Vec<_>>();
+        Polygon::from_points(&pts)
+    }
+
+    #[test]
+    fn test_decompose_star_pieces_convex() {
+        // Arrange
+        let poly = star(8);
+
+        for mode in [DecompositionMode::HertelMehlhorn, DecompositionMode::Minimum].iter() {
+            // Act
+            let pieces = poly.decompose_convex(*mode, ZERO_TOLERANCE).unwrap();
+
+            // Assert
+            let area: f64 = pieces.iter().map(|p| p.calculate_signed_area()).sum();
+            assert!((area - poly.calculate_area()).abs() < ZERO_TOLERANCE);
+            assert!(pieces.iter().all(|p| p.is_convex()));
+        }
+    }
+
+    #[test]
+    fn test_decompose_minimum_not_worse() {
+        // Arrange
+        let poly = star(7);
+
+        // Act
+        let approximated = poly.decompose_convex_indices(DecompositionMode::HertelMehlhorn, ZERO_TOLERANCE).unwrap();
+        let optimal = poly.decompose_convex_indices(DecompositionMode::Minimum, ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert!(optimal.len() <= approximated.len());
+        assert!(optimal.len() <= 8);
+    }
+
+    #[test]
+    fn test_decompose_convex_is_single_piece() {
+        // Arrange
+        let mut circle = Polygon::circle(3.0, 24);
+        circle.reverse_orientation();
+
+        // Act
+        let pieces = circle.decompose_convex_indices(DecompositionMode::Minimum, ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].len(), 24);
+    }
+}
+
+#[cfg(test)]
+pub mod delaunay_tests {
+    use super::super::geometry::{Polygon, PolygonWithHoles, Point, Vector, constants::ZERO_TOLERANCE};
+
+    // no corner of any triangle lies strictly inside the circumcircle of a neighbour sharing an edge
+    fn is_locally_delaunay(pts: &[Point], tris: &[[usize; 3]]) -> bool {
+        for t in tris.iter() {
+            let a = pts[t[0]];
+            let b = pts[t[1]];
+            let c = pts[t[2]];
+            let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+            let ux = ((a.x * a.x + a.y * a.y) * (b.y - c.y) + (b.x * b.x + b.y * b.y) * (c.y - a.y) + (c.x * c.x + c.y * c.y) * (a.y - b.y)) / d;
+            let uy = ((a.x * a.x + a.y * a.y) * (c.x - b.x) + (b.x * b.x + b.y * b.y) * (a.x - c.x) + (c.x * c.x + c.y * c.y) * (b.x - a.x)) / d;
+            let center = Point::new().set_values(ux, uy);
+            let radius = center.distance_to(&a);
+
+            for n in tris.iter() {
+                let shared = n.iter().filter(|i| t.contains(i)).count();
+                if shared != 2 {
+                    continue;
+                }
+                let opposite = n.iter().find(|i| !t.contains(i)).unwrap();
+                if center.distance_to(&pts[*opposite]) < radius - ZERO_TOLERANCE {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn has_edge(tris: &[[usize; 3]], from: usize, to: usize) -> bool {
+        tris.iter().any(|t| (0..3).any(|e| t[e] == from && t[(e + 1) % 3] == to))
+    }
+
+    #[test]
+    fn test_delaunay_flat_strip() {
+        // Arrange
+        let wave = |n: usize| if n % 2 == 1 {-0.1} else {0.0};
+        let mut pts = (0..=10).map(|n| Point::new().set_values(n as f64, wave(n))).collect::<Vec<_>>();
+        pts.extend((0..=10).rev().map(|n| Point::new().set_values(n as f64 + 0.5, 1.0 - wave(n))));
+        let strip = Polygon::from_points(&pts);
+
+        // Act
+        let tris = strip.triangulate_delaunay_indices(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert_eq!(tris.len(), pts.len() - 2);
+        assert!(is_locally_delaunay(&strip.points, &tris));
+        assert!((0..pts.len()).all(|n| has_edge(&tris, n, (n + 1) % pts.len())));
+    }
+
+    #[test]
+    fn test_delaunay_keeps_concave_boundary() {
+        // Arrange
+        let comb = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(6.0, 0.0),
+            Point::new().set_values(6.0, 4.0),
+            Point::new().set_values(5.0, 4.0),
+            Point::new().set_values(4.0, 0.5),
+            Point::new().set_values(3.0, 4.0),
+            Point::new().set_values(2.0, 0.5),
+            Point::new().set_values(1.0, 4.0),
+            Point::new().set_values(0.0, 4.0)]);
+
+        // Act
+        let tris = comb.triangulate_delaunay(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        let area: f64 = tris.iter().map(|t| t.calculate_signed_area()).sum();
+        assert_eq!(tris.len(), 7);
+        assert!(tris.iter().all(|t| t.calculate_signed_area() > 0.0));
+        assert!((area - comb.calculate_area()).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_delaunay_with_holes() {
+        // Arrange
+        let hole0 = Polygon::circle(1.0, 8).copy_along_vector(&Vector::new().set_values(-2.0, 0.0));
+        let hole1 = Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(2.0, 1.0));
+        let poly = PolygonWithHoles::new(Polygon::circle(5.0, 16), vec![hole0, hole1]);
+        let pts = poly.all_points();
+
+        // Act
+        let tris = poly.triangulate_delaunay_indices(ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        let area: f64 = tris.iter()
+            .map(|t| Polygon::from_points(&vec![pts[t[0]], pts[t[1]], pts[t[2]]]).calculate_signed_area())
+            .sum();
+        assert!((area - poly.calculate_area()).abs() < ZERO_TOLERANCE);
+        assert!(is_locally_delaunay(&pts, &tris));
+    }
+}
+
+#[cfg(test)]
+pub mod path_tests {
+    use super::super::geometry::{Arc, Path, PathSegment, Polygon, Point, constants::ZERO_TOLERANCE};
+    use std::f64::consts::PI;
+
+    // 4 x 4 square around the origin with corners rounded by the given radius, counter-clockwise
+    fn rounded_square(radius: f64) -> Path {
+        let c = 2.0 - radius;
+        let centers = [(c, -c), (c, c), (-c, c), (-c, -c)];
+
+        let mut path = Path::new();
+        for (n, (x, y)) in centers.iter().enumerate() {
+            let arc = Arc::new(&Point::new().set_values(*x, *y), radius, (n as f64 - 1.0) * PI / 2.0, PI / 2.0);
+            if path.end_point().is_some() {
+                path.line_to(&arc.start_point());
+            }
+            path.segments.push(PathSegment::Arc(arc));
+        }
+        let start = path.start_point().unwrap();
+        path.line_to(&start);
+        path
+    }
+
+    #[test]
+    fn test_arc_tessellation_within_chord_error() {
+        // Arrange
+        let arc = Arc::new(&Point::new().set_values(3.0, -1.0), 7.5, 0.3, -4.0);
+        let chord_error = 0.01;
+
+        // Act
+        let pts = arc.tessellate(chord_error);
+
+        // Assert
+        assert!(pts[0].epsilon_equals(&arc.start_point(), ZERO_TOLERANCE));
+        assert!(pts.last().unwrap().epsilon_equals(&arc.end_point(), ZERO_TOLERANCE));
+        for pair in pts.windows(2) {
+            let mid = Point::new().set_values((pair[0].x + pair[1].x) / 2.0, (pair[0].y + pair[1].y) / 2.0);
+            let sagitta = arc.radius - mid.distance_to(&arc.center);
+            assert!(sagitta <= chord_error + ZERO_TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn test_arc_tessellation_without_positive_chord_error() {
+        // Arrange
+        let arc = Arc::new(&Point::new(), 1.0, 0.0, PI);
+        let inverted = Arc::new(&Point::new(), -1.0, 0.0, PI);
+
+        // Act
+        let pts = arc.tessellate(0.0);
+        let negative = arc.tessellate(-1.0);
+        let inverted_pts = inverted.tessellate(0.01);
+
+        // Assert
+        assert_eq!(pts.len(), 2048 + 1);
+        assert_eq!(negative.len(), pts.len());
+        assert_eq!(inverted_pts.len(), pts.len());
+    }
+
+    #[test]
+    fn test_arc_bounding_box_clockwise() {
+        // Arrange
+        let arc = Arc::new(&Point::new(), 2.0, PI / 2.0, -PI);
+
+        // Act
+        let bbox = arc.bounding_box();
+
+        // Assert
+        assert!(bbox.min.epsilon_equals(&Point::new().set_values(0.0, -2.0), ZERO_TOLERANCE));
+        assert!(bbox.max.epsilon_equals(&Point::new().set_values(2.0, 2.0), ZERO_TOLERANCE));
+    }
+
+    #[test]
+    fn test_path_rounded_square_shrinks_exactly() {
+        // Arrange
+        let path = rounded_square(1.0);
+
+        // Act
+        let shrunk = path.offset(-0.5, ZERO_TOLERANCE);
+
+        // Assert
+        assert!(path.is_closed(ZERO_TOLERANCE));
+        assert!(shrunk.is_closed(ZERO_TOLERANCE));
+        assert_eq!(shrunk.segments.len(), 8);
+        assert!((shrunk.calculate_signed_area() - (9.0 - (4.0 - PI) * 0.25)).abs() < ZERO_TOLERANCE);
+        assert!((shrunk.bounding_box().width() - 3.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_path_offset_concave_corner_trimmed() {
+        // Arrange
+        let mut l_shape = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(2.0, 0.0),
+            Point::new().set_values(2.0, 1.0),
+            Point::new().set_values(1.0, 1.0),
+            Point::new().set_values(1.0, 2.0),
+            Point::new().set_values(0.0, 2.0)]);
+        l_shape.reverse_orientation();
+        let d = 0.25;
+
+        // Act
+        let grown = Path::from_polygon(&l_shape).offset(d, ZERO_TOLERANCE);
+
+        // Assert
+        // five convex corners get a quarter circle each, the concave one loses its doubled square
+        assert!(grown.is_closed(ZERO_TOLERANCE));
+        assert_eq!(grown.segments.len(), 11);
+        assert!((grown.calculate_signed_area().abs() - (3.0 + 8.0 * d + 1.25 * PI * d * d - d * d)).abs() < ZERO_TOLERANCE);
+    }
+}
+
+#[cfg(test)]
+pub mod curve_tests {
+    use super::super::geometry::{QuadraticBezier, CubicBezier, BSpline, LineSegment, Point, constants::ZERO_TOLERANCE};
+
+    // largest distance of densely sampled curve points to the polyline
+    fn sampled_deviation(pts: &[Point], eval: impl Fn(f64) -> Point) -> f64 {
+        let edges = pts.windows(2).map(|p| LineSegment::new_from_points(&p[0], &p[1])).collect::<Vec<_>>();
+        (0..=1000)
+            .map(|n| eval(n as f64 / 1000.0))
+            .map(|pt| edges.iter().map(|e| e.distance_to_point(&pt)).fold(f64::MAX, f64::min))
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn test_cubic_flattening_within_tolerance() {
+        // Arrange
+        let curve = CubicBezier::new(
+            &Point::new(),
+            &Point::new().set_values(0.0, 5.0),
+            &Point::new().set_values(5.0, 5.0),
+            &Point::new().set_values(5.0, 0.0));
+
+        // Act
+        let flat = curve.flatten(0.005);
+
+        // Assert
+        assert!(flat.max_deviation <= 0.005);
+        assert!(sampled_deviation(&flat.points, |t| curve.point_at_normalized_parameter(t)) <= flat.max_deviation + ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_flattening_without_positive_tolerance() {
+        // Arrange
+        let curve = QuadraticBezier::new(&Point::new(), &Point::new().set_values(1.0, 2.0), &Point::new().set_values(2.0, 0.0));
+
+        // Act
+        let flat = curve.flatten(0.0);
+        let negative = curve.flatten(-1.0);
+
+        // Assert
+        // the quadratic deviation shrinks by 4 per split, a billionth of the size needs 15 levels
+        assert!(flat.points.len() <= (1 << 16) + 1);
+        assert!(flat.max_deviation <= 2.0e-9);
+        assert_eq!(flat.points.len(), negative.points.len());
+    }
+
+    #[test]
+    fn test_straight_quadratic_is_single_chord() {
+        // Arrange
+        let curve = QuadraticBezier::new(&Point::new(), &Point::new().set_values(1.0, 1.0), &Point::new().set_values(3.0, 3.0));
+
+        // Act
+        let flat = curve.flatten(ZERO_TOLERANCE);
+
+        // Assert
+        assert_eq!(flat.points.len(), 2);
+        assert_eq!(flat.max_deviation, 0.0);
+    }
+
+    #[test]
+    fn test_quadratic_elevated_to_cubic() {
+        // Arrange
+        let quad = QuadraticBezier::new(&Point::new(), &Point::new().set_values(2.0, 4.0), &Point::new().set_values(4.0, -1.0));
+
+        // Act
+        let cubic = CubicBezier::from_quadratic(&quad);
+
+        // Assert
+        for n in 0..=10 {
+            let t = n as f64 / 10.0;
+            assert!(cubic.point_at_normalized_parameter(t).epsilon_equals(&quad.point_at_normalized_parameter(t), ZERO_TOLERANCE));
+        }
+    }
+
+    #[test]
+    fn test_bspline_matches_bezier_knots() {
+        // Arrange
+        let pts = vec![
+            Point::new(),
+            Point::new().set_values(1.0, 3.0),
+            Point::new().set_values(4.0, 3.0),
+            Point::new().set_values(5.0, 0.0)];
+        let spline = BSpline::new(3, &pts, &[0.0, 0.0, 0.0, 0.0, 2.0, 2.0, 2.0, 2.0]).unwrap();
+        let bezier = CubicBezier::new(&pts[0], &pts[1], &pts[2], &pts[3]);
+
+        // Act
+        let flat = spline.flatten(0.01);
+
+        // Assert
+        assert_eq!(flat, bezier.flatten(0.01));
+        assert!(spline.point_at_normalized_parameter(0.3).epsilon_equals(&bezier.point_at_normalized_parameter(0.3), ZERO_TOLERANCE));
+    }
+
+    #[test]
+    fn test_bspline_unclamped_flattening() {
+        // Arrange
+        let pts = (0..7).map(|n| Point::new().set_values(n as f64, if n % 2 == 0 {0.0} else {2.0})).collect::<Vec<_>>();
+        let knots = (0..10).map(|n| n as f64).collect::<Vec<_>>();
+        let spline = BSpline::new(2, &pts, &knots).unwrap();
+
+        // Act
+        let flat = spline.flatten(0.001);
+
+        // Assert
+        assert!(flat.points[0].epsilon_equals(&Point::new().set_values(0.5, 1.0), ZERO_TOLERANCE));
+        assert!(sampled_deviation(&flat.points, |t| spline.point_at_normalized_parameter(t)) <= 0.001 + ZERO_TOLERANCE);
+        assert!(BSpline::new(2, &pts, &knots[1..]).is_none());
+    }
+}
+
+#[cfg(test)]
+pub mod svg_reader_tests {
+    use super::super::io::{SvgReader, SvgError};
+    use super::super::geometry::{Point, constants::ZERO_TOLERANCE};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_svg_basic_shapes() {
+        // Arrange
+        let svg = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!-- two parts side by side -->
+            <svg xmlns="http://www.w3.org/2000/svg" width="100px" height="100px">
+                <defs><rect width="50" height="50"/></defs>
+                <rect x="0" y="0" width="10" height="20"/>
+                <polygon points="20,0 30,0 25,10"/>
+                <svg:polyline points="40 0 50 0 50 10 40 10"/>
+            </svg>"#;
+
+        // Act
+        let parts = SvgReader::read_str(svg, 0.01).unwrap();
+
+        // Assert
+        let mut areas = parts.iter().map(|p| p.calculate_area()).collect::<Vec<_>>();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(areas, vec![50.0, 100.0, 200.0]);
+        assert!(parts.iter().all(|p| p.outer.calculate_signed_area() > 0.0));
+    }
+
+    #[test]
+    fn test_svg_rect_sizes() {
+        // Act
+        let empty = SvgReader::read_rings("<svg><rect width=\"0\" height=\"1\"/><rect width=\"2\" height=\"0\" rx=\"1\"/></svg>", 0.01);
+        let negative_width = SvgReader::read_rings("<svg><rect width=\"-2\" height=\"1\"/></svg>", 0.01);
+        let negative_height = SvgReader::read_rings("<svg><rect width=\"2\" height=\"-1\" ry=\"0.5\"/></svg>", 0.01);
+        let nan = SvgReader::read_rings("<svg><rect width=\"NaN\" height=\"1\"/></svg>", 0.01);
+
+        // Assert
+        assert_eq!(empty.unwrap().len(), 0);
+        assert_eq!(negative_width.err(), Some(SvgError::Attribute("rect".to_string(), "width".to_string())));
+        assert_eq!(negative_height.err(), Some(SvgError::Attribute("rect".to_string(), "height".to_string())));
+        assert_eq!(nan.err(), Some(SvgError::Attribute("rect".to_string(), "width".to_string())));
+    }
+
+    #[test]
+    fn test_svg_circle_within_tolerance() {
+        // Arrange
+        let tol = 0.01;
+        let svg = r#"<svg><circle cx="5" cy="5" r="10" /><ellipse cx="50" cy="0" rx="4" ry="2"/></svg>"#;
+
+        // Act
+        let rings = SvgReader::read_rings(svg, tol).unwrap();
+
+        // Assert
+        let center = Point::new().set_values(5.0, 5.0);
+        assert_eq!(rings.len(), 2);
+        assert!(rings[0].points.iter().all(|pt| (pt.distance_to(&center) - 10.0).abs() < ZERO_TOLERANCE));
+        assert!(rings[0].calculate_area() < 100.0 * PI);
+        assert!(rings[0].calculate_area() > 100.0 * PI - 2.0 * PI * 10.0 * tol);
+        assert!((rings[1].calculate_area() - 8.0 * PI).abs() < 2.0 * PI * 4.0 * tol);
+    }
+
+    #[test]
+    fn test_svg_nested_transforms() {
+        // Arrange
+        let svg = r#"<svg>
+            <g transform="translate(100, 0)">
+                <g transform="scale(2)">
+                    <rect x="1" y="1" width="1" height="1" transform="rotate(90 1 1)"/>
+                </g>
+            </g>
+        </svg>"#;
+
+        // Act
+        let rings = SvgReader::read_rings(svg, ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        let bbox = rings[0].bounding_box();
+        assert!(bbox.min.epsilon_equals(&Point::new().set_values(100.0, 2.0), ZERO_TOLERANCE));
+        assert!(bbox.max.epsilon_equals(&Point::new().set_values(102.0, 4.0), ZERO_TOLERANCE));
+    }
+
+    #[test]
+    fn test_svg_path_curves_and_arcs() {
+        // Arrange
+        let tol = 0.001;
+        // 10 x 10 square with a half circle on top, a rounded slot as hole, written compactly
+        let svg = r#"<svg><path d="M0,0h10v10a5 5 0 01-10 0z M3 4c0-1 .5-1 1-1s1 0 1 1q0 1-1 1T3 4z" transform="scale(1,-1)"/></svg>"#;
+
+        // Act
+        let parts = SvgReader::read_str(svg, tol).unwrap();
+
+        // Assert
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].holes.len(), 1);
+        assert!((parts[0].outer.calculate_area() - (100.0 + 12.5 * PI)).abs() < 0.1);
+        assert!(parts[0].outer.bounding_box().min.y < -14.99);
+    }
+
+    #[test]
+    fn test_svg_errors() {
+        // Assert
+        assert_eq!(SvgReader::read_str("<svg><path d=\"M0 0 L\"/></svg>", 0.1).err(), Some(SvgError::PathData("M0 0 L".to_string())));
+        assert_eq!(SvgReader::read_str("<svg><rect width=\"1mm\"/></svg>", 0.1).err(), Some(SvgError::Attribute("rect".to_string(), "width".to_string())));
+        assert_eq!(SvgReader::read_str("<svg><g transform=\"spin(3)\"></g></svg>", 0.1).err(), Some(SvgError::Attribute("g".to_string(), "transform".to_string())));
+        assert_eq!(SvgReader::read_str("<svg><rect width=\"1", 0.1).err(), Some(SvgError::Xml(5)));
+        assert_eq!(SvgReader::read_str("<svg><circle r='1'/></svg>", 0.0).err(), Some(SvgError::Tolerance(0.0)));
+        assert_eq!(SvgReader::read_str("<svg><ellipse rx='2' ry='1'/></svg>", -0.1).err(), Some(SvgError::Tolerance(-0.1)));
+    }
+}
+
+#[cfg(test)]
+pub mod svg_writer_tests {
+    use super::super::io::{SvgReader, SvgWriter, SvgStyle, SvgStyles};
+    use super::super::geometry::{Polygon, PolygonWithHoles, Vector, constants::ZERO_TOLERANCE};
+    use super::super::nesting::{Layout, Sheet, Placement};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_svg_writer_round_trip() {
+        // Arrange
+        let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+        let mut writer = SvgWriter::new(SvgStyles::default());
+
+        // Act
+        writer.add_polygon_with_holes(&frame, &SvgStyle::new("gray", "black", 0.1));
+        writer.add_polygon(&Polygon::circle(1.0, 16).copy_along_vector(&Vector::new().set_values(10.0, 0.0)), &SvgStyle::new("red", "none", 0.0));
+        let parts = SvgReader::read_str(&writer.render(), ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].calculate_area(), 12.0);
+        assert!((parts[1].calculate_area() - Polygon::circle(1.0, 16).calculate_area()).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_svg_writer_layout() {
+        // Arrange
+        let mut layout = Layout::new(vec![Sheet::rectangle("first", 20.0, 10.0), Sheet::rectangle("second & last", 20.0, 10.0)]);
+        let part = PolygonWithHoles::from_outer(Polygon::square(4.0));
+        layout.placements.push(Placement::new("a", 0, &part, 0.0, &Vector::new().set_values(2.0, 2.0)));
+        layout.placements.push(Placement::new("b", 1, &part, PI / 4.0, &Vector::new().set_values(5.0, 5.0)));
+        let mut writer = SvgWriter::new(SvgStyles::default());
+        writer.flip_y = true;
+
+        // Act
+        writer.add_layout(&layout);
+        let svg = writer.render();
+
+        // Assert
+        assert_eq!(svg.matches("<g id=\"sheet-").count(), 2);
+        assert_eq!(svg.matches("<text").count(), 4);
+        assert!(svg.contains(">second &amp; last</text>"));
+        // second sheet is drawn right of the first one and above the x-axis
+        let rings = SvgReader::read_rings(&svg, ZERO_TOLERANCE).unwrap();
+        assert_eq!(rings.len(), 4);
+        assert_eq!(rings[2].bounding_box().min.x, 30.0);
+        assert_eq!(rings[2].bounding_box().max.y, 0.0);
+    }
+}
+
+#[cfg(test)]
+pub mod dxf_tests {
+    use super::super::io::{DxfReader, DxfWriter, DxfError};
+    use super::super::geometry::{Polygon, PolygonWithHoles, Vector, constants::ZERO_TOLERANCE};
+    use super::super::nesting::{Layout, Sheet, Placement, DefectZone};
+    use std::f64::consts::PI;
+
+    fn entities(body: &str) -> String {
+        format!("0\nSECTION\n2\nENTITIES\n{}0\nENDSEC\n0\nEOF\n", body)
+    }
+
+    #[test]
+    fn test_dxf_chains_lines_and_arcs_with_hole() {
+        // Arrange
+        // slot of 4 x 2 with round ends, pieces shuffled and partly reversed, plus a circular hole
+        let dxf = entities(concat!(
+            "0\nARC\n8\n0\n10\n4.0\n20\n0.0\n40\n1.0\n50\n270.0\n51\n90.0\n",
+            "0\nLINE\n8\n0\n10\n0.0\n20\n-1.0\n11\n4.0\n21\n-1.0\n",
+            "0\nARC\n8\n0\n10\n0.0\n20\n0.0\n40\n1.0\n50\n90.0\n51\n270.0\n",
+            "0\nLINE\n8\n0\n10\n0.0\n20\n1.0\n11\n4.0\n21\n1.0\n",
+            "0\nCIRCLE\n8\n0\n10\n2.0\n20\n0.0\n40\n0.5\n",
+            "0\nLINE\n8\n0\n10\n20.0\n20\n0.0\n11\n21.0\n21\n0.0\n"));
+
+        // Act
+        let parts = DxfReader::read_str(&dxf, 0.0001).unwrap();
+
+        // Assert
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].holes.len(), 1);
+        assert!((parts[0].calculate_area() - (8.0 + PI - 0.25 * PI)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_dxf_lwpolyline_bulges() {
+        // Arrange
+        // stadium from two lines and two half circle bulges, with windows line endings
+        let dxf = entities(concat!(
+            "0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n",
+            "10\n0.0\n20\n0.0\n10\n4.0\n20\n0.0\n42\n1.0\n",
+            "10\n4.0\n20\n2.0\n10\n0.0\n20\n2.0\n42\n1.0\n")).replace('\n', "\r\n");
+
+        // Act
+        let rings = DxfReader::read_rings(&dxf, 0.0001).unwrap();
+
+        // Assert
+        assert_eq!(rings.len(), 1);
+        assert!((rings[0].calculate_area() - (8.0 + PI)).abs() < 0.01);
+        assert!((rings[0].bounding_box().width() - 6.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_dxf_spline_closed_by_line() {
+        // Arrange
+        // clamped quadratic spline arching over the x-axis, closed by a line
+        let dxf = entities(concat!(
+            "0\nSPLINE\n8\n0\n70\n8\n71\n2\n72\n6\n73\n3\n",
+            "40\n0\n40\n0\n40\n0\n40\n1\n40\n1\n40\n1\n",
+            "10\n0.0\n20\n0.0\n10\n1.0\n20\n2.0\n10\n2.0\n20\n0.0\n",
+            "0\nLINE\n8\n0\n10\n0.0\n20\n0.0\n11\n2.0\n21\n0.0\n"));
+
+        // Act
+        let rings = DxfReader::read_rings(&dxf, 0.0001).unwrap();
+
+        // Assert
+        assert_eq!(rings.len(), 1);
+        assert!((rings[0].calculate_area() - 4.0 / 3.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_dxf_layout_round_trip() {
+        // Arrange
+        let mut layout = Layout::new(vec![Sheet::rectangle("sheet:1", 10.0, 10.0), Sheet::rectangle("sheet 2", 10.0, 10.0)]);
+        let part = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(1.0)]);
+        layout.placements.push(Placement::new("a", 0, &part, 0.0, &Vector::new().set_values(5.0, 5.0)));
+        layout.placements.push(Placement::new("b", 1, &part, PI / 2.0, &Vector::new().set_values(3.0, 3.0)));
+        let mut writer = DxfWriter::new();
+
+        // Act
+        writer.add_layout(&layout);
+        let dxf = writer.render();
+        let rings = DxfReader::read_rings(&dxf, ZERO_TOLERANCE).unwrap();
+
+        // Assert
+        assert!(dxf.contains("8\nsheet-0-sheet_1\n"));
+        assert!(dxf.contains("8\nsheet-1-sheet 2\n"));
+        assert_eq!(dxf.matches("0\nLAYER\n").count(), 2);
+        assert_eq!(rings.len(), 6);
+        assert!((rings[3].bounding_box().min.x - 20.0).abs() < ZERO_TOLERANCE);
+    }
+
+    #[test]
+    fn test_dxf_layout_defect_layer() {
+        // Arrange
+        let mut sheet = Sheet::rectangle("hide", 10.0, 10.0);
+        sheet.defects.push(DefectZone::new(Polygon::square(1.0)));
+        sheet.defects.push(DefectZone::new(Polygon::square(2.0)).set_grade(1));
+        let layout = Layout::new(vec![Sheet::rectangle("plate", 4.0, 4.0), sheet]);
+        let mut writer = DxfWriter::new();
+
+        // Act
+        writer.add_layout(&layout);
+        let dxf = writer.render();
+
+        // Assert
+        assert_eq!(dxf.matches("0\nLAYER\n").count(), 3);
+        assert_eq!(dxf.matches("0\nPOLYLINE\n8\nsheet-1-hide-defects\n").count(), 2);
+        assert!(!dxf.contains("sheet-0-plate-defects"));
+        assert_eq!(DxfReader::read_rings(&dxf, ZERO_TOLERANCE).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_dxf_errors() {
+        // Assert
+        assert_eq!(DxfReader::read_str("0\nSECTION\nx\nENTITIES\n", 0.1).err(), Some(DxfError::GroupCode(3)));
+        assert_eq!(DxfReader::read_str(&entities("0\nLINE\n10\nabc\n"), 0.1).err(), Some(DxfError::Number(8)));
+        assert_eq!(DxfReader::read_str(&entities("0\nCIRCLE\n10\n1.0\n"), 0.1).err(), Some(DxfError::Entity("CIRCLE".to_string())));
+        assert_eq!(DxfReader::read_str(&entities("0\nCIRCLE\n10\n1.0\n20\n1.0\n40\n-1.0\n"), 0.1).err(), Some(DxfError::Radius(12)));
+        assert_eq!(DxfReader::read_str(&entities("0\nSPLINE\n71\n3\n11\n0.0\n21\n0.0\n11\n1.0\n21\n1.0\n11\n2.0\n21\n0.0\n"), 0.1).err(), Some(DxfError::Entity("SPLINE".to_string())));
+        assert_eq!(DxfReader::read_str(&entities(""), 0.0).err(), Some(DxfError::Tolerance(0.0)));
+        assert_eq!(DxfReader::read_str(&entities(""), -0.1).err(), Some(DxfError::Tolerance(-0.1)));
+        assert!(matches!(DxfReader::read_str(&entities(""), f64::NAN).err(), Some(DxfError::Tolerance(t)) if t.is_nan()));
+    }
+}
+
+#[cfg(test)]
+pub mod wkt_tests {
+    use super::super::io::{Wkt, WktError};
+    use super::super::geometry::{Point, Polygon, PolygonWithHoles};
+
+    #[test]
+    fn test_wkt_round_trip_with_hole() {
+        // Arrange
+        let mut hole = Polygon::square(2.0);
+        hole.reverse_orientation();
+        let frame = PolygonWithHoles::new(Polygon::square(6.0), vec![hole]);
+
+        // Act
+        let text = Wkt::write_polygon(&frame);
+        let read = Wkt::read(&text).unwrap();
+
+        // Assert
+        assert_eq!(text, "POLYGON ((-3 -3, 3 -3, 3 3, -3 3, -3 -3), (-1 1, 1 1, 1 -1, -1 -1, -1 1))");
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0].outer.points, frame.outer.points);
+        assert_eq!(read[0].holes[0].points, frame.holes[0].points);
+    }
+
+    #[test]
+    fn test_wkt_multi_polygon_round_trip() {
+        // Arrange
+        let first = PolygonWithHoles::from_outer(Polygon::square(2.0));
+        let second = PolygonWithHoles::from_outer(Polygon::from_points(&vec![
+            Point::new().set_values(5.0, 0.0),
+            Point::new().set_values(6.5, 0.0),
+            Point::new().set_values(6.5, 1.25)]));
+
+        // Act
+        let text = Wkt::write_multi_polygon(&[first.clone(), second.clone()]);
+        let read = Wkt::read(&text).unwrap();
+
+        // Assert
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].outer.points, first.outer.points);
+        assert_eq!(read[1].outer.points, second.outer.points);
+        assert_eq!(Wkt::write_multi_polygon(&read), text);
+    }
+
+    #[test]
+    fn test_wkt_reads_dimensions_and_empty() {
+        // Act
+        let polys = Wkt::read("polygon z ((0 0 1, 2 0 1, 2 2 1, 0 2 1, 0 0 1))").unwrap();
+
+        // Assert
+        assert_eq!(polys[0].calculate_area(), 4.0);
+        assert_eq!(polys[0].outer.points.len(), 4);
+        assert!(Wkt::read("MULTIPOLYGON EMPTY").unwrap().is_empty());
+        assert_eq!(Wkt::write_multi_polygon(&[]), "MULTIPOLYGON EMPTY");
+    }
+
+    #[test]
+    fn test_wkt_errors() {
+        assert_eq!(Wkt::read("LINESTRING (0 0, 1 1)").err(), Some(WktError::UnsupportedType("LINESTRING".to_string())));
+        assert_eq!(Wkt::read("POLYGON ((0 0, 1 0, 1 1)").err(), Some(WktError::Syntax(24)));
+        assert_eq!(Wkt::read("POLYGON ((0 0, 1 x, 1 1))").err(), Some(WktError::Syntax(17)));
+        assert_eq!(Wkt::read("").err(), Some(WktError::Syntax(0)));
+    }
+}
+
+#[cfg(test)]
+pub mod geojson_tests {
+    use super::super::io::{GeoJson, GeoJsonError};
+    use super::super::geometry::{Polygon, PolygonWithHoles, Transform};
+
+    #[test]
+    fn test_geojson_round_trip_normalizes_orientation() {
+        // Arrange
+        let mut outer = Polygon::square(6.0);
+        outer.reverse_orientation();
+        let frame = PolygonWithHoles::new(outer, vec![Polygon::square(2.0)]);
+
+        // Act
+        let text = GeoJson::write_geometry(&[frame]);
+        let read = GeoJson::read_geometry(&text).unwrap();
+
+        // Assert
+        assert!(text.starts_with(r#"{"type":"Polygon","coordinates":[[[-3,-3],[3,-3]"#));
+        assert_eq!(read.len(), 1);
+        assert!(read[0].outer.calculate_signed_area() > 0.0);
+        assert!(read[0].holes[0].calculate_signed_area() < 0.0);
+        assert_eq!(read[0].calculate_area(), 32.0);
+    }
+
+    #[test]
+    fn test_geojson_multi_polygon_round_trip() {
+        // Arrange
+        let first = PolygonWithHoles::from_outer(Polygon::square(2.0));
+        let second = first.transform(&Transform::translation(10.5, -0.25));
+
+        // Act
+        let text = GeoJson::write_geometry(&[first.clone(), second.clone()]);
+        let read = GeoJson::read_geometry(&text).unwrap();
+
+        // Assert
+        assert!(text.starts_with(r#"{"type":"MultiPolygon""#));
+        assert_eq!(read[0].outer.points, first.outer.points);
+        assert_eq!(read[1].outer.points, second.outer.points);
+    }
+
+    #[test]
+    fn test_geojson_reads_feature_collection() {
+        // Arrange
+        let text = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {}, "geometry": {"type": "Point", "coordinates": [1, 2]}},
+            {"type": "Feature", "properties": {}, "geometry": null},
+            {"type": "Feature", "properties": {}, "geometry":
+                {"type": "MultiPolygon", "coordinates": [[[[0, 0, 5], [1, 0, 5], [1, 1, 5], [0, 0, 5]]], [[[2, 0], [4, 0], [4, 2], [2, 2]]]]}}
+        ]}"#;
+
+        // Act
+        let polys = GeoJson::read_geometry(text).unwrap();
+
+        // Assert
+        assert_eq!(polys.len(), 2);
+        assert_eq!(polys[0].outer.points.len(), 3);
+        assert_eq!(polys[1].calculate_area(), 4.0);
+    }
+
+    #[test]
+    fn test_geojson_errors() {
+        assert_eq!(GeoJson::read_geometry(r#"{"type": "Point", "coordinates": [1, 2]}"#).err(), Some(GeoJsonError::UnsupportedType("Point".to_string())));
+        assert_eq!(GeoJson::read_geometry(r#"{"type": "Polygon", "coordinates": [[[0, 0], [1]]]}"#).err(), Some(GeoJsonError::Coordinates));
+        assert_eq!(GeoJson::read_geometry(r#"{"type": "Polygon""#).err(), Some(GeoJsonError::Json(18)));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+pub mod serde_tests {
+    use super::super::geometry::{Point, Polygon, PolygonWithHoles, LineSegment, Arc, Path, Vector};
+    use super::super::nesting::{Layout, Sheet, Placement, Part, NestingJob};
+    use super::super::lib::PenetrationResult;
+
+    #[test]
+    fn test_serde_polygon_schema() {
+        // Arrange
+        let tri = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(2.0, 0.0),
+            Point::new().set_values(0.0, 1.5)]);
+
+        // Act
+        let json = serde_json::to_string(&tri).unwrap();
+        let read: Polygon = serde_json::from_str(&json).unwrap();
+
+        // Assert
+        assert_eq!(json, r#"{"points":[{"x":0.0,"y":0.0},{"x":2.0,"y":0.0},{"x":0.0,"y":1.5}]}"#);
+        assert_eq!(read.points, tri.points);
+    }
+
+    #[test]
+    fn test_serde_geometry_round_trip() {
+        // Arrange
+        let segment = LineSegment::new_from_points(&Point::new(), &Point::new().set_values(1.0, 2.0));
+        let path = Path::circle(&Point::new().set_values(3.0, 4.0), 2.0);
+        let arc = Arc::new(&Point::new(), 1.0, 0.25, -1.5);
+        let penetration = PenetrationResult::Overlap(0.5, Vector::new().set_values(0.0, -0.5));
+
+        // Act
+        let read_segment: LineSegment = serde_json::from_str(&serde_json::to_string(&segment).unwrap()).unwrap();
+        let read_path: Path = serde_json::from_str(&serde_json::to_string(&path).unwrap()).unwrap();
+        let read_arc: Arc = serde_json::from_str(&serde_json::to_string(&arc).unwrap()).unwrap();
+        let read_penetration: PenetrationResult = serde_json::from_str(&serde_json::to_string(&penetration).unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(read_segment, segment);
+        assert_eq!(read_path, path);
+        assert_eq!(read_arc, arc);
+        assert_eq!(read_penetration, penetration);
+    }
+
+    #[test]
+    fn test_serde_nesting_job_defaults() {
+        // Arrange
+        let json = r#"{
+            "sheets": [{"name": "plate", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 10, "y": 0}, {"x": 10, "y": 5}, {"x": 0, "y": 5}]}}}],
+            "parts": [{"name": "square", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 1, "y": 0}, {"x": 1, "y": 1}, {"x": 0, "y": 1}]}}}]
+        }"#;
+
+        // Act
+        let job: NestingJob = serde_json::from_str(json).unwrap();
+
+        // Assert
+        assert_eq!(job.sheets[0].shape.calculate_area(), 50.0);
+        assert!(!job.sheets[0].shape.has_holes());
+        assert_eq!(job.part_count(), 1);
+        assert_eq!(job.parts[0].rotations, vec![0.0]);
+        assert_eq!(job.spacing, 0.0);
+        assert_eq!(job.sheets[0].cost, 0.0);
+        assert!(job.bins.is_empty());
+    }
+
+    #[test]
+    fn test_serde_nesting_job_bins() {
+        // Arrange
+        let json = r#"{
+            "sheets": [],
+            "bins": [
+                {"name": "stock", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 10, "y": 0}, {"x": 10, "y": 5}, {"x": 0, "y": 5}]}}, "cost": 20.0},
+                {"name": "remnant", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 3, "y": 0}, {"x": 3, "y": 3}, {"x": 0, "y": 3}]}}, "quantity": 1}
+            ],
+            "parts": []
+        }"#;
+
+        // Act
+        let job: NestingJob = serde_json::from_str(json).unwrap();
+
+        // Assert
+        assert_eq!(job.bins.len(), 2);
+        assert_eq!((job.bins[0].cost, job.bins[0].quantity), (20.0, None));
+        assert_eq!((job.bins[1].cost, job.bins[1].quantity), (0.0, Some(1)));
+        assert!(!job.part_in_part);
+    }
+
+    #[test]
+    fn test_serde_sheet_defects() {
+        // Arrange
+        let json = r#"{
+            "sheets": [
+                {"name": "hide", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 10, "y": 0}, {"x": 10, "y": 5}]}},
+                 "defects": [{"shape": {"points": [{"x": 1, "y": 1}, {"x": 2, "y": 1}, {"x": 2, "y": 2}]}},
+                             {"shape": {"points": [{"x": 5, "y": 1}, {"x": 6, "y": 1}, {"x": 6, "y": 2}]}, "grade": 2}]}
+            ],
+            "parts": [{"name": "square", "shape": {"outer": {"points": [{"x": 0, "y": 0}, {"x": 1, "y": 0}, {"x": 1, "y": 1}, {"x": 0, "y": 1}]}}, "grade": 1}]
+        }"#;
+
+        // Act
+        let job: NestingJob = serde_json::from_str(json).unwrap();
+
+        // Assert
+        assert_eq!(job.sheets[0].defects.iter().map(|d| d.grade).collect::<Vec<_>>(), vec![None, Some(2)]);
+        assert_eq!(job.parts[0].grade, 1);
+        assert!(job.sheets[0].defects[1].accepts(job.parts[0].grade));
+        assert!(!job.sheets[0].defects[0].accepts(0));
+    }
+
+    #[test]
+    fn test_serde_layout_round_trip() {
+        // Arrange
+        let part = PolygonWithHoles::from_outer(Polygon::square(2.0));
+        let mut layout = Layout::new(vec![Sheet::rectangle("sheet", 4.0, 2.0)]);
+        layout.placements.push(Placement::new("square", 0, &part, 0.0, &Vector::new().set_values(1.0, 1.0)));
+        let job = NestingJob::new(layout.sheets.clone(), vec![Part::new("square", part, 2).set_rotation_steps(2)]);
+
+        // Act
+        let read_layout: Layout = serde_json::from_str(&serde_json::to_string(&layout).unwrap()).unwrap();
+        let read_job: NestingJob = serde_json::from_str(&serde_json::to_string(&job).unwrap()).unwrap();
+
+        // Assert
+        assert_eq!(read_layout.calculate_utilization(0), 0.5);
+        assert_eq!(read_layout.placements[0].translation, layout.placements[0].translation);
+        assert_eq!(read_job.parts[0].rotations, job.parts[0].rotations);
+        assert_eq!(read_job.part_count(), 2);
+    }
+}
+
+#[cfg(test)]
+pub mod validation_tests {
+    use super::super::geometry::{Point, Polygon, PolygonWithHoles, PolygonIssue, Transform};
+
+    #[test]
+    fn test_validate_degenerate_rings() {
+        // Arrange
+        let line = PolygonWithHoles::from_outer(Polygon::from_points(&vec![Point::new(), Point::new().set_values(1.0, 0.0)]));
+        let duplicate = PolygonWithHoles::from_outer(Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(2.0, 0.0),
+            Point::new().set_values(2.0, 0.0),
+            Point::new().set_values(0.0, 2.0)]));
+
+        // Assert
+        assert_eq!(line.validate(0.0001), vec![PolygonIssue::TooFewPoints(0)]);
+        assert_eq!(duplicate.validate(0.0001), vec![PolygonIssue::DuplicatePoint(0, 2)]);
+    }
+
+    #[test]
+    fn test_validate_holes() {
+        // Arrange
+        let outside = PolygonWithHoles::new(Polygon::square(2.0), vec![Polygon::square(1.0).transform(&Transform::translation(5.0, 0.0))]);
+        let touching = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0), Polygon::square(2.0).transform(&Transform::translation(0.5, 0.0))]);
+
+        // Assert
+        assert_eq!(outside.validate(0.0001), vec![PolygonIssue::HoleOutside(1)]);
+        assert_eq!(touching.validate(0.0001), vec![PolygonIssue::RingsIntersect(1, 2)]);
+    }
+}
+
+#[cfg(test)]
+pub mod nesting_tests {
+    use super::super::geometry::{Point, Polygon, PolygonWithHoles, Transform};
+    use super::super::nesting::{Nester, NestingJob, Layout, Sheet, Bin, Part, DefectZone};
+    use super::super::lib::{calculate_nfp, is_inside_nfp};
+    use std::f64::consts::PI;
+
+    // checks that no two placements of the nested job overlap and all of them lie inside their sheet
+    fn assert_valid_layout(job: &NestingJob, tol: f64) {
+        assert_valid_placements(&Nester::nest(job, tol), tol);
+    }
+
+    fn assert_valid_placements(layout: &Layout, tol: f64) {
+        for (i, first) in layout.placements.iter().enumerate() {
+            let sheet = &layout.sheets[first.sheet].shape.outer;
+            assert!(first.shape.outer.points.iter().all(|pt| sheet.contains_point(pt, tol)));
+            for other in layout.placements.iter().skip(i + 1).filter(|o| o.sheet == first.sheet) {
+                let nfp = calculate_nfp(&first.shape.outer, &other.shape.outer, tol);
+                assert!(!is_inside_nfp(&Point::new(), &nfp, tol));
+            }
+        }
+    }
+
+    #[test]
+    fn test_nfp_of_concave_polygon() {
+        // Arrange
+        let u_shape = Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(5.0, 0.0),
+            Point::new().set_values(5.0, 3.0),
+            Point::new().set_values(4.0, 3.0),
+            Point::new().set_values(4.0, 1.0),
+            Point::new().set_values(1.0, 1.0),
+            Point::new().set_values(1.0, 3.0),
+            Point::new().set_values(0.0, 3.0)]);
+        let square = Polygon::square(1.0);
+
+        // Act
+        let nfp = calculate_nfp(&u_shape, &square, 0.0001);
+
+        // Assert
+        assert!(nfp.len() >= 3);
+        assert!(!is_inside_nfp(&Point::new().set_values(2.5, 2.0), &nfp, 0.0001));
+        assert!(is_inside_nfp(&Point::new().set_values(2.5, 0.5), &nfp, 0.0001));
+        assert!(is_inside_nfp(&Point::new().set_values(0.5, 2.0), &nfp, 0.0001));
+    }
+
+    #[test]
+    fn test_nest_uses_rotations() {
+        // Arrange
+        let bar = PolygonWithHoles::from_outer(Polygon::square(1.0).transform(&Transform::scale(4.0, 1.0)));
+        let fixed = NestingJob::new(vec![Sheet::rectangle("sheet", 2.0, 8.0)], vec![Part::new("bar", bar.clone(), 2)]);
+        let rotating = NestingJob::new(vec![Sheet::rectangle("sheet", 2.0, 8.0)], vec![Part::new("bar", bar, 2).set_rotation_steps(4)]);
+
+        // Act
+        let fixed_layout = Nester::nest(&fixed, 0.0001);
+        let rotating_layout = Nester::nest(&rotating, 0.0001);
+
+        // Assert
+        assert!(fixed_layout.placements.is_empty());
+        assert_eq!(rotating_layout.placements.len(), 2);
+        assert!((rotating_layout.placements[0].rotation - PI / 2.0).abs() < 0.0001);
+        assert!(rotating_layout.unplaced.is_empty());
+    }
+
+    #[test]
+    fn test_nest_overflows_to_next_sheet_and_avoids_holes() {
+        // Arrange
+        let frame = Sheet::new("frame", PolygonWithHoles::new(
+            Polygon::square(1.0).transform(&Transform::translation(0.5, 0.5).then(&Transform::scale(6.0, 2.0))),
+            vec![Polygon::square(2.0).transform(&Transform::translation(3.0, 1.0))]));
+        let job = NestingJob::new(
+            vec![frame, Sheet::rectangle("spare", 2.0, 2.0)],
+            vec![Part::new("square", PolygonWithHoles::from_outer(Polygon::square(2.0)), 3)]);
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        assert_eq!(layout.placements_on_sheet(0).count(), 2);
+        assert_eq!(layout.placements_on_sheet(1).count(), 1);
+        assert!((layout.placements[1].translation.x - 5.0).abs() < 0.0001);
+        assert_valid_layout(&job, 0.0001);
+    }
+
+    #[test]
+    fn test_nest_on_concave_sheet() {
+        // Arrange
+        let u_shape = Sheet::new("u", PolygonWithHoles::from_outer(Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(5.0, 0.0),
+            Point::new().set_values(5.0, 3.0),
+            Point::new().set_values(4.0, 3.0),
+            Point::new().set_values(4.0, 1.0),
+            Point::new().set_values(1.0, 1.0),
+            Point::new().set_values(1.0, 3.0),
+            Point::new().set_values(0.0, 3.0)])));
+        let bar = PolygonWithHoles::from_outer(Polygon::square(1.0).transform(&Transform::scale(5.0, 0.5)));
+        let job = NestingJob::new(vec![u_shape], vec![Part::new("bar", bar, 3)]);
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        // a third bar would have all corners on the arms of the u, but cross its gap
+        assert_eq!(layout.placements.len(), 2);
+        assert_eq!(layout.unplaced, vec!["bar".to_string()]);
+        assert!(layout.placements.iter().all(|p| p.shape.bounding_box().max.y <= 1.0 + 0.0001));
+    }
+
+    #[test]
+    fn test_nest_avoids_defect_zones_by_grade() {
+        // Arrange
+        let cell = |x: f64| Polygon::square(1.0).transform(&Transform::translation(x, 0.5));
+        let mut sheet = Sheet::rectangle("hide", 3.0, 1.0);
+        sheet.defects.push(DefectZone::new(cell(1.5)));
+        sheet.defects.push(DefectZone::new(cell(0.5)).set_grade(1));
+        let square = PolygonWithHoles::from_outer(Polygon::square(1.0));
+        let job = NestingJob::new(vec![sheet], vec![
+            Part::new("fine", square.clone(), 1).set_grade(2),
+            Part::new("coarse", square.clone(), 1).set_grade(1),
+            Part::new("any", square, 1)]);
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        // the fine part skips the graded zone, the coarse part may use it and nothing goes on the defect
+        assert_eq!(layout.placements.len(), 2);
+        assert_eq!(layout.placements[0].part, "fine");
+        assert!((layout.placements[0].translation.x - 2.5).abs() < 0.0001);
+        assert_eq!(layout.placements[1].part, "coarse");
+        assert!((layout.placements[1].translation.x - 0.5).abs() < 0.0001);
+        assert_eq!(layout.unplaced, vec!["any".to_string()]);
+    }
+
+    #[test]
+    fn test_nest_part_in_part_prefers_holes() {
+        // Arrange
+        let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+        let square = PolygonWithHoles::from_outer(Polygon::square(1.0));
+        let mut job = NestingJob::new(
+            vec![Sheet::rectangle("sheet", 6.0, 4.0)],
+            vec![Part::new("frame", frame, 1), Part::new("square", square, 2)]);
+        job.part_in_part = true;
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        // the sheet has room right of the frame, but both squares go into its hole
+        assert!(layout.unplaced.is_empty());
+        assert_eq!(layout.placements[0].part, "frame");
+        assert!((layout.placements[1].translation.x - 1.5).abs() < 0.0001);
+        assert!((layout.placements[1].translation.y - 1.5).abs() < 0.0001);
+        assert!((layout.placements[2].translation.x - 2.5).abs() < 0.0001);
+        assert!((layout.placements[2].translation.y - 1.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_nest_part_in_part_keeps_spacing() {
+        // Arrange
+        let frame = PolygonWithHoles::new(Polygon::square(4.0), vec![Polygon::square(2.0)]);
+        let square = PolygonWithHoles::from_outer(Polygon::square(1.0));
+        let mut job = NestingJob::new(
+            vec![Sheet::rectangle("sheet", 4.0, 4.0)],
+            vec![Part::new("frame", frame, 1), Part::new("square", square, 2)]);
+        job.spacing = 0.25;
+
+        // Act
+        let without = Nester::nest(&job, 0.0001);
+        job.part_in_part = true;
+        let with = Nester::nest(&job, 0.0001);
+
+        // Assert
+        // the hole shrinks to 1.5 by the spacing and holds one square only
+        assert_eq!(without.placements.len(), 1);
+        assert_eq!(with.placements.len(), 2);
+        assert!((with.placements[1].translation.x - 1.75).abs() < 0.0001);
+        assert!((with.placements[1].translation.y - 1.75).abs() < 0.0001);
+        assert_eq!(with.unplaced, vec!["square".to_string()]);
+    }
+
+    #[test]
+    fn test_nest_keeps_spacing() {
+        // Arrange
+        let mut job = NestingJob::new(
+            vec![Sheet::rectangle("sheet", 10.0, 2.0)],
+            vec![Part::new("square", PolygonWithHoles::from_outer(Polygon::square(2.0)), 3)]);
+        job.spacing = 0.5;
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        assert_eq!(layout.placements.len(), 3);
+        assert!((layout.placements[1].translation.x - 3.5).abs() < 0.0001);
+        assert!((layout.placements[2].translation.x - 6.0).abs() < 0.0001);
+        assert_valid_layout(&job, 0.0001);
+    }
+
+    #[test]
+    fn test_nest_uses_limited_remnants_first() {
+        // Arrange
+        let square = PolygonWithHoles::from_outer(Polygon::square(2.0));
+        let mut job = NestingJob::new(Vec::new(), vec![Part::new("square", square, 3)]);
+        job.bins.push(Bin::new("stock", Sheet::rectangle("", 2.0, 2.0).shape, 3.0));
+        job.bins.push(Bin::new("remnant", Sheet::rectangle("", 2.5, 2.5).shape, 1.0).set_quantity(1));
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        assert!(layout.unplaced.is_empty());
+        assert_eq!(layout.sheets.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["remnant #1", "stock #1", "stock #2"]);
+        assert!(layout.placements.iter().all(|p| p.part_index == Some(0)));
+        assert_eq!(layout.calculate_cost(), 7.0);
+        assert_valid_layout(&job, 0.0001);
+    }
+
+    #[test]
+    fn test_nest_downsizes_opened_sheets() {
+        // Arrange
+        let mut job = NestingJob::new(
+            vec![Sheet::rectangle("offcut", 1.0, 1.0)],
+            vec![
+                Part::new("large", PolygonWithHoles::from_outer(Polygon::square(2.0)), 1),
+                Part::new("small", PolygonWithHoles::from_outer(Polygon::square(1.0)), 1)]);
+        job.bins.push(Bin::new("wide", Sheet::rectangle("", 3.0, 2.0).shape, 5.0));
+        job.bins.push(Bin::new("square", Sheet::rectangle("", 2.0, 2.0).shape, 4.5));
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        // the wide bin has the lowest cost per area for both parts, but the small one ends up on the offcut
+        assert!(layout.unplaced.is_empty());
+        assert_eq!(layout.sheets[1].name, "square #1");
+        assert_eq!(layout.placements_on_sheet(0).next().unwrap().part, "small");
+        assert_eq!(layout.placements_on_sheet(1).next().unwrap().part_index, Some(0));
+        assert_eq!(layout.calculate_cost(), 4.5);
+        assert_valid_layout(&job, 0.0001);
+    }
+
+    #[test]
+    fn test_nest_flat_part_on_free_bins() {
+        // Arrange
+        let flat = Polygon::from_points(&vec![Point::new(), Point::new().set_values(1.0, 0.0), Point::new().set_values(2.0, 0.0)]);
+        let mut job = NestingJob::new(Vec::new(), vec![Part::new("flat", PolygonWithHoles::from_outer(flat), 1)]);
+        job.bins.push(Bin::new("large", Sheet::rectangle("", 4.0, 4.0).shape, 0.0));
+        job.bins.push(Bin::new("small", Sheet::rectangle("", 3.0, 3.0).shape, 0.0));
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        assert!(layout.unplaced.is_empty());
+        assert_eq!(layout.sheets.len(), 1);
+        assert_eq!(layout.sheets[0].name, "small #1");
+    }
+
+    #[test]
+    fn test_nest_without_fitting_bin() {
+        // Arrange
+        let mut job = NestingJob::new(Vec::new(), vec![Part::new("square", PolygonWithHoles::from_outer(Polygon::square(3.0)), 1)]);
+        job.bins.push(Bin::new("stock", Sheet::rectangle("", 2.0, 2.0).shape, 1.0));
+
+        // Act
+        let layout = Nester::nest(&job, 0.0001);
+
+        // Assert
+        assert!(layout.sheets.is_empty());
+        assert_eq!(layout.unplaced, vec!["square".to_string()]);
+        assert_eq!(layout.calculate_cost(), 0.0);
+    }
+
+    #[test]
+    fn test_nest_strip_compresses_length() {
+        // Arrange
+        let job = NestingJob::new(Vec::new(), vec![
+            Part::new("wide", PolygonWithHoles::from_outer(Polygon::square(1.0).transform(&Transform::scale(2.0, 1.0))), 2),
+            Part::new("tall", PolygonWithHoles::from_outer(Polygon::square(1.0).transform(&Transform::scale(1.0, 2.0))), 2)]);
+
+        // Act
+        let strip = Nester::nest_strip(&job, 4.0, 0.0001);
+
+        // Assert
+        // bottom-left stacks the wide parts and needs a length of 3, the column fill closes the gap
+        assert!(strip.layout.unplaced.is_empty());
+        assert!(strip.compressions > 0);
+        assert!((strip.length - 2.0).abs() < 0.0001);
+        assert!((strip.utilization - 1.0).abs() < 0.0001);
+        assert_eq!(strip.layout.sheets.len(), 1);
+        assert!((strip.layout.sheets[0].shape.calculate_area() - 8.0).abs() < 0.0001);
+        assert_valid_placements(&strip.layout, 0.0001);
+    }
+
+    #[test]
+    fn test_nest_strip_skips_parts_wider_than_strip() {
+        // Arrange
+        let mut job = NestingJob::new(Vec::new(), vec![
+            Part::new("large", PolygonWithHoles::from_outer(Polygon::square(3.0)), 1),
+            Part::new("small", PolygonWithHoles::from_outer(Polygon::square(1.0)), 3)]);
+        job.spacing = 0.5;
+
+        // Act
+        let strip = Nester::nest_strip(&job, 2.5, 0.0001);
+
+        // Assert
+        assert_eq!(strip.layout.unplaced, vec!["large".to_string()]);
+        assert_eq!(strip.layout.placements.len(), 3);
+        assert!((strip.length - 2.5).abs() < 0.0001);
+        assert!((strip.utilization - 3.0 / 6.25).abs() < 0.0001);
+        assert_valid_placements(&strip.layout, 0.0001);
+    }
+}
+
+#[cfg(all(test, feature = "capi"))]
+pub mod capi_tests {
+    use super::super::capi::*;
+    use std::ptr::{null, null_mut};
+
+    const SQUARE: [f64; 8] = [0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0];
+    const SMALL_SQUARE: [f64; 8] = [1.0, 1.0, 2.0, 1.0, 2.0, 2.0, 1.0, 2.0];
+
+    fn create(coords: &[f64]) -> *mut NfpPolygon {
+        let mut poly = null_mut();
+        assert_eq!(unsafe { nfp_polygon_create(coords.as_ptr(), coords.len() / 2, &mut poly) }, NfpError::Ok);
+        poly
+    }
+
+    #[test]
+    fn test_capi_polygon_with_hole() {
+        unsafe {
+            // Arrange
+            let poly = create(&SQUARE);
+            assert_eq!(nfp_polygon_add_hole(poly, SMALL_SQUARE.as_ptr(), 4), NfpError::Ok);
+
+            // Act
+            let mut area = 0.0;
+            let mut rings = 0;
+            let mut written = 0;
+            let mut buffer = [0.0; 6];
+            let area_result = nfp_polygon_area(poly, &mut area);
+            let count_result = nfp_polygon_ring_count(poly, &mut rings);
+            let query_result = nfp_polygon_ring_points(poly, 1, null_mut(), 0, &mut written);
+            let small_result = nfp_polygon_ring_points(poly, 1, buffer.as_mut_ptr(), 3, &mut written);
+
+            // Assert
+            assert_eq!((area_result, area), (NfpError::Ok, 15.0));
+            assert_eq!((count_result, rings), (NfpError::Ok, 2));
+            assert_eq!((query_result, written), (NfpError::Ok, 4));
+            assert_eq!(small_result, NfpError::BufferTooSmall);
+
+            let mut buffer = [0.0; 8];
+            assert_eq!(nfp_polygon_ring_points(poly, 1, buffer.as_mut_ptr(), 4, &mut written), NfpError::Ok);
+            assert_eq!(buffer, SMALL_SQUARE);
+            nfp_polygon_free(poly);
+        }
+    }
+
+    #[test]
+    fn test_capi_header_is_up_to_date() {
+        // Arrange
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/rust_nfp.h"));
+
+        // Assert
+        assert_eq!(include_str!("../../include/rust_nfp.h"), generated, "regenerate the header with RUST_NFP_UPDATE_HEADER=1");
+    }
+
+    #[test]
+    fn test_capi_errors() {
+        unsafe {
+            let mut poly = null_mut();
+            let mut area = 0.0;
+            let nan = [0.0, 0.0, f64::NAN, 0.0, 1.0, 1.0];
+
+            assert_eq!(nfp_polygon_create(null(), 3, &mut poly), NfpError::NullPointer);
+            assert_eq!(nfp_polygon_create(SQUARE.as_ptr(), 2, &mut poly), NfpError::InvalidArgument);
+            assert_eq!(nfp_polygon_create(nan.as_ptr(), 3, &mut poly), NfpError::InvalidArgument);
+            assert_eq!(nfp_polygon_area(null(), &mut area), NfpError::NullPointer);
+            assert!(poly.is_null());
+
+            let poly = create(&SQUARE);
+            let mut written = 0;
+            assert_eq!(nfp_polygon_area(poly, null_mut()), NfpError::NullPointer);
+            assert_eq!(nfp_polygon_clone(poly, null_mut()), NfpError::NullPointer);
+            assert_eq!(nfp_polygon_create(SQUARE.as_ptr(), 4, null_mut()), NfpError::NullPointer);
+            assert_eq!(nfp_polygon_ring_points(poly, 1, null_mut(), 0, &mut written), NfpError::InvalidArgument);
+            nfp_polygon_free(poly);
+            nfp_polygon_free(null_mut());
+        }
+    }
+
+    #[test]
+    fn test_capi_nfp_and_ifp() {
+        unsafe {
+            // Arrange
+            let sheet = create(&SQUARE);
+            let part = create(&SMALL_SQUARE);
+
+            // Act
+            let mut nfp = null_mut();
+            let mut ifp = null_mut();
+            let mut not_fitting = null_mut();
+            let nfp_result = nfp_calculate_nfp(sheet, part, 0.0001, &mut nfp);
+            let ifp_result = nfp_calculate_ifp(sheet, part, 0.0001, &mut ifp);
+            let not_fitting_result = nfp_calculate_ifp(part, sheet, 0.0001, &mut not_fitting);
+
+            // Assert
+            assert_eq!((nfp_result, ifp_result, not_fitting_result), (NfpError::Ok, NfpError::Ok, NfpError::NoResult));
+
+            let mut count = 0;
+            let mut inside = false;
+            assert_eq!(nfp_polygon_list_count(nfp, &mut count), NfpError::Ok);
+            assert_eq!(count, 1);
+            assert_eq!(nfp_is_inside_nfp(nfp, 0.0, 0.0, 0.0001, &mut inside), NfpError::Ok);
+            assert!(inside);
+            assert_eq!(nfp_is_inside_nfp(nfp, -2.0, 0.0, 0.0001, &mut inside), NfpError::Ok);
+            assert!(!inside);
+
+            // part at (1, 1)..(2, 2) is moved by (-1, -1)..(2, 2) within the sheet
+            let mut area = 0.0;
+            assert_eq!(nfp_polygon_area(ifp, &mut area), NfpError::Ok);
+            assert_eq!(area, 9.0);
+
+            nfp_polygon_list_free(nfp);
+            nfp_polygon_free(ifp);
+            nfp_polygon_free(part);
+            nfp_polygon_free(sheet);
+        }
+    }
+
+    #[test]
+    fn test_capi_offset_and_intersections() {
+        unsafe {
+            // Arrange
+            let frame = create(&SQUARE);
+            assert_eq!(nfp_polygon_add_hole(frame, SMALL_SQUARE.as_ptr(), 4), NfpError::Ok);
+            let crossing = create(&[2.5, -1.0, 3.0, -1.0, 3.0, 5.0, 2.5, 5.0]);
+
+            // Act
+            let mut shrunk = null_mut();
+            let mut grown = null_mut();
+            let mut written = 0;
+            let shrunk_result = nfp_polygon_offset(frame, -0.25, 0.01, 0.0001, &mut shrunk);
+            let grown_result = nfp_polygon_offset(frame, 1.0, 0.01, 0.0001, &mut grown);
+            let intersection_result = nfp_polygon_intersections(frame, crossing, 0.0001, null_mut(), 0, &mut written);
+
+            // Assert
+            let mut area = 0.0;
+            let mut rings = 0;
+            assert_eq!((shrunk_result, grown_result), (NfpError::Ok, NfpError::Ok));
+            assert_eq!(nfp_polygon_area(shrunk, &mut area), NfpError::Ok);
+            // the hole grows with rounded corners
+            assert!((area - (3.5 * 3.5 - (1.0 + 4.0 * 0.25 + std::f64::consts::PI * 0.25 * 0.25))).abs() < 0.01);
+            assert_eq!(nfp_polygon_ring_count(grown, &mut rings), NfpError::Ok);
+            assert_eq!(rings, 1);
+            assert_eq!((intersection_result, written), (NfpError::Ok, 4));
+
+            nfp_polygon_free(crossing);
+            nfp_polygon_free(grown);
+            nfp_polygon_free(shrunk);
+            nfp_polygon_free(frame);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod boolean_tests {
+    use super::super::geometry::{Point, Polygon, PolygonWithHoles, BooleanOperation, Transform};
+
+    const TOL: f64 = 0.0001;
+
+    fn square(size: f64, x: f64, y: f64) -> PolygonWithHoles {
+        PolygonWithHoles::from_outer(Polygon::square(size).transform(&Transform::translation(x, y)))
+    }
+
+    #[test]
+    fn test_boolean_identical_polygons() {
+        // Arrange
+        let first = square(2.0, 0.0, 0.0);
+
+        // Act
+        let union = first.boolean(&first, BooleanOperation::Union, TOL);
+        let intersection = first.boolean(&first, BooleanOperation::Intersection, TOL);
+        let difference = first.boolean(&first, BooleanOperation::Difference, TOL);
+
+        // Assert
+        assert_eq!(union.len(), 1);
+        assert_eq!(union[0].outer.points.len(), 4);
+        assert_eq!(intersection[0].calculate_area(), 4.0);
+        assert!(difference.is_empty());
+    }
+
+    #[test]
+    fn test_boolean_difference_creates_hole() {
+        // Arrange
+        let plate = square(4.0, 0.0, 0.0);
+        let cutout = square(2.0, 0.5, 0.0);
+
+        // Act
+        let frame = plate.boolean(&cutout, BooleanOperation::Difference, TOL);
+        let refilled = frame[0].boolean(&cutout, BooleanOperation::Union, TOL);
+
+        // Assert
+        assert_eq!(frame.len(), 1);
+        assert_eq!(frame[0].holes.len(), 1);
+        assert_eq!(frame[0].calculate_area(), 12.0);
+        assert_eq!(refilled.len(), 1);
+        assert!(!refilled[0].has_holes());
+        assert_eq!(refilled[0].outer.points.len(), 4);
+    }
+
+    #[test]
+    fn test_boolean_touching_and_disjoint() {
+        // Arrange
+        let first = square(2.0, 0.0, 0.0);
+        let beside = square(2.0, 2.0, 0.5);
+        let far = square(2.0, 10.0, 0.0);
+
+        // Act
+        let merged = first.boolean(&beside, BooleanOperation::Union, TOL);
+        let touching = first.boolean(&beside, BooleanOperation::Intersection, TOL);
+        let separate = first.boolean(&far, BooleanOperation::Union, TOL);
+
+        // Assert
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].outer.points.len(), 8);
+        assert_eq!(merged[0].calculate_area(), 8.0);
+        assert!(touching.is_empty());
+        assert_eq!(separate.len(), 2);
+    }
+
+    #[test]
+    fn test_boolean_concave_split() {
+        // Arrange
+        let u_shape = PolygonWithHoles::from_outer(Polygon::from_points(&vec![
+            Point::new(),
+            Point::new().set_values(5.0, 0.0),
+            Point::new().set_values(5.0, 3.0),
+            Point::new().set_values(4.0, 3.0),
+            Point::new().set_values(4.0, 1.0),
+            Point::new().set_values(1.0, 1.0),
+            Point::new().set_values(1.0, 3.0),
+            Point::new().set_values(0.0, 3.0)]));
+        let bar = PolygonWithHoles::from_outer(Polygon::square(1.0).transform(&Transform::translation(0.5, 0.5).then(&Transform::scale(7.0, 1.0)).then(&Transform::translation(-1.0, 1.5))));
+
+        // Act
+        let legs = u_shape.boolean(&bar, BooleanOperation::Intersection, TOL);
+        let cut = u_shape.boolean(&bar, BooleanOperation::Difference, TOL);
+
+        // Assert
+        assert_eq!(legs.len(), 2);
+        assert!(legs.iter().all(|p| (p.calculate_area() - 1.0).abs() < TOL));
+        assert_eq!(cut.len(), 3);
+        assert!((cut.iter().map(|p| p.calculate_area()).sum::<f64>() - 7.0).abs() < TOL);
+    }
+}
+
+// run in a headless wasm runtime with `wasm-pack test --node --features wasm`
+#[cfg(all(test, feature = "wasm"))]
+pub mod wasm_tests {
+    use super::super::wasm::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const SQUARE: [f64; 8] = [0.0, 0.0, 2.0, 0.0, 2.0, 2.0, 0.0, 2.0];
+    const SMALL_SQUARE: [f64; 8] = [0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0];
+
+    #[test]
+    fn test_wasm_nfp() {
+        // Act
+        let nfp = calculate_nfp(&SQUARE, &SMALL_SQUARE, 0.0001).unwrap();
+
+        // Assert
+        assert_eq!(nfp.length(), 1);
+        let piece = nfp.get(0).unwrap();
+        assert_eq!(piece.len() % 2, 0);
+        assert_eq!(piece.iter().step_by(2).cloned().fold(f64::INFINITY, f64::min), -1.0);
+        assert_eq!(piece.iter().skip(1).step_by(2).cloned().fold(f64::NEG_INFINITY, f64::max), 2.0);
+        assert!(is_inside_nfp(&nfp, 0.5, 0.5, 0.0001));
+        assert!(!is_inside_nfp(&nfp, 2.0, 0.5, 0.0001));
+    }
+
+    #[test]
+    fn test_wasm_ifp() {
+        // Act
+        let ifp = calculate_ifp(&SQUARE, &SMALL_SQUARE, 0.0001).unwrap();
+        let no_fit = calculate_ifp(&SMALL_SQUARE, &SQUARE, 0.0001).unwrap();
+
+        // Assert
+        assert_eq!(ifp, Some(vec![0.0, 0.0, 1.0, 1.0]));
+        assert_eq!(no_fit, None);
+    }
+
+    #[test]
+    fn test_wasm_nest() {
+        // Arrange
+        let mut job = WasmNestingJob::new(0.0);
+        let sheet = job.add_sheet("sheet", &[0.0, 0.0, 5.0, 0.0, 5.0, 2.0, 0.0, 2.0]).unwrap();
+        job.add_sheet_hole(sheet, &[4.2, 0.2, 4.8, 0.2, 4.8, 0.8, 4.2, 0.8]).unwrap();
+        job.add_part("square", &SQUARE, 3, &[]).unwrap();
+
+        // Act
+        let layout = job.nest(0.0001);
+
+        // Assert
+        assert_eq!(job.part_count(), 3);
+        assert_eq!(layout.placement_count(), 2);
+        assert_eq!(layout.unplaced(), vec!["square".to_string()]);
+        assert_eq!(layout.placement_part(1).unwrap(), "square");
+        assert_eq!(layout.placement_sheet(1).unwrap(), 0);
+        assert_eq!(layout.placement_translation(0).unwrap(), vec![0.0, 0.0]);
+        assert_eq!(layout.placement_outline(1).unwrap().len(), 8);
+        assert!((layout.utilization(0).unwrap() - 8.0 / 9.64).abs() < 0.0001);
+    }
+}
+
+#[cfg(all(test, feature = "log"))]
+pub mod log_tests {
+    use super::super::geometry::Polygon;
+    use super::super::lib::calculate_nfp;
+    use log::kv::{Key, VisitSource, Value, Error};
+    use std::sync::Mutex;
+
+    type Fields = Vec<(String, String)>;
+
+    // records of all tests, they run in parallel and share the global logger
+    static RECORDS: Mutex<Vec<(String, Fields)>> = Mutex::new(Vec::new());
+
+    struct Recorder;
+
+    struct Visitor(Fields);
+
+    impl<'kvs> VisitSource<'kvs> for Visitor {
+        fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), Error> {
+            self.0.push((key.to_string(), value.to_string()));
+            Ok(())
+        }
+    }
+
+    impl log::Log for Recorder {
+        fn enabled(&self, _: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            let mut visitor = Visitor(Vec::new());
+            record.key_values().visit(&mut visitor).unwrap();
+            RECORDS.lock().unwrap().push((record.args().to_string(), visitor.0));
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_log_structured_fields() {
+        // Arrange
+        let _ = log::set_logger(&Recorder);
+        log::set_max_level(log::LevelFilter::Trace);
+
+        // Act
+        calculate_nfp(&Polygon::square(2.0), &Polygon::square(1.0), 0.0001);
+
+        // Assert
+        let records = RECORDS.lock().unwrap();
+        let has = |fields: &Fields, key: &str, value: &str| fields.iter().any(|(k, v)| k == key && v == value);
+        assert!(records.iter().any(|(message, fields)| message == "convex nfp" && has(fields, "first_corners", "4") && has(fields, "edges", "8")));
+        assert!(records.iter().any(|(message, fields)| message == "nfp of convex pieces" && has(fields, "first_pieces", "1")));
+        assert!(records.iter().any(|(message, fields)| message == "convex nfp edge" && fields.len() == 2));
+    }
+}
+
+#[cfg(test)]
+pub mod trace_tests {
+    use super::super::geometry::{Polygon, Point, LineSegment, Vector};
+    use super::super::io::{SvgWriter, SvgStyles};
+    use super::super::lib::{calculate_nfp, calculate_nfp_traced, calculate_penetration, calculate_penetration_traced, PenetrationResult};
+    use super::super::trace::{NfpTrace, NfpTraceStep};
+
+    fn l_shape() -> Polygon {
+        Polygon::from_points(&vec![
+            Point::new(), Point::new().set_values(4.0, 0.0), Point::new().set_values(4.0, 1.0),
+            Point::new().set_values(1.0, 1.0), Point::new().set_values(1.0, 4.0), Point::new().set_values(0.0, 4.0)])
+    }
+
+    #[test]
+    fn test_traced_nfp_matches_untraced() {
+        // Arrange
+        let mut trace = NfpTrace::new();
+
+        // Act
+        let traced = calculate_nfp_traced(&l_shape(), &Polygon::square(1.0), 0.0001, &mut trace);
+        let untraced = calculate_nfp(&l_shape(), &Polygon::square(1.0), 0.0001);
+
+        // Assert
+        assert_eq!(traced.len(), untraced.len());
+        for (a, b) in traced.iter().zip(untraced.iter()) {
+            assert_eq!(a.points, b.points);
+        }
+
+        let count = |f: fn(&NfpTraceStep) -> bool| trace.steps.iter().filter(|s| f(s)).count();
+        assert_eq!(count(|s| matches!(s, NfpTraceStep::ConvexPieces{..})), 1);
+        assert_eq!(count(|s| matches!(s, NfpTraceStep::PiecePair{..})), 2);
+        assert_eq!(count(|s| matches!(s, NfpTraceStep::ConvexResult{..})), 2);
+        assert!(matches!(trace.steps.last(), Some(NfpTraceStep::NfpResult{nfp}) if nfp.len() == 2));
+    }
+
+    #[test]
+    fn test_trace_translations_chain_edges() {
+        // Arrange
+        let mut trace = NfpTrace::new();
+        let mut sorted: Vec<LineSegment> = Vec::new();
+        let mut start = Point::new();
+        let mut last_to = Point::new();
+
+        // Act
+        calculate_nfp_traced(&l_shape(), &Polygon::square(1.0), 0.0001, &mut trace);
+
+        // Assert
+        for step in trace.steps.iter() {
+            match step {
+                NfpTraceStep::ConvexStart{start: s, ..} => {
+                    start = *s;
+                    last_to = *s;
+                },
+                NfpTraceStep::SortedEdges{edges} => sorted = edges.clone(),
+                NfpTraceStep::EdgeTranslated{edge, translation} => {
+                    // every sorted edge is moved to the end of the chain
+                    let original = sorted.remove(0);
+                    assert!(edge.from.epsilon_equals(&last_to, 0.0001));
+                    assert!(original.from.copy_along_vector(translation).epsilon_equals(&edge.from, 0.0001));
+                    last_to = edge.to;
+                },
+                NfpTraceStep::ConvexResult{..} => {
+                    assert!(sorted.is_empty());
+                    assert!(last_to.epsilon_equals(&start, 0.0001));
+                },
+                _ => ()
+            }
+        }
+    }
+
+    #[test]
+    fn test_trace_touching_points_are_shared_corners() {
+        // Arrange
+        let mut trace = NfpTrace::new();
+        let square = Polygon::square(1.0);
+
+        // Act
+        calculate_nfp_traced(&l_shape(), &square, 0.0001, &mut trace);
+
+        // Assert
+        let mut touching = 0;
+        for pair in trace.steps.windows(2) {
+            if let [NfpTraceStep::ConvexStart{first, other, start}, NfpTraceStep::TouchingPoints{reference, contacts}] = pair {
+                assert!(reference.epsilon_equals(start, 0.0001));
+                for contact in contacts.iter() {
+                    let on_other = Point::new().set_values(contact.x - start.x, contact.y - start.y);
+                    assert!(first.points.iter().any(|p| p.epsilon_equals(contact, 0.0001)));
+                    assert!(other.points.iter().any(|p| p.epsilon_equals(&on_other, 0.0001)));
+                }
+                touching += 1;
+            }
+        }
+        assert_eq!(touching, 2);
+    }
+
+    #[test]
+    fn test_traced_penetration_matches_untraced() {
+        // Arrange
+        let mut trace = NfpTrace::new();
+        let square = Polygon::square(1.0).copy_along_vector(&Vector::new().set_values(2.0, 0.2));
+
+        // Act
+        let traced = calculate_penetration_traced(&l_shape(), &square, 0.0001, &mut trace);
+        let untraced = calculate_penetration(&l_shape(), &square, 0.0001);
+
+        // Assert
+        assert_eq!(traced, untraced);
+        assert!(trace.steps.iter().any(|s| matches!(s, NfpTraceStep::NfpResult{..})));
+        match (trace.steps.last(), traced) {
+            (Some(NfpTraceStep::Penetration{depth, translation}), PenetrationResult::Overlap(d, t)) => {
+                assert_eq!(*depth, d);
+                assert!(translation.epsilon_equals(&t, 0.0001));
+                assert!(t.epsilon_equals(&Vector::new().set_values(0.0, -0.7), 0.0001));
+            },
+            _ => panic!("Expected an overlap recorded last!")
+        }
+    }
+
+    #[test]
+    fn test_trace_json_and_animation() {
+        // Arrange
+        let mut trace = NfpTrace::new();
+        calculate_nfp_traced(&l_shape(), &Polygon::square(1.0), 0.0001, &mut trace);
+
+        // Act
+        let json: serde_json::Value = serde_json::from_str(&trace.to_json()).unwrap();
+        let mut writer = SvgWriter::new(SvgStyles::default());
+        writer.add_nfp_trace_animation(&trace, 0.5);
+        let svg = writer.render();
+
+        // Assert
+        let steps = json["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), trace.steps.len());
+        assert_eq!(steps[0]["step"], "convex_pieces");
+        assert_eq!(steps[0]["first"].as_array().unwrap().len(), 2);
+        assert_eq!(steps[1]["step"], "piece_pair");
+        assert_eq!(steps[2]["start"].as_array().unwrap().len(), 2);
+        assert_eq!(steps[3]["step"], "touching_points");
+        assert_eq!(steps[3]["contacts"].as_array().unwrap().len(), 1);
+        assert_eq!(svg.matches("<set ").count(), trace.steps.len());
+        assert_eq!(svg.matches("fill=\"freeze\"").count(), 1);
+    }
+}
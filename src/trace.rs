@@ -0,0 +1,99 @@
+use super::geometry::{Point, Polygon, LineSegment, Vector};
+use super::io::json::JsonValue;
+
+/// A single recorded step of an nfp calculation
+#[derive(Debug, Clone)]
+pub enum NfpTraceStep {
+    ConvexPieces{first: Vec<Polygon>, other: Vec<Polygon>}, // decomposition of both inputs, counter-clockwise
+    PiecePair{first_index: usize, other_index: usize}, // start of the nfp of a pair of convex pieces
+    ConvexStart{first: Polygon, other: Polygon, start: Point}, // oriented inputs, other touches first with its reference point at start
+    TouchingPoints{reference: Point, contacts: Vec<Point>}, // points where other touches first with its reference point at reference
+    SortedEdges{edges: Vec<LineSegment>}, // edges of first and of reversed other, sorted by angle
+    EdgeTranslated{edge: LineSegment, translation: Vector}, // sorted edge moved by translation to continue the nfp boundary
+    ConvexResult{nfp: Polygon},
+    PieceDropped{first_index: usize, other_index: usize}, // degenerate pair nfp, trimmed from the result
+    NfpResult{nfp: Vec<Polygon>},
+    Penetration{depth: f64, translation: Vector} // minimal translation moving other out of first, zero if they do not overlap
+}
+
+/// Opt-in record of how an nfp was built, filled by the `_traced` variants of the nfp and penetration functions
+/// Dump it with `to_json` or render it step by step with `SvgWriter::add_nfp_trace`
+///
+/// # Examples
+///
+/// ```
+/// use rust_nfp::geometry::Polygon;
+/// use rust_nfp::lib::calculate_convex_nfp_traced;
+/// use rust_nfp::trace::{NfpTrace, NfpTraceStep};
+///
+/// let mut trace = NfpTrace::new();
+/// let nfp = calculate_convex_nfp_traced(&Polygon::square(2.0), Polygon::square(1.0), &mut trace);
+///
+/// // start, touching points, sorted edges, one step per edge and the result
+/// assert_eq!(trace.steps.len(), 1 + 1 + 1 + 8 + 1);
+/// match trace.steps.last() {
+///     Some(NfpTraceStep::ConvexResult{nfp: traced}) => assert_eq!(traced.points, nfp.points),
+///     _ => panic!("Expected the result last!")
+/// }
+/// assert!(trace.to_json().starts_with("{\"steps\":[{\"step\":\"convex_start\""));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NfpTrace {
+    pub steps: Vec<NfpTraceStep>
+}
+
+impl NfpTrace {
+    // public constructor for an empty trace
+    pub fn new() -> NfpTrace {
+        NfpTrace{steps: Vec::new()}
+    }
+
+    // public appends a step, called by the traced algorithms
+    pub fn record(&mut self, step: NfpTraceStep) {
+        self.steps.push(step);
+    }
+
+    /// Compact json document `{"steps": [...]}`, every step is an object with its kind in `step`
+    /// Points and vectors are `[x, y]`, polygons lists of points and line segments `[from, to]`
+    pub fn to_json(&self) -> String {
+        JsonValue::Object(vec![("steps".to_string(), JsonValue::Array(self.steps.iter().map(step_json).collect()))]).to_json()
+    }
+}
+
+fn number(n: usize) -> JsonValue {
+    JsonValue::Number(n as f64)
+}
+
+fn point(x: f64, y: f64) -> JsonValue {
+    JsonValue::Array(vec![JsonValue::Number(x), JsonValue::Number(y)])
+}
+
+fn polygon(poly: &Polygon) -> JsonValue {
+    JsonValue::Array(poly.points.iter().map(|p| point(p.x, p.y)).collect())
+}
+
+fn polygons(polys: &[Polygon]) -> JsonValue {
+    JsonValue::Array(polys.iter().map(polygon).collect())
+}
+
+fn segment(seg: &LineSegment) -> JsonValue {
+    JsonValue::Array(vec![point(seg.from.x, seg.from.y), point(seg.to.x, seg.to.y)])
+}
+
+fn step_json(step: &NfpTraceStep) -> JsonValue {
+    let (kind, mut members) = match step {
+        NfpTraceStep::ConvexPieces{first, other} => ("convex_pieces", vec![("first", polygons(first)), ("other", polygons(other))]),
+        NfpTraceStep::PiecePair{first_index, other_index} => ("piece_pair", vec![("first_index", number(*first_index)), ("other_index", number(*other_index))]),
+        NfpTraceStep::ConvexStart{first, other, start} => ("convex_start", vec![("first", polygon(first)), ("other", polygon(other)), ("start", point(start.x, start.y))]),
+        NfpTraceStep::TouchingPoints{reference, contacts} => ("touching_points", vec![("reference", point(reference.x, reference.y)), ("contacts", JsonValue::Array(contacts.iter().map(|p| point(p.x, p.y)).collect()))]),
+        NfpTraceStep::SortedEdges{edges} => ("sorted_edges", vec![("edges", JsonValue::Array(edges.iter().map(segment).collect()))]),
+        NfpTraceStep::EdgeTranslated{edge, translation} => ("edge_translated", vec![("edge", segment(edge)), ("translation", point(translation.x, translation.y))]),
+        NfpTraceStep::ConvexResult{nfp} => ("convex_result", vec![("nfp", polygon(nfp))]),
+        NfpTraceStep::PieceDropped{first_index, other_index} => ("piece_dropped", vec![("first_index", number(*first_index)), ("other_index", number(*other_index))]),
+        NfpTraceStep::NfpResult{nfp} => ("nfp_result", vec![("nfp", polygons(nfp))]),
+        NfpTraceStep::Penetration{depth, translation} => ("penetration", vec![("depth", JsonValue::Number(*depth)), ("translation", point(translation.x, translation.y))])
+    };
+
+    members.insert(0, ("step", JsonValue::String(kind.to_string())));
+    JsonValue::Object(members.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
@@ -0,0 +1,203 @@
+// JavaScript interface of the library, built with the `wasm` feature for `wasm32-unknown-unknown`
+//
+// The module is built with `cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+// and its JavaScript glue is generated by running `wasm-bindgen` on the resulting `.wasm` file.
+//
+// Coordinates are interleaved `x, y` pairs in `Float64Array`s, one array per ring. Outer rings and holes
+// are passed separately, holes are added to sheets and parts by their index. Invalid input, like rings with
+// less than 3 points, non-finite coordinates or indices out of range, throws a JavaScript `Error`
+
+use super::geometry::{Point, Polygon, PolygonWithHoles};
+use super::lib;
+use super::nesting::{Layout, NestingJob, Nester, Part, Placement, Sheet};
+use wasm_bindgen::prelude::*;
+
+fn read_ring(coords: &[f64]) -> Result<Polygon, JsError> {
+    if coords.len() % 2 == 1 {
+        return Err(JsError::new("coordinates have to be x, y pairs"));
+    }
+    if coords.len() < 6 {
+        return Err(JsError::new("a ring needs at least 3 points"));
+    }
+    if coords.iter().any(|v| !v.is_finite()) {
+        return Err(JsError::new("coordinates have to be finite"));
+    }
+
+    Ok(Polygon::from_points(&coords.chunks(2).map(|c| Point::new().set_values(c[0], c[1])).collect()))
+}
+
+fn write_ring(ring: &Polygon) -> Vec<f64> {
+    ring.points.iter().flat_map(|p| vec![p.x, p.y]).collect()
+}
+
+fn out_of_range(what: &str, index: usize) -> JsError {
+    JsError::new(&format!("{} index {} is out of range", what, index))
+}
+
+/// Read-only list of polygon outlines, e.g. the convex pieces of an nfp
+#[wasm_bindgen]
+pub struct PolygonList {
+    polygons: Vec<Polygon>
+}
+
+#[wasm_bindgen]
+impl PolygonList {
+    /// Number of polygons in the list
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// Corners of the polygon at index as interleaved coordinates
+    pub fn get(&self, index: usize) -> Result<Vec<f64>, JsError> {
+        self.polygons.get(index).map(write_ring).ok_or_else(|| out_of_range("polygon", index))
+    }
+}
+
+/// Nfp of two outer rings as convex pieces whose union is the nfp, the reference point of other is its origin
+#[wasm_bindgen(js_name = calculateNfp)]
+pub fn calculate_nfp(first: &[f64], other: &[f64], tol: f64) -> Result<PolygonList, JsError> {
+    Ok(PolygonList{polygons: lib::calculate_nfp(&read_ring(first)?, &read_ring(other)?, tol)})
+}
+
+/// True if the reference point lies strictly inside the nfp, i.e. the polygons overlap
+#[wasm_bindgen(js_name = isInsideNfp)]
+pub fn is_inside_nfp(nfp: &PolygonList, x: f64, y: f64, tol: f64) -> bool {
+    lib::is_inside_nfp(&Point::new().set_values(x, y), &nfp.polygons, tol)
+}
+
+/// Inner fit rectangle of other in the bounding box of container as `[minX, minY, maxX, maxY]`,
+/// `undefined` if other does not fit
+#[wasm_bindgen(js_name = calculateIfp)]
+pub fn calculate_ifp(container: &[f64], other: &[f64], tol: f64) -> Result<Option<Vec<f64>>, JsError> {
+    let ifp = lib::calculate_rectangular_ifp(&read_ring(container)?, &read_ring(other)?, tol);
+    Ok(ifp.map(|b| vec![b.min.x, b.min.y, b.max.x, b.max.y]))
+}
+
+/// Nesting job assembled from sheets and parts, see `NestingJob`
+#[wasm_bindgen(js_name = NestingJob)]
+pub struct WasmNestingJob {
+    job: NestingJob
+}
+
+#[wasm_bindgen(js_class = NestingJob)]
+impl WasmNestingJob {
+    /// Empty job keeping the spacing between placed parts
+    #[wasm_bindgen(constructor)]
+    pub fn new(spacing: f64) -> WasmNestingJob {
+        WasmNestingJob{job: NestingJob{spacing, ..NestingJob::default()}}
+    }
+
+    /// Adds a sheet and returns its index
+    #[wasm_bindgen(js_name = addSheet)]
+    pub fn add_sheet(&mut self, name: &str, outer: &[f64]) -> Result<usize, JsError> {
+        self.job.sheets.push(Sheet::new(name, PolygonWithHoles::from_outer(read_ring(outer)?)));
+        Ok(self.job.sheets.len() - 1)
+    }
+
+    /// Adds a hole to the sheet at index, nothing is placed on it
+    #[wasm_bindgen(js_name = addSheetHole)]
+    pub fn add_sheet_hole(&mut self, sheet: usize, hole: &[f64]) -> Result<(), JsError> {
+        let hole = read_ring(hole)?;
+        let sheet = self.job.sheets.get_mut(sheet).ok_or_else(|| out_of_range("sheet", sheet))?;
+        sheet.shape.holes.push(hole);
+        Ok(())
+    }
+
+    /// Adds a part with its quantity and allowed rotations in radians, no rotations allow only 0
+    #[wasm_bindgen(js_name = addPart)]
+    pub fn add_part(&mut self, name: &str, outer: &[f64], quantity: usize, rotations: &[f64]) -> Result<usize, JsError> {
+        let mut part = Part::new(name, PolygonWithHoles::from_outer(read_ring(outer)?), quantity);
+        if !rotations.is_empty() {
+            part.rotations = rotations.to_vec();
+        }
+        self.job.parts.push(part);
+        Ok(self.job.parts.len() - 1)
+    }
+
+    /// Adds a hole to the part at index
+    #[wasm_bindgen(js_name = addPartHole)]
+    pub fn add_part_hole(&mut self, part: usize, hole: &[f64]) -> Result<(), JsError> {
+        let hole = read_ring(hole)?;
+        let part = self.job.parts.get_mut(part).ok_or_else(|| out_of_range("part", part))?;
+        part.shape.holes.push(hole);
+        Ok(())
+    }
+
+    /// Total number of part copies
+    #[wasm_bindgen(getter = partCount)]
+    pub fn part_count(&self) -> usize {
+        self.job.part_count()
+    }
+
+    /// Nests the job, see `Nester::nest`
+    pub fn nest(&self, tol: f64) -> WasmLayout {
+        WasmLayout{layout: Nester::nest(&self.job, tol)}
+    }
+}
+
+/// Result of a nesting run, placements are accessed by index
+#[wasm_bindgen(js_name = Layout)]
+pub struct WasmLayout {
+    layout: Layout
+}
+
+impl WasmLayout {
+    fn placement(&self, index: usize) -> Result<&Placement, JsError> {
+        self.layout.placements.get(index).ok_or_else(|| out_of_range("placement", index))
+    }
+}
+
+#[wasm_bindgen(js_class = Layout)]
+impl WasmLayout {
+    /// Number of placed part copies
+    #[wasm_bindgen(getter = placementCount)]
+    pub fn placement_count(&self) -> usize {
+        self.layout.placements.len()
+    }
+
+    /// Name of the part placed at index
+    #[wasm_bindgen(js_name = placementPart)]
+    pub fn placement_part(&self, index: usize) -> Result<String, JsError> {
+        Ok(self.placement(index)?.part.clone())
+    }
+
+    /// Index of the sheet the placement at index is on
+    #[wasm_bindgen(js_name = placementSheet)]
+    pub fn placement_sheet(&self, index: usize) -> Result<usize, JsError> {
+        Ok(self.placement(index)?.sheet)
+    }
+
+    /// Counter-clockwise rotation of the placement at index, in radians
+    #[wasm_bindgen(js_name = placementRotation)]
+    pub fn placement_rotation(&self, index: usize) -> Result<f64, JsError> {
+        Ok(self.placement(index)?.rotation)
+    }
+
+    /// Translation of the placement at index as `[x, y]`, applied after the rotation
+    #[wasm_bindgen(js_name = placementTranslation)]
+    pub fn placement_translation(&self, index: usize) -> Result<Vec<f64>, JsError> {
+        let translation = self.placement(index)?.translation;
+        Ok(vec![translation.x, translation.y])
+    }
+
+    /// Outer ring of the placement at index in sheet coordinates
+    #[wasm_bindgen(js_name = placementOutline)]
+    pub fn placement_outline(&self, index: usize) -> Result<Vec<f64>, JsError> {
+        Ok(write_ring(&self.placement(index)?.shape.outer))
+    }
+
+    /// Names of the part copies that fit on no sheet
+    #[wasm_bindgen(getter)]
+    pub fn unplaced(&self) -> Vec<String> {
+        self.layout.unplaced.clone()
+    }
+
+    /// Fraction of the sheet area at index covered by placed parts
+    pub fn utilization(&self, sheet: usize) -> Result<f64, JsError> {
+        if sheet >= self.layout.sheets.len() {
+            return Err(out_of_range("sheet", sheet));
+        }
+        Ok(self.layout.calculate_utilization(sheet))
+    }
+}